@@ -13,16 +13,36 @@ pub struct CliArgs {
         - :<port> - search by port, i.e ':8080'
         - /<path> - search by command path, i.e. '/home/user/bin'
         - -<arg> - search by argument, i.e. '-i'
-        If no prefix is given search will be done by process name"#
+        - %<pod> - search by kubernetes pod 'namespace/pod/container', i.e. '%default/nginx'
+        If no prefix is given search will be done by process name
+        Alternative queries are separated by '|', i.e. ':3000|:8080', '\|' is literal '|'"#
     )]
     pub query: String,
+    /// Starts with query saved under given name in [searches] section of config
+    #[arg(short = 's', long, conflicts_with = "query")]
+    pub search: Option<String>,
     #[command(flatten)]
     pub ignore: IgnoreOptions,
     #[command(flatten)]
     pub screen_size: Option<ScreenSizeOptions>,
+    /// Starts with port check showing which processes hold given port
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub port: Option<u16>,
+    /// Kills every process matching query without starting TUI, protected processes are skipped
+    #[arg(long, default_value_t = false)]
+    pub kill_all: bool,
+    /// Only prints processes that would be killed by --kill-all
+    #[arg(long, default_value_t = false, requires = "kill_all")]
+    pub dry_run: bool,
+    /// Kills processes matched by --kill-all without asking for confirmation
+    #[arg(short = 'y', long, default_value_t = false, requires = "kill_all")]
+    pub yes: bool,
     /// Prints configuration on STDOUT and exists
     #[arg(short = 'P', long, default_value_t = false)]
     pub print_config: bool,
+    /// Prints history of signals sent by pik, oldest first, and exits
+    #[arg(long, default_value_t = false)]
+    pub history: bool,
 }
 
 #[derive(Args, Debug, Clone, Copy)]
@@ -0,0 +1,124 @@
+use std::io::{IsTerminal, Write};
+
+use anyhow::{Result, bail};
+
+use crate::{
+    processes::{
+        BatchKill, KillHistory, OperationResult, Operations, ProcessManager, ProcssAsyncService,
+        ignore_options_for, selects_by_existence_only,
+    },
+    settings::AppSettings,
+};
+
+/// Kills every process matching query like `pkill`, with dry run only matching processes are printed
+/// Kill has to be confirmed on terminal or with `yes`
+pub fn kill_all(settings: AppSettings, dry_run: bool, yes: bool) -> Result<()> {
+    //NOTE: query like '' or '/' matches every process, that is never what user wants to kill
+    if selects_by_existence_only(&settings.query) {
+        bail!("--kill-all requires query, i.e. 'pik --kill-all node'");
+    }
+    let mut process_manager = ProcessManager::new()?;
+    let ignore = ignore_options_for(
+        settings.saved_search.as_deref(),
+        &settings.filter_opions,
+        &settings.saved_searches,
+    );
+    let results = process_manager.find_processes(&settings.query, ignore);
+    let plan = BatchKill::plan(&settings.query, &results, &settings.kill_guard);
+    print_plan(&plan);
+    if dry_run || plan.targets.is_empty() {
+        return Ok(());
+    }
+    if !yes && !confirm_kill(plan.targets.len())? {
+        println!("Nothing was killed");
+        return Ok(());
+    }
+
+    let (ops_sender, results_receiver) = ProcssAsyncService::new(
+        process_manager,
+        settings.filter_opions,
+        settings.kill_options,
+    )
+    .with_saved_searches(settings.saved_searches)
+    .run_as_background_process();
+    ops_sender.send(Operations::KillProcesses {
+        targets: plan.kill_targets(),
+        graceful: true,
+    })?;
+    loop {
+        match results_receiver.recv()? {
+            OperationResult::ProcessesKilled {
+                killed,
+                still_alive,
+                failed,
+                ..
+            } => {
+                for prc in still_alive.iter() {
+                    eprintln!("{} {} is still alive", prc.pid, prc.name);
+                }
+                for (prc, err) in failed.iter() {
+                    eprintln!("{} {} failed: {err}", prc.pid, prc.name);
+                }
+                println!(
+                    "Killed {} of {} processes",
+                    killed.len(),
+                    plan.targets.len()
+                );
+                break;
+            }
+            OperationResult::Error(err) => eprintln!("{err}"),
+            _ => (),
+        }
+    }
+    ops_sender.send(Operations::Shutdown).ok();
+    Ok(())
+}
+
+/// Prints every signal sent by pik, oldest first
+pub fn print_history(history: &KillHistory) -> Result<()> {
+    for entry in history.entries()? {
+        println!("{}", entry.to_line());
+    }
+    Ok(())
+}
+
+// without terminal there is nobody to ask, --yes has to be given then
+fn confirm_kill(count: usize) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        bail!("Refusing to kill {count} processes without confirmation, pass --yes to kill them");
+    }
+    print!("Kill {count} processes? [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(is_confirmed(&answer))
+}
+
+fn is_confirmed(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+fn print_plan(plan: &BatchKill) {
+    for prc in plan.targets.iter() {
+        println!("{} {} ({})", prc.pid, prc.cmd, prc.user_name);
+    }
+    for (prc, reason) in plan.skipped.iter() {
+        println!("{} {} skipped, {reason}", prc.pid, prc.cmd);
+    }
+    println!("{} processes match '{}'", plan.targets.len(), plan.query);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_confirmed;
+
+    #[test]
+    fn should_kill_only_when_confirmed() {
+        for answer in ["y\n", "Y", " yes \n"] {
+            assert!(is_confirmed(answer), "{answer:?}");
+        }
+        for answer in ["", "\n", "n", "no", "yep"] {
+            assert!(!is_confirmed(answer), "{answer:?}");
+        }
+    }
+}
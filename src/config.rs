@@ -22,6 +22,15 @@ pub fn load_app_config() -> Result<AppConfig> {
     }
 }
 
+/// Directory for data pik keeps between runs, i.e. processes suspended by pik
+pub fn state_dir() -> Option<std::path::PathBuf> {
+    directories::ProjectDirs::from("", "", "pik").map(|dirs| {
+        dirs.state_dir()
+            .unwrap_or(dirs.data_local_dir())
+            .to_path_buf()
+    })
+}
+
 fn load_config_from_file(path: &std::path::PathBuf) -> Result<AppConfig> {
     let toml = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to load config from file: {path:?}"))?;
@@ -39,6 +48,24 @@ pub fn parse_config(toml: &str) -> Result<AppConfig> {
         .try_into()
         .context("Failed to deserialize merged config")?;
     config.key_mappings.validate()?;
+    if let Some(name) = config
+        .key_mappings
+        .searches
+        .keys()
+        .find(|name| !config.searches.contains_key(*name))
+    {
+        anyhow::bail!("Key binding set for search '{name}' which is not defined in [searches]");
+    }
+    if let Some(name) = config
+        .key_mappings
+        .copy_templates
+        .keys()
+        .find(|name| !config.copy_templates.contains_key(*name))
+    {
+        anyhow::bail!(
+            "Key binding set for copy template '{name}' which is not defined in [copy_templates]"
+        );
+    }
     Ok(config)
 }
 
@@ -75,6 +102,9 @@ fn deep_merge(base: &mut toml::Value, overlay: toml::Value) {
     }
 }
 
+use std::collections::BTreeMap;
+
+use crate::processes::{CopyTemplate, ProtectedAction};
 use keymappings::KeyMappings;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -86,6 +116,18 @@ pub struct AppConfig {
     pub screen_size: ScreenSize,
     #[serde(default)]
     pub ignore: IgnoreConfig,
+    #[serde(default)]
+    pub kill: KillConfig,
+    #[serde(default)]
+    pub suspend: SuspendConfig,
+    #[serde(default)]
+    pub search: SearchOptionsConfig,
+    // queries saved under a name, i.e. dev-servers = ":3000|:8080"
+    #[serde(default)]
+    pub searches: BTreeMap<String, SearchConfig>,
+    // text copied with values of selected process, i.e. kill = "kill -9 {pid}"
+    #[serde(default)]
+    pub copy_templates: BTreeMap<String, CopyTemplate>,
     pub key_mappings: KeyMappings,
     pub ui: UIConfig,
 }
@@ -139,6 +181,162 @@ impl PartialEq for IgnoreConfig {
 
 impl Eq for IgnoreConfig {}
 
+/// Saved search is either just query or query with its own ignore options
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SearchConfig {
+    Query(String),
+    WithOptions {
+        query: String,
+        #[serde(default)]
+        ignore: IgnoreOverridesConfig,
+    },
+}
+
+impl SearchConfig {
+    pub fn query(&self) -> &str {
+        match self {
+            SearchConfig::Query(query) => query,
+            SearchConfig::WithOptions { query, .. } => query,
+        }
+    }
+}
+
+// options that are not set are taken from [ignore] section
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IgnoreOverridesConfig {
+    // ignored in addition to paths from [ignore] section
+    #[serde(with = "serde_regex", default)]
+    pub paths: Vec<Regex>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub other_users: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<bool>,
+}
+
+impl PartialEq for IgnoreOverridesConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.threads == other.threads
+            && self.other_users == other.other_users
+            && self.paths.iter().map(|r| r.as_str()).collect::<Vec<&str>>()
+                == other
+                    .paths
+                    .iter()
+                    .map(|r| r.as_str())
+                    .collect::<Vec<&str>>()
+    }
+}
+
+impl Eq for IgnoreOverridesConfig {}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KillConfig {
+    #[serde(default = "default_grace_period_ms")]
+    pub grace_period_ms: u64,
+    #[serde(default)]
+    pub confirm: bool,
+    #[serde(default)]
+    pub protected_action: ProtectedAction,
+    #[serde(default)]
+    pub protected: ProtectedConfig,
+    // command used to retry kill when permission is denied, i.e. ["sudo"], empty disables retry
+    #[serde(default = "default_privilege_helper")]
+    pub privilege_helper: Vec<String>,
+    // append every signal sent by pik to history log in state dir
+    #[serde(default = "set_true")]
+    pub history: bool,
+}
+
+const fn default_grace_period_ms() -> u64 {
+    3000
+}
+
+fn default_privilege_helper() -> Vec<String> {
+    vec!["sudo".to_string()]
+}
+
+impl Default for KillConfig {
+    fn default() -> Self {
+        Self {
+            grace_period_ms: default_grace_period_ms(),
+            confirm: false,
+            protected_action: ProtectedAction::default(),
+            protected: ProtectedConfig::default(),
+            privilege_helper: default_privilege_helper(),
+            history: set_true(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SuspendConfig {
+    // keep list of suspended processes in state dir so they can be resumed after pik restart
+    #[serde(default)]
+    pub persist: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchOptionsConfig {
+    // keep queries user acted on in state dir so they can be recalled after pik restart
+    #[serde(default = "set_true")]
+    pub history: bool,
+}
+
+impl Default for SearchOptionsConfig {
+    fn default() -> Self {
+        Self {
+            history: set_true(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProtectedConfig {
+    #[serde(with = "serde_regex", default)]
+    pub patterns: Vec<Regex>,
+    #[serde(default = "set_true")]
+    pub root: bool,
+    #[serde(default = "set_true")]
+    pub init: bool,
+    #[serde(default = "set_true")]
+    pub pik: bool,
+    #[serde(default = "set_true")]
+    pub parent_shell: bool,
+}
+
+impl Default for ProtectedConfig {
+    fn default() -> Self {
+        Self {
+            patterns: vec![],
+            root: set_true(),
+            init: set_true(),
+            pik: set_true(),
+            parent_shell: set_true(),
+        }
+    }
+}
+
+impl PartialEq for ProtectedConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root
+            && self.init == other.init
+            && self.pik == other.pik
+            && self.parent_shell == other.parent_shell
+            && self
+                .patterns
+                .iter()
+                .map(|r| r.as_str())
+                .collect::<Vec<&str>>()
+                == other
+                    .patterns
+                    .iter()
+                    .map(|r| r.as_str())
+                    .collect::<Vec<&str>>()
+    }
+}
+
+impl Eq for ProtectedConfig {}
+
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum ScreenSize {
@@ -159,7 +357,7 @@ mod tests {
 
     use ratatui::{
         crossterm::event::{KeyCode, KeyModifiers},
-        layout::{Alignment, Margin},
+        layout::{Alignment, Constraint, Margin},
         style::{
             Color, Modifier, Style,
             palette::tailwind::{self, SLATE},
@@ -167,8 +365,9 @@ mod tests {
         widgets::{BorderType, TitlePosition},
     };
     use ui::{
-        BorderTheme, CellTheme, NotificationsConfig, NotificationsTheme, ProcessDetailsTheme,
-        RowTheme, ScrollbarTheme, SearchBarTheme, TableTheme, TitleTheme,
+        BorderTheme, CellTheme, ColumnConfig, ColumnId, ColumnWidth, LayoutKind,
+        NotificationsConfig, NotificationsTheme, ProcessDetailsTheme, RowTheme, ScrollbarTheme,
+        SearchBarTheme, TableTheme, TitleTheme,
     };
 
     use crate::config::{
@@ -190,9 +389,32 @@ mod tests {
                     other_users: true,
                     threads: true
                 },
+                kill: KillConfig {
+                    grace_period_ms: 3000,
+                    confirm: false,
+                    protected_action: ProtectedAction::Confirm,
+                    protected: ProtectedConfig {
+                        patterns: vec![],
+                        root: true,
+                        init: true,
+                        pik: true,
+                        parent_shell: true,
+                    },
+                    privilege_helper: vec!["sudo".to_string()],
+                    history: true,
+                },
+                suspend: SuspendConfig::default(),
+                search: SearchOptionsConfig { history: true },
+                searches: BTreeMap::new(),
+                copy_templates: default_copy_templates(),
                 key_mappings: default_config().unwrap().key_mappings,
                 ui: UIConfig {
                     icons: ui::IconConfig::Ascii,
+                    mouse: false,
+                    refresh_on_focus: false,
+                    layout: LayoutKind::Vertical,
+                    details_height: 7,
+                    details_width: 40,
                     process_table: TableTheme {
                         title: TitleTheme {
                             alignment: Alignment::Left,
@@ -228,7 +450,8 @@ mod tests {
                                 vertical: 1,
                                 horizontal: 0,
                             },
-                        }
+                        },
+                        columns: TableTheme::default().columns,
                     },
                     process_details: ProcessDetailsTheme {
                         title: TitleTheme {
@@ -292,13 +515,49 @@ mod tests {
             other_users = false
             threads = false
 
+            [kill]
+            grace_period_ms = 500
+            confirm = true
+            protected_action = "refuse"
+            privilege_helper = ["doas"]
+            history = false
+
+            [suspend]
+            persist = true
+
+            [search]
+            history = false
+
+            [kill.protected]
+            patterns = ["zsh$"]
+            root = false
+
+            [searches]
+            dev-servers = ":3000|:5173|:8080"
+            all-java = { query = "java", ignore = { other_users = false, paths = ["/opt/ide/.*"] } }
+
+            [copy_templates]
+            kill = "kill -TERM {pid}"
+            cwd = "cd {cwd}"
+
             [key_mappings]
             quit = ["ctrl+c", "alt+c"]
             close = ["enter"]
 
+            [key_mappings.searches]
+            dev-servers = ["alt+1"]
+
+            [key_mappings.copy_templates]
+            cwd = ["alt+2"]
+
             [ui]
             use_icons = true
             icons = "nerd_font_v3"
+            mouse = true
+            refresh_on_focus = true
+            layout = "horizontal"
+            details_height = 10
+            details_width = 50
 
             [ui.process_table.title]
             alignment = "right"
@@ -326,6 +585,14 @@ mod tests {
             end_symbol = "q"
             margin = {horizontal = 10, vertical = 20}
 
+            [[ui.process_table.columns]]
+            id = "pid"
+            width = "8"
+            alignment = "right"
+
+            [[ui.process_table.columns]]
+            id = "cmd"
+
             [ui.process_details.title]
             alignment = "center"
             position = "bottom"
@@ -375,6 +642,20 @@ mod tests {
             ],
         );
         key_mappings.insert(AppAction::Close, vec![KeyBinding::key(KeyCode::Enter)]);
+        key_mappings.searches.insert(
+            "dev-servers".to_string(),
+            vec![KeyBinding::char_with_mod('1', KeyModifiers::ALT)],
+        );
+        key_mappings.copy_templates.insert(
+            "cwd".to_string(),
+            vec![KeyBinding::char_with_mod('2', KeyModifiers::ALT)],
+        );
+        let mut copy_templates = default_copy_templates();
+        copy_templates.insert(
+            "kill".to_string(),
+            CopyTemplate::parse("kill -TERM {pid}").unwrap(),
+        );
+        copy_templates.insert("cwd".to_string(), CopyTemplate::parse("cd {cwd}").unwrap());
         assert_eq!(
             overrided_settings,
             AppConfig {
@@ -384,9 +665,46 @@ mod tests {
                     other_users: false,
                     threads: false
                 },
+                kill: KillConfig {
+                    grace_period_ms: 500,
+                    confirm: true,
+                    protected_action: ProtectedAction::Refuse,
+                    protected: ProtectedConfig {
+                        patterns: vec![Regex::new("zsh$").unwrap()],
+                        root: false,
+                        ..Default::default()
+                    },
+                    privilege_helper: vec!["doas".to_string()],
+                    history: false,
+                },
+                suspend: SuspendConfig { persist: true },
+                search: SearchOptionsConfig { history: false },
+                searches: BTreeMap::from([
+                    (
+                        "all-java".to_string(),
+                        SearchConfig::WithOptions {
+                            query: "java".to_string(),
+                            ignore: IgnoreOverridesConfig {
+                                paths: vec![Regex::new("/opt/ide/.*").unwrap()],
+                                other_users: Some(false),
+                                threads: None,
+                            },
+                        },
+                    ),
+                    (
+                        "dev-servers".to_string(),
+                        SearchConfig::Query(":3000|:5173|:8080".to_string()),
+                    ),
+                ]),
+                copy_templates,
                 key_mappings,
                 ui: UIConfig {
                     icons: ui::IconConfig::NerdFontV3,
+                    mouse: true,
+                    refresh_on_focus: true,
+                    layout: LayoutKind::Horizontal,
+                    details_height: 10,
+                    details_width: 50,
                     process_table: TableTheme {
                         title: TitleTheme {
                             alignment: Alignment::Right,
@@ -431,7 +749,19 @@ mod tests {
                                 vertical: 20,
                                 horizontal: 10
                             }
-                        }
+                        },
+                        columns: vec![
+                            ColumnConfig {
+                                id: ColumnId::Pid,
+                                width: Some(ColumnWidth(Constraint::Length(8))),
+                                alignment: Alignment::Right,
+                            },
+                            ColumnConfig {
+                                id: ColumnId::Cmd,
+                                width: None,
+                                alignment: Alignment::Left,
+                            },
+                        ],
                     },
                     process_details: ProcessDetailsTheme {
                         title: TitleTheme {
@@ -513,4 +843,60 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn should_fail_when_key_is_bound_to_undefined_copy_template() {
+        let err = parse_config(
+            r#"
+            [key_mappings.copy_templates]
+            missing = ["alt+1"]
+            "#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Key binding set for copy template 'missing' which is not defined in [copy_templates]"
+        );
+    }
+
+    #[test]
+    fn should_fail_on_unknown_placeholder_in_copy_template() {
+        let err = parse_config(
+            r#"
+            [copy_templates]
+            name = "{process_name}"
+            "#,
+        )
+        .unwrap_err();
+
+        assert!(format!("{err:#}").contains("Unknown placeholder '{process_name}'"));
+    }
+
+    fn default_copy_templates() -> BTreeMap<String, CopyTemplate> {
+        [
+            ("command", "{cmd_path} {args}"),
+            ("kill", "kill -9 {pid}"),
+            ("ports", "{ports}"),
+        ]
+        .into_iter()
+        .map(|(name, template)| (name.to_string(), CopyTemplate::parse(template).unwrap()))
+        .collect()
+    }
+
+    #[test]
+    fn should_fail_when_key_is_bound_to_undefined_search() {
+        let err = parse_config(
+            r#"
+            [key_mappings.searches]
+            missing = ["alt+1"]
+            "#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Key binding set for search 'missing' which is not defined in [searches]"
+        );
+    }
 }
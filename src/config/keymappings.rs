@@ -1,4 +1,7 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
 
 use itertools::Itertools;
 use ratatui::crossterm::event::*;
@@ -6,6 +9,12 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct KeyMappings {
+    // keys running saved searches, by name of search from [searches] section
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub searches: BTreeMap<String, Vec<KeyBinding>>,
+    // keys copying text of templates from [copy_templates] section
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub copy_templates: BTreeMap<String, Vec<KeyBinding>>,
     #[serde(flatten)]
     pub bindings: HashMap<AppAction, Vec<KeyBinding>>,
 }
@@ -19,6 +28,8 @@ impl Default for KeyMappings {
 impl KeyMappings {
     pub fn new() -> Self {
         Self {
+            searches: BTreeMap::new(),
+            copy_templates: BTreeMap::new(),
             bindings: HashMap::new(),
         }
     }
@@ -43,12 +54,24 @@ impl KeyMappings {
     }
 
     pub(crate) fn validate(&self) -> anyhow::Result<()> {
-        use crate::config::keymappings::{AppAction, KeyBinding};
+        use crate::config::keymappings::KeyBinding;
         use ratatui::crossterm::event::{KeyCode, KeyModifiers};
 
-        let mut used_bindings: HashMap<&KeyBinding, &AppAction> = HashMap::new();
+        let mut used_bindings: HashMap<&KeyBinding, String> = HashMap::new();
+        let actions = self
+            .bindings
+            .iter()
+            .map(|(action, bindings)| (action.to_string(), bindings));
+        let searches = self
+            .searches
+            .iter()
+            .map(|(name, bindings)| (format!("searches.{name}"), bindings));
+        let copy_templates = self
+            .copy_templates
+            .iter()
+            .map(|(name, bindings)| (format!("copy_templates.{name}"), bindings));
 
-        for (action, bindings) in self.bindings.iter() {
+        for (action, bindings) in actions.chain(searches).chain(copy_templates) {
             for binding in bindings.iter() {
                 // Validation 1: Disallow single character keys without modifiers
                 if binding.modifier == KeyModifiers::NONE && matches!(binding.key, KeyCode::Char(_))
@@ -71,7 +94,7 @@ impl KeyMappings {
                         );
                     }
                 } else {
-                    used_bindings.insert(binding, action);
+                    used_bindings.insert(binding, action.clone());
                 }
             }
         }
@@ -92,55 +115,225 @@ impl KeyMappings {
                     None
                 }
             })
+            .or_else(|| resolve_named(&self.searches, event).map(|_| AppAction::RunSearch))
+            .or_else(|| {
+                resolve_named(&self.copy_templates, event).map(|_| AppAction::RunCopyTemplate)
+            })
             .unwrap_or(AppAction::Unmapped)
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
-#[serde(rename_all = "snake_case")]
-// This order is reflected in help popup
-pub enum AppAction {
-    NextItem,
-    PreviousItem,
-    //TODO: consider jump half screen instead
-    JumpTenNextItems,
-    JumpTenPreviousItems,
-    GoToFirstItem,
-    GoToLastItem,
-
-    Close,
-    Quit,
-
-    KillProcess,
-    ForceKillProcess,
-    RefreshProcessList,
-    CopyProcessPid,
-
-    ScrollProcessDetailsDown,
-    ScrollProcessDetailsUp,
-
-    SelectProcessParent,
-    SelectProcessFamily,
-    SelectProcessSiblings,
-
-    ToggleHelp,
-    ToggleDebug,
-
-    CursorLeft,
-    CursorRight,
-    CursorHome,
-    CursorEnd,
-    CursorWordLeft,
-    CursorWordRight,
-    DeleteChar,
-    DeleteNextChar,
-    DeleteWord,
-    DeleteNextWord,
-    DeleteToStart,
-    DeleteToEnd,
-
-    //Special case
-    Unmapped,
+/// Name of entry (saved search, copy template) bound to key
+pub fn resolve_named(named: &BTreeMap<String, Vec<KeyBinding>>, event: KeyEvent) -> Option<&str> {
+    let looking_for = KeyBinding::from(event);
+    named
+        .iter()
+        .find(|(_, bindings)| bindings.contains(&looking_for))
+        .map(|(name, _)| name.as_str())
+}
+
+/// Declares enum along with ALL list of its variants, so no variant can be left out of the list
+macro_rules! with_all_variants {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($variant:ident,)* }) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant,)*
+        }
+
+        impl $name {
+            /// Every variant in declaration order
+            pub const ALL: &[$name] = &[$($name::$variant),*];
+        }
+    };
+}
+pub(crate) use with_all_variants;
+
+with_all_variants! {
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+    #[serde(rename_all = "snake_case")]
+    // This order is reflected in help popup
+    pub enum AppAction {
+        NextItem,
+        PreviousItem,
+        //TODO: consider jump half screen instead
+        JumpTenNextItems,
+        JumpTenPreviousItems,
+        GoToFirstItem,
+        GoToLastItem,
+
+        Close,
+        Quit,
+
+        KillProcess,
+        ForceKillProcess,
+        KillAllMatching,
+        RestartProcess,
+        RefreshProcessList,
+        CopyProcessPid,
+        CopyProcessField,
+        CheckPort,
+        SuspendProcess,
+        ShowSuspended,
+        ShowHistory,
+        ReniceProcess,
+        SetProcessAffinity,
+        ToggleMark,
+
+        ScrollProcessDetailsDown,
+        ScrollProcessDetailsUp,
+        ToggleLayout,
+        GrowDetails,
+        ShrinkDetails,
+
+        SelectProcessParent,
+        SelectProcessFamily,
+        SelectProcessSiblings,
+
+        ToggleHelp,
+        ToggleDebug,
+
+        CursorLeft,
+        CursorRight,
+        CursorHome,
+        CursorEnd,
+        CursorWordLeft,
+        CursorWordRight,
+        DeleteChar,
+        DeleteNextChar,
+        DeleteWord,
+        DeleteNextWord,
+        DeleteToStart,
+        DeleteToEnd,
+        PreviousSearch,
+        NextSearch,
+        ShowSearchHistory,
+
+        ShowSavedSearches,
+        ShowCommandPalette,
+
+        //Special case
+        // key bound to one of saved searches in [key_mappings.searches]
+        RunSearch,
+        // key bound to one of templates in [key_mappings.copy_templates]
+        RunCopyTemplate,
+        Unmapped,
+    }
+}
+
+/// Group of actions in help popup, its order is reflected there
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum ActionCategory {
+    Navigation,
+    ProcessActions,
+    SearchEditing,
+    General,
+}
+
+impl Display for ActionCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ActionCategory::Navigation => "Navigation",
+            ActionCategory::ProcessActions => "Process actions",
+            ActionCategory::SearchEditing => "Search editing",
+            ActionCategory::General => "General",
+        })
+    }
+}
+
+impl AppAction {
+    /// Actions which are not bound in [key_mappings] directly, they are resolved from other keys
+    pub fn is_special(&self) -> bool {
+        matches!(
+            self,
+            AppAction::RunSearch | AppAction::RunCopyTemplate | AppAction::Unmapped
+        )
+    }
+
+    pub fn category(&self) -> ActionCategory {
+        use AppAction::*;
+        match self {
+            NextItem
+            | PreviousItem
+            | JumpTenNextItems
+            | JumpTenPreviousItems
+            | GoToFirstItem
+            | GoToLastItem
+            | ScrollProcessDetailsDown
+            | ScrollProcessDetailsUp
+            | SelectProcessParent
+            | SelectProcessFamily
+            | SelectProcessSiblings => ActionCategory::Navigation,
+            KillProcess | ForceKillProcess | KillAllMatching | RestartProcess
+            | RefreshProcessList | CopyProcessPid | CopyProcessField | CheckPort
+            | SuspendProcess | ShowSuspended | ShowHistory | ReniceProcess | SetProcessAffinity
+            | ToggleMark | RunCopyTemplate => ActionCategory::ProcessActions,
+            CursorLeft | CursorRight | CursorHome | CursorEnd | CursorWordLeft
+            | CursorWordRight | DeleteChar | DeleteNextChar | DeleteWord | DeleteNextWord
+            | DeleteToStart | DeleteToEnd | PreviousSearch | NextSearch | ShowSearchHistory
+            | ShowSavedSearches | RunSearch => ActionCategory::SearchEditing,
+            Close | Quit | ToggleLayout | GrowDetails | ShrinkDetails | ToggleHelp
+            | ToggleDebug | ShowCommandPalette | Unmapped => ActionCategory::General,
+        }
+    }
+
+    /// One line shown in help popup instead of action name
+    pub fn description(&self) -> &'static str {
+        use AppAction::*;
+        match self {
+            NextItem => "Jumps to next item",
+            PreviousItem => "Jumps to previous item",
+            JumpTenNextItems => "Jumps 10 items down",
+            JumpTenPreviousItems => "Jumps 10 items up",
+            GoToFirstItem => "Jumps to first item",
+            GoToLastItem => "Jumps to last item",
+            Close => "Closes the current view",
+            Quit => "Quits the application",
+            KillProcess => "Gracefully kills the selected process (SIGTERM)",
+            ForceKillProcess => "Forcefully kills the selected process (SIGKILL)",
+            KillAllMatching => "Kills every process matching query (preview)",
+            RestartProcess => "Kills selected process and starts it again",
+            RefreshProcessList => "Refreshes the process list",
+            CopyProcessPid => "Copies selected process PID",
+            CopyProcessField => "Picks detail of selected process to copy",
+            CheckPort => "Shows which processes hold a port",
+            SuspendProcess => "Suspends selected process with SIGSTOP",
+            ShowSuspended => "Shows processes suspended by pik to resume them",
+            ShowHistory => "Shows history of signals sent by pik",
+            ReniceProcess => "Changes nice value of marked or selected process",
+            SetProcessAffinity => "Changes CPUs marked or selected process may run on",
+            ToggleMark => "Marks or unmarks selected process",
+            ScrollProcessDetailsDown => "Scrolls details down",
+            ScrollProcessDetailsUp => "Scrolls details up",
+            ToggleLayout => "Switches details below, next to table or hidden",
+            GrowDetails => "Makes details pane bigger",
+            ShrinkDetails => "Makes details pane smaller",
+            SelectProcessParent => "Selects parent process",
+            SelectProcessFamily => "Selects process family",
+            SelectProcessSiblings => "Selects process siblings",
+            ToggleHelp => "Toggles help display",
+            ToggleDebug => "Toggles debug display",
+            CursorLeft => "Moves cursor left",
+            CursorRight => "Moves cursor right",
+            CursorHome => "Moves cursor to line start",
+            CursorEnd => "Moves cursor to line end",
+            CursorWordLeft => "Moves cursor to previous word",
+            CursorWordRight => "Moves cursor to next word",
+            DeleteChar => "Deletes character",
+            DeleteNextChar => "Deletes next character",
+            DeleteWord => "Deletes word",
+            DeleteNextWord => "Deletes next word",
+            DeleteToStart => "Deletes to line start",
+            DeleteToEnd => "Deletes to line end",
+            PreviousSearch => "Recalls previous query from search history",
+            NextSearch => "Recalls next query from search history",
+            ShowSearchHistory => "Shows search history to pick query from",
+            ShowSavedSearches => "Shows searches saved in config",
+            ShowCommandPalette => "Shows all actions to pick one and run it",
+            RunSearch => "Runs saved search bound to key",
+            RunCopyTemplate => "Copies template bound to key",
+            Unmapped => "",
+        }
+    }
 }
 
 impl Display for AppAction {
@@ -308,10 +501,14 @@ fn str_to_key(value: &str) -> Result<KeyCode, String> {
 mod test {
     use std::collections::BTreeMap;
 
+    use itertools::Itertools;
+
     use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
     use crate::config::{
-        keymappings::{AppAction, KeyBinding, KeyMappings, str_to_key, str_to_modifier},
+        keymappings::{
+            AppAction, KeyBinding, KeyMappings, resolve_named, str_to_key, str_to_modifier,
+        },
         parse_config,
     };
 
@@ -543,6 +740,54 @@ toggle_debug = ["ctrl+alt+shift+d"]
         assert!(err.contains("'kill_process'"));
     }
 
+    #[test]
+    fn test_validate_key_mappings_duplicate_binding_of_search_fails() {
+        let mut key_mappings = KeyMappings::new();
+        let duplicate_binding = KeyBinding::char_with_mod('1', KeyModifiers::ALT);
+
+        key_mappings.insert(AppAction::Quit, vec![duplicate_binding]);
+        key_mappings
+            .searches
+            .insert("dev-servers".to_string(), vec![duplicate_binding]);
+
+        let err = key_mappings.validate().unwrap_err().to_string();
+        assert!(err.contains("Duplicate key binding 'alt+1' assigned to actions"));
+        assert!(err.contains("'searches.dev-servers'"));
+    }
+
+    #[test]
+    fn test_resolve_key_bound_to_saved_search() {
+        let mut key_mappings = KeyMappings::new();
+        key_mappings.searches.insert(
+            "dev-servers".to_string(),
+            vec![KeyBinding::char_with_mod('1', KeyModifiers::ALT)],
+        );
+        let event = KeyEvent::new(KeyCode::Char('1'), KeyModifiers::ALT);
+
+        assert_eq!(key_mappings.resolve(event), AppAction::RunSearch);
+        assert_eq!(
+            resolve_named(&key_mappings.searches, event),
+            Some("dev-servers")
+        );
+    }
+
+    #[test]
+    fn test_resolve_key_bound_to_copy_template() {
+        let mut key_mappings = KeyMappings::new();
+        key_mappings.copy_templates.insert(
+            "kill".to_string(),
+            vec![KeyBinding::char_with_mod('9', KeyModifiers::ALT)],
+        );
+        let event = KeyEvent::new(KeyCode::Char('9'), KeyModifiers::ALT);
+
+        assert_eq!(key_mappings.resolve(event), AppAction::RunCopyTemplate);
+        assert_eq!(
+            resolve_named(&key_mappings.copy_templates, event),
+            Some("kill")
+        );
+        assert_eq!(resolve_named(&key_mappings.searches, event), None);
+    }
+
     #[test]
     fn test_validate_key_mappings_duplicate_binding_same_action_ok() {
         let mut key_mappings = KeyMappings::new();
@@ -751,4 +996,17 @@ toggle_debug = ["ctrl+alt+shift+d"]
             AppAction::Unmapped
         );
     }
+
+    #[test]
+    fn should_list_every_action_once() {
+        let key_mappings = crate::config::default_config().unwrap().key_mappings;
+
+        assert!(AppAction::ALL.iter().all_unique());
+        assert!(
+            key_mappings
+                .bindings
+                .keys()
+                .all(|action| AppAction::ALL.contains(action) && !action.is_special())
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use std::sync::OnceLock;
 
 use ratatui::{
-    layout::{Alignment, Margin},
+    layout::{Alignment, Constraint, Margin},
     style::{
         Color, Modifier, Style,
         palette::tailwind::{self, SLATE},
@@ -14,6 +14,20 @@ use serde::{Deserialize, Serialize};
 pub struct UIConfig {
     #[serde(default)]
     pub icons: IconConfig,
+    // mouse capture, terminal text selection needs shift then
+    #[serde(default)]
+    pub mouse: bool,
+    // process list is searched again when terminal gets focus back
+    #[serde(default)]
+    pub refresh_on_focus: bool,
+    #[serde(default)]
+    pub layout: LayoutKind,
+    // rows of details pane below process table
+    #[serde(default = "default_details_height")]
+    pub details_height: u16,
+    // percent of width of details pane next to process table
+    #[serde(default = "default_details_width")]
+    pub details_width: u16,
     #[serde(default)]
     pub process_table: TableTheme,
     #[serde(default)]
@@ -37,12 +51,28 @@ pub struct IconsStruct {
     pub args: String,
     pub ports: String,
     pub search_prompt: String,
+    // marker shown next to PID of process suspended by pik
+    #[serde(default = "default_suspended_icon")]
+    pub suspended: String,
+    // marker shown next to PID of process marked for renice and CPU affinity change
+    #[serde(default = "default_marked_icon")]
+    pub marked: String,
+}
+
+fn default_suspended_icon() -> String {
+    "[S]".to_string()
+}
+
+fn default_marked_icon() -> String {
+    "[*]".to_string()
 }
 
 impl IconsStruct {
     pub fn ascii() -> Self {
         Self {
             search_prompt: ">".to_string(),
+            suspended: default_suspended_icon(),
+            marked: default_marked_icon(),
             ..Default::default()
         }
     }
@@ -58,6 +88,28 @@ impl IconsStruct {
             args: "󱃼".to_string(),
             ports: "".to_string(),
             search_prompt: "".to_string(),
+            suspended: "".to_string(),
+            marked: "".to_string(),
+        }
+    }
+}
+
+/// Where process details are shown relative to process table
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutKind {
+    #[default]
+    Vertical,
+    Horizontal,
+    DetailsHidden,
+}
+
+impl LayoutKind {
+    pub fn next(self) -> Self {
+        match self {
+            LayoutKind::Vertical => LayoutKind::Horizontal,
+            LayoutKind::Horizontal => LayoutKind::DetailsHidden,
+            LayoutKind::DetailsHidden => LayoutKind::Vertical,
         }
     }
 }
@@ -85,7 +137,7 @@ impl IconConfig {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct TableTheme {
     #[serde(default)]
     pub title: TitleTheme,
@@ -97,6 +149,193 @@ pub struct TableTheme {
     pub cell: CellTheme,
     #[serde(default)]
     pub scrollbar: ScrollbarTheme,
+    // columns in order they are rendered, columns that are not listed are not rendered
+    #[serde(default = "default_columns")]
+    pub columns: Vec<ColumnConfig>,
+}
+
+impl Default for TableTheme {
+    fn default() -> Self {
+        Self {
+            title: TitleTheme::default(),
+            border: BorderTheme::default(),
+            row: RowTheme::default(),
+            cell: CellTheme::default(),
+            scrollbar: ScrollbarTheme::default(),
+            columns: default_columns(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnId {
+    User,
+    Pid,
+    Ppid,
+    Time,
+    Cmd,
+    Path,
+    Args,
+    Ports,
+    Cpu,
+    Mem,
+    State,
+    Cwd,
+    Container,
+}
+
+impl ColumnId {
+    pub fn header(&self, icons: &IconsStruct) -> String {
+        let (name, icon) = match self {
+            ColumnId::User => ("USER", icons.user.as_str()),
+            ColumnId::Pid => ("PID", icons.pid.as_str()),
+            ColumnId::Ppid => ("PARENT", icons.parent.as_str()),
+            ColumnId::Time => ("TIME", icons.time.as_str()),
+            ColumnId::Cmd => ("CMD", icons.cmd.as_str()),
+            ColumnId::Path => ("PATH", icons.path.as_str()),
+            ColumnId::Args => ("ARGS", icons.args.as_str()),
+            ColumnId::Ports => ("PORTS", icons.ports.as_str()),
+            ColumnId::Cpu => ("CPU%", ""),
+            ColumnId::Mem => ("MEM", ""),
+            ColumnId::State => ("STATE", ""),
+            ColumnId::Cwd => ("CWD", ""),
+            ColumnId::Container => ("CONTAINER", ""),
+        };
+        format!("{name} {icon}").trim().to_string()
+    }
+
+    //NOTE: short values get fixed width, so wide terminals give the space to long ones
+    pub fn default_width(&self) -> Constraint {
+        match self {
+            ColumnId::User => Constraint::Length(12),
+            ColumnId::Pid => Constraint::Length(10),
+            ColumnId::Ppid => Constraint::Length(8),
+            ColumnId::Time => Constraint::Length(10),
+            ColumnId::Cmd => Constraint::Fill(2),
+            ColumnId::Path => Constraint::Fill(6),
+            ColumnId::Args => Constraint::Fill(5),
+            ColumnId::Ports => Constraint::Fill(3),
+            ColumnId::Cpu => Constraint::Length(6),
+            ColumnId::Mem => Constraint::Length(8),
+            ColumnId::State => Constraint::Length(9),
+            ColumnId::Cwd => Constraint::Fill(4),
+            ColumnId::Container => Constraint::Fill(3),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ColumnConfig {
+    pub id: ColumnId,
+    // when not set, default width of column is used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<ColumnWidth>,
+    #[serde(default, with = "AlignmentDef")]
+    pub alignment: Alignment,
+}
+
+impl ColumnConfig {
+    pub fn new(id: ColumnId) -> Self {
+        Self {
+            id,
+            width: Some(ColumnWidth(id.default_width())),
+            alignment: Alignment::Left,
+        }
+    }
+
+    pub fn constraint(&self) -> Constraint {
+        self.width
+            .map(|width| width.0)
+            .unwrap_or_else(|| self.id.default_width())
+    }
+}
+
+fn default_columns() -> Vec<ColumnConfig> {
+    [
+        ColumnId::User,
+        ColumnId::Pid,
+        ColumnId::Ppid,
+        ColumnId::Time,
+        ColumnId::Cmd,
+        ColumnId::Path,
+        ColumnId::Args,
+        ColumnId::Ports,
+    ]
+    .into_iter()
+    .map(ColumnConfig::new)
+    .collect()
+}
+
+/// Column width written as 12 or "12" (cells), "20%", "min:8", "max:30" or "fill:2" (share of space left)
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(try_from = "RawColumnWidth", into = "String")]
+pub struct ColumnWidth(pub Constraint);
+
+// number of cells can be written without quotes
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawColumnWidth {
+    Cells(u16),
+    Text(String),
+}
+
+impl TryFrom<RawColumnWidth> for ColumnWidth {
+    type Error = String;
+
+    fn try_from(value: RawColumnWidth) -> Result<Self, Self::Error> {
+        match value {
+            RawColumnWidth::Cells(cells) => Ok(Self(Constraint::Length(cells))),
+            RawColumnWidth::Text(text) => Self::try_from(text),
+        }
+    }
+}
+
+impl TryFrom<String> for ColumnWidth {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || {
+            format!(
+                "Invalid column width '{value}', expected i.e. '12', '20%', 'min:8', 'max:30' or 'fill:2'"
+            )
+        };
+        let number = |n: &str| n.trim().parse::<u16>().map_err(|_| invalid());
+        let value = value.trim();
+        let constraint = if let Some(percentage) = value.strip_suffix('%') {
+            match number(percentage)? {
+                p if p <= 100 => Constraint::Percentage(p),
+                _ => return Err(invalid()),
+            }
+        } else if let Some(min) = value.strip_prefix("min:") {
+            Constraint::Min(number(min)?)
+        } else if let Some(max) = value.strip_prefix("max:") {
+            Constraint::Max(number(max)?)
+        } else if value == "fill" {
+            Constraint::Fill(1)
+        } else if let Some(weight) = value.strip_prefix("fill:") {
+            Constraint::Fill(number(weight)?)
+        } else {
+            Constraint::Length(number(value)?)
+        };
+        Ok(Self(constraint))
+    }
+}
+
+impl From<ColumnWidth> for String {
+    fn from(width: ColumnWidth) -> Self {
+        match width.0 {
+            Constraint::Percentage(p) => format!("{p}%"),
+            Constraint::Min(min) => format!("min:{min}"),
+            Constraint::Max(max) => format!("max:{max}"),
+            Constraint::Fill(weight) => format!("fill:{weight}"),
+            Constraint::Length(length) => length.to_string(),
+            //NOTE: ratio can't be configured, it is never created from config
+            Constraint::Ratio(numerator, denominator) => {
+                format!("{}%", numerator * 100 / denominator.max(1))
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
@@ -323,6 +562,14 @@ pub struct NotificationsConfig {
     pub theme: NotificationsTheme,
 }
 
+const fn default_details_height() -> u16 {
+    7
+}
+
+const fn default_details_width() -> u16 {
+    40
+}
+
 const fn default_notification_timeout_ms() -> u64 {
     2500
 }
@@ -400,6 +647,91 @@ mod tests {
         assert_eq!(config.border._type, BorderType::QuadrantInside);
     }
 
+    #[test]
+    fn should_parse_column_widths() {
+        let toml = r#"
+            [[columns]]
+            id = "pid"
+            width = "7"
+            alignment = "right"
+            [[columns]]
+            id = "cmd"
+            width = "20%"
+            [[columns]]
+            id = "path"
+            width = "min:8"
+            [[columns]]
+            id = "args"
+            width = "max:30"
+            [[columns]]
+            id = "cpu"
+            width = "fill"
+            [[columns]]
+            id = "container"
+        "#;
+
+        let config: TableTheme = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config
+                .columns
+                .iter()
+                .map(|c| c.constraint())
+                .collect::<Vec<_>>(),
+            vec![
+                Constraint::Length(7),
+                Constraint::Percentage(20),
+                Constraint::Min(8),
+                Constraint::Max(30),
+                Constraint::Fill(1),
+                Constraint::Fill(3),
+            ]
+        );
+        assert_eq!(config.columns[0].alignment, Alignment::Right);
+        assert_eq!(config.columns[1].alignment, Alignment::Left);
+    }
+
+    #[test]
+    fn should_reject_invalid_column_width() {
+        for width in ["wide", "120%", "min:", "-5"] {
+            let toml = format!("[[columns]]\nid = \"pid\"\nwidth = \"{width}\"");
+            assert!(toml::from_str::<TableTheme>(&toml).is_err(), "{width}");
+        }
+        assert!(toml::from_str::<TableTheme>("[[columns]]\nid = \"uptime\"").is_err());
+        assert!(toml::from_str::<TableTheme>("[[columns]]\nid = \"pid\"\nwidth = -5").is_err());
+    }
+
+    #[test]
+    fn should_parse_column_width_written_as_number() {
+        let toml = r#"
+            [[columns]]
+            id = "pid"
+            width = 12
+        "#;
+
+        let config: TableTheme = toml::from_str(toml).unwrap();
+
+        assert_eq!(config.columns[0].constraint(), Constraint::Length(12));
+    }
+
+    #[test]
+    fn should_use_default_columns_when_not_configured() {
+        let config: TableTheme = toml::from_str("").unwrap();
+
+        assert_eq!(
+            config.columns.iter().map(|c| c.id).collect::<Vec<_>>(),
+            vec![
+                ColumnId::User,
+                ColumnId::Pid,
+                ColumnId::Ppid,
+                ColumnId::Time,
+                ColumnId::Cmd,
+                ColumnId::Path,
+                ColumnId::Args,
+                ColumnId::Ports,
+            ]
+        );
+    }
+
     #[test]
     fn test_icons_struct_ascii() {
         let icons = IconsStruct::ascii();
@@ -412,6 +744,8 @@ mod tests {
         assert_eq!(icons.path, "".to_string());
         assert_eq!(icons.args, "".to_string());
         assert_eq!(icons.ports, "".to_string());
+        assert_eq!(icons.suspended, "[S]".to_string());
+        assert_eq!(icons.marked, "[*]".to_string());
     }
 
     #[test]
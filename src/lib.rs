@@ -1,4 +1,5 @@
 pub mod args;
+pub mod cli;
 pub mod config;
 pub mod processes;
 pub mod settings;
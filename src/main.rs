@@ -13,6 +13,15 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let settings = AppSettings::from(config, args);
+    if args.history {
+        let kill_options = pik::processes::KillOptions::from(config.kill);
+        return pik::cli::print_history(&kill_options.history);
+    }
+
+    let (kill_all, dry_run, yes) = (args.kill_all, args.dry_run, args.yes);
+    let settings = AppSettings::from(config, args)?;
+    if kill_all {
+        return pik::cli::kill_all(settings, dry_run, yes);
+    }
     start_app(settings)
 }
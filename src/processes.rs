@@ -3,18 +3,40 @@ use std::collections::HashMap;
 use std::time::SystemTime;
 
 use anyhow::{Ok, Result};
+use itertools::Itertools;
 use sysinfo::{Pid, SUPPORTED_SIGNALS, System, Uid, Users};
-use sysinfo::{ProcessRefreshKind, Signal};
+use sysinfo::{ProcessRefreshKind, ProcessStatus, Signal};
 
+mod batch;
 mod container;
+mod copy_template;
 mod daemon;
 mod filters;
+mod history;
+mod kill_error;
+mod pod;
 mod ports;
+mod privileged;
+mod protection;
+mod restart;
+mod scheduling;
+mod suspended;
 mod utils;
 
+pub use batch::BatchKill;
+pub use copy_template::{CopyTemplate, NamedCopyTemplate};
 pub use daemon::*;
-pub use filters::IgnoreOptions;
 pub use filters::SearchBy;
+pub use filters::{IgnoreOptions, SavedSearch, ignore_options_for, selects_by_existence_only};
+pub use history::{HistoryEntry, KillHistory};
+pub use kill_error::KillError;
+pub use pod::PodInfo;
+pub use ports::{PortCheck, PortOwner, UnownedSocket};
+pub use privileged::PrivilegedKill;
+pub use protection::{KillCheck, KillGuard, ProtectedAction};
+pub use restart::RestartCommand;
+pub use scheduling::{Scheduling, SchedulingChange, SchedulingKind};
+pub use suspended::{SuspendedProcess, SuspendedProcesses};
 
 use filters::QueryFilter;
 
@@ -23,16 +45,20 @@ pub struct ProcessManager {
     sys: System,
     users: Users,
     containers: HashMap<u32, String>,
+    pods: PodCache,
+    children: HashMap<u32, usize>,
     process_ports: ProcessPorts,
     current_user_id: Uid,
 }
 
-use crate::processes::ports::ProcessPorts;
+use crate::processes::ports::{PortsInfo, ProcessPorts, find_unowned_sockets};
 
-use self::container::{get_container_pids, kill_container};
+use self::container::{Container, get_container_pids, inspect_containers, kill_container};
 use self::filters::IgnoreProcessesFilter;
+use self::pod::PodCache;
 use self::utils::{
-    find_current_process_user, get_process_args, process_run_time, to_system_local_time,
+    find_current_process_user, format_run_time, get_process_args, process_run_time, send_signal,
+    to_system_local_time,
 };
 
 pub trait ProcessInfo {
@@ -55,6 +81,12 @@ pub trait ProcessInfo {
     fn run_time(&self) -> u64;
 
     fn args(&self) -> Vec<&str>;
+
+    fn cpu_usage(&self) -> f32;
+
+    fn status(&self) -> String;
+
+    fn cwd(&self) -> Option<&str>;
 }
 
 impl ProcessInfo for sysinfo::Process {
@@ -97,6 +129,18 @@ impl ProcessInfo for sysinfo::Process {
     fn args(&self) -> Vec<&str> {
         self.cmd().iter().filter_map(|a| a.to_str()).collect()
     }
+
+    fn cpu_usage(&self) -> f32 {
+        self.cpu_usage()
+    }
+
+    fn status(&self) -> String {
+        self.status().to_string()
+    }
+
+    fn cwd(&self) -> Option<&str> {
+        self.cwd().and_then(|cwd| cwd.to_str())
+    }
 }
 
 #[derive(Debug)]
@@ -136,21 +180,28 @@ impl ProcessManager {
     pub fn new() -> Result<Self> {
         let mut sys = System::new();
         let mut users = Users::new_with_refreshed_list();
-        let process_ports = optimized_refresh(&mut sys, &mut users);
+        //NOTE: container inspection errors are reported on next refresh
+        let (process_ports, containers, _) = optimized_refresh(&mut sys, &mut users);
         let current_user_id = find_current_process_user(&sys)?;
-        let containers = get_container_pids();
+        let containers = get_container_pids(&containers);
+        let mut pods = PodCache::default();
+        //NOTE: pod lookup errors are kept, they are reported on next refresh too
+        pods.refresh(process_start_times(&sys)).ok();
+        let children = count_children(&sys);
 
         Ok(Self {
             sys,
             users,
             containers,
+            pods,
+            children,
             process_ports,
             current_user_id,
         })
     }
 
     pub fn find_processes(&mut self, query: &str, ignore: &IgnoreOptions) -> ProcessSearchResults {
-        let query_filter = QueryFilter::new(query);
+        let query_filters = QueryFilter::alternatives(query);
         let ignored_processes_filter = IgnoreProcessesFilter::new(ignore, &self.current_user_id);
 
         let mut items = self
@@ -160,7 +211,15 @@ impl ProcessManager {
             .filter(|prc| ignored_processes_filter.accept(*prc))
             .filter_map(|prc| {
                 let ports = self.process_ports.get(&prc.pid().as_u32());
-                let match_data = query_filter.accept(prc, ports.map(|p| p.as_str()))?;
+                let pod = self
+                    .pods
+                    .get(&prc.pid().as_u32())
+                    .map(PodInfo::display_name);
+                let summary = ports.map(|p| p.summary.as_str());
+                let match_data = query_filters
+                    .iter()
+                    .filter_map(|filter| filter.accept(prc, summary, pod.as_deref()))
+                    .min_by(|a, b| a.match_type.cmp(&b.match_type))?;
                 Some(ResultItem::new(
                     match_data,
                     self.create_process_info(prc, ports),
@@ -173,12 +232,40 @@ impl ProcessManager {
         ProcessSearchResults { items }
     }
 
-    pub fn refresh(&mut self) {
-        self.process_ports = optimized_refresh(&mut self.sys, &mut self.users);
-        self.containers = get_container_pids();
+    /// Error is returned when containers could not be inspected or pods named, everything else is refreshed anyway.
+    pub fn refresh(&mut self) -> Result<()> {
+        let (process_ports, containers, inspection) =
+            optimized_refresh(&mut self.sys, &mut self.users);
+        self.process_ports = process_ports;
+        self.containers = get_container_pids(&containers);
+        let pods = self.pods.refresh(process_start_times(&self.sys));
+        self.children = count_children(&self.sys);
+        combine_errors([inspection, pods])
+    }
+
+    /// Finds processes (and published container ports) bound to port, ignore options are not applied
+    /// as port may be held by any user's process
+    pub fn check_port(&self, port: u16) -> PortCheck {
+        let owners = self
+            .process_ports
+            .bindings_on_port(port)
+            .filter_map(|binding| {
+                let prc = self.sys.process(Pid::from_u32(binding.pid))?;
+                Some(PortOwner {
+                    process: self.create_process_info(prc, self.process_ports.get(&binding.pid)),
+                    binding: binding.to_string(),
+                    state: binding.state.to_string(),
+                })
+            })
+            .collect();
+        PortCheck {
+            port,
+            owners,
+            unowned: find_unowned_sockets(port),
+        }
     }
 
-    fn create_process_info(&self, prc: &impl ProcessInfo, ports: Option<&String>) -> Process {
+    fn create_process_info(&self, prc: &impl ProcessInfo, ports: Option<&PortsInfo>) -> Process {
         let user_name = prc
             .user_id()
             .map(|user_id| {
@@ -191,44 +278,143 @@ impl ProcessManager {
         let cmd = prc.cmd().to_string();
         let cmd_path = prc.cmd_path().map(|p| p.to_string());
         let pid = prc.pid();
-        let process_type = match self.containers.get(&pid) {
-            Some(container_id) => ProcessType::Container {
+        let process_type = match (self.containers.get(&pid), self.pods.get(&pid)) {
+            (Some(container_id), _) => ProcessType::Container {
                 container_id: container_id.clone(),
             },
-            None => ProcessType::Native,
+            (None, Some(pod)) => ProcessType::Pod(pod.clone()),
+            (None, None) => ProcessType::Native,
         };
 
         Process {
             pid,
             parent_pid: prc.parent_id(),
+            children: self.children.get(&pid).copied().unwrap_or_default(),
             args: get_process_args(prc).unwrap_or_default(),
             cmd,
             cmd_path,
             user_name,
-            ports: ports.cloned(),
+            ports: ports.map(|p| p.summary.clone()),
+            port_bindings: ports.map(|p| p.bindings.clone()),
             memory: prc.memory(),
-            start_time: to_system_local_time(prc.start_time())
-                .format("%H:%M:%S")
-                .to_string(),
+            cpu_usage: prc.cpu_usage(),
+            state: prc.status(),
+            cwd: prc.cwd().map(|cwd| cwd.to_string()),
+            start_time: prc.start_time(),
             run_time: process_run_time(prc.run_time(), SystemTime::now()),
             process_type,
         }
     }
 
-    pub fn kill_process(&self, pid: u32, process_type: &ProcessType, graceful: bool) -> bool {
+    pub fn start_time(&self, pid: u32) -> Option<u64> {
+        self.sys
+            .process(Pid::from_u32(pid))
+            .map(|prc| prc.start_time())
+    }
+
+    /// Refreshes single process and checks if it is still alive.
+    /// When start time is given process with different start time is treated as new one that reused PID
+    pub fn is_running(&mut self, pid: u32, start_time: Option<u64>) -> bool {
+        let pid = Pid::from_u32(pid);
+        self.sys.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+        self.sys
+            .process(pid)
+            .is_some_and(|prc| is_alive(prc.status(), prc.start_time(), start_time))
+    }
+
+    pub fn signal_process(&self, pid: u32, signal: Signal) -> Result<(), KillError> {
+        #[cfg(unix)]
+        let sent = send_signal(pid, signal);
+        #[cfg(not(unix))]
+        let sent = send_signal(&self.sys, pid, signal);
+        sent.map_err(|err| KillError::from_io(err, signal))
+    }
+
+    /// Reads exe, arguments and working directory of process so it can be started again.
+    /// None is returned when process is gone or its command line is not accessible
+    pub fn restart_command(&mut self, pid: u32) -> Option<RestartCommand> {
+        let pid = Pid::from_u32(pid);
+        self.sys.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cmd(sysinfo::UpdateKind::Always)
+                .with_exe(sysinfo::UpdateKind::Always)
+                .with_cwd(sysinfo::UpdateKind::Always),
+        );
+        let prc = self.sys.process(pid)?;
+        let argv = prc
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        //NOTE: kernel threads have no command line, there is nothing to start
+        if argv.is_empty() {
+            return None;
+        }
+        Some(RestartCommand::new(
+            prc.exe()?.to_path_buf(),
+            &argv,
+            prc.cwd().map(|cwd| cwd.to_path_buf()),
+        ))
+    }
+
+    pub fn spawn(&self, command: &RestartCommand) -> Result<u32> {
+        command.spawn()
+    }
+
+    pub fn change_scheduling(&self, pid: u32, change: &SchedulingChange) -> Result<()> {
+        scheduling::apply(pid, change)
+    }
+
+    pub fn kill_process(
+        &self,
+        pid: u32,
+        process_type: &ProcessType,
+        graceful: bool,
+    ) -> Result<(), KillError> {
         match process_type {
-            ProcessType::Native => match self.sys.process(Pid::from_u32(pid)) {
-                Some(prc) => {
-                    let signal = determine_kill_signal(graceful);
-                    prc.kill_with(signal).unwrap_or(false)
-                }
-                None => false,
-            },
+            //NOTE: pod processes are killed like native ones, kubelet decides about container restart
+            ProcessType::Native | ProcessType::Pod(_) => {
+                self.signal_process(pid, determine_kill_signal(graceful))
+            }
             ProcessType::Container { container_id } => kill_container(container_id),
         }
     }
 }
 
+//NOTE: on linux threads are listed as children of their process, they are not counted
+fn count_children(sys: &System) -> HashMap<u32, usize> {
+    sys.processes()
+        .values()
+        .filter(|prc| !prc.is_thread())
+        .filter_map(|prc| prc.parent_id())
+        .counts()
+}
+
+// zombies are not alive, they already exited and wait for parent to reap them
+fn is_alive(status: ProcessStatus, start_time: u64, expected_start_time: Option<u64>) -> bool {
+    !matches!(status, ProcessStatus::Zombie | ProcessStatus::Dead)
+        && expected_start_time.is_none_or(|expected| expected == start_time)
+}
+
+/// Unix name of signal, i.e. 'SIGTERM'
+pub fn signal_name(signal: Signal) -> &'static str {
+    match signal {
+        Signal::Term => "SIGTERM",
+        Signal::Kill => "SIGKILL",
+        Signal::Interrupt => "SIGINT",
+        Signal::Hangup => "SIGHUP",
+        Signal::Stop => "SIGSTOP",
+        Signal::Continue => "SIGCONT",
+        _ => "signal",
+    }
+}
+
 fn determine_kill_signal(graceful: bool) -> Signal {
     //windows does not support graceful kill
     if graceful && SUPPORTED_SIGNALS.contains(&Signal::Term) {
@@ -245,31 +431,64 @@ fn process_refresh_kind() -> ProcessRefreshKind {
         .with_cmd(sysinfo::UpdateKind::OnlyIfNotSet)
         .with_exe(sysinfo::UpdateKind::OnlyIfNotSet)
         .with_user(sysinfo::UpdateKind::OnlyIfNotSet)
+        .with_cwd(sysinfo::UpdateKind::OnlyIfNotSet)
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProcessType {
     Native,
     Container { container_id: String },
+    Pod(PodInfo),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Process {
     pub pid: u32,
     pub parent_pid: Option<u32>,
+    pub children: usize,
     pub user_name: String,
     pub cmd: String,
     pub cmd_path: Option<String>,
     pub args: String,
     pub ports: Option<String>,
+    pub port_bindings: Option<String>,
     pub memory: u64,
-    //FIXME: cpu rquires refresh twice!
-    // pub cpu_usage: f32,
-    pub start_time: String,
-    pub run_time: String,
+    //NOTE: cpu usage is computed between two refreshes, it is 0 after the first one
+    pub cpu_usage: f32,
+    pub state: String,
+    pub cwd: Option<String>,
+    // seconds since epoch
+    pub start_time: u64,
+    // seconds process was running at last refresh
+    pub run_time: u64,
     pub process_type: ProcessType,
 }
 
+#[cfg(test)]
+impl Process {
+    /// Native 'node' process of 'user', tests override fields they care about
+    pub fn test(pid: u32) -> Self {
+        Self {
+            pid,
+            parent_pid: None,
+            children: 0,
+            user_name: "user".to_string(),
+            cmd: "node".to_string(),
+            cmd_path: None,
+            args: String::new(),
+            ports: None,
+            port_bindings: None,
+            memory: 0,
+            cpu_usage: 0.0,
+            state: String::new(),
+            cwd: None,
+            start_time: 0,
+            run_time: 0,
+            process_type: ProcessType::Native,
+        }
+    }
+}
+
 impl Process {
     pub fn exe(&self) -> &str {
         self.cmd_path.as_ref().unwrap_or(&self.cmd)
@@ -280,6 +499,28 @@ impl Process {
             .map(|pid| pid.to_string())
             .unwrap_or_default()
     }
+
+    pub fn start_time_as_string(&self) -> String {
+        to_system_local_time(self.start_time)
+            .format("%H:%M:%S")
+            .to_string()
+    }
+
+    /// Run time like '1h 2m 3s'
+    pub fn run_time_as_string(&self) -> String {
+        format_run_time(self.run_time)
+    }
+
+    /// Short container id like docker shows it or pod name
+    pub fn container(&self) -> Option<String> {
+        match &self.process_type {
+            ProcessType::Native => None,
+            ProcessType::Container { container_id } => {
+                Some(container_id.chars().take(12).collect())
+            }
+            ProcessType::Pod(pod) => Some(pod.display_name()),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -324,6 +565,7 @@ pub enum MatchedBy {
     Port,
     Pid,
     ParentPid,
+    Pod,
     ProcessExistence,
 }
 
@@ -367,11 +609,38 @@ impl Ord for MatchType {
     }
 }
 
-/// Refreshes the system information, including processes and their associated ports.
+fn process_start_times(sys: &System) -> impl Iterator<Item = (u32, u64)> + '_ {
+    sys.processes()
+        .values()
+        .map(|prc| (prc.pid().as_u32(), prc.start_time()))
+}
+
+// every failure is reported, one does not hide the other
+fn combine_errors(results: impl IntoIterator<Item = Result<()>>) -> Result<()> {
+    let errors = results
+        .into_iter()
+        .filter_map(Result::err)
+        .map(|err| format!("{err:#}"))
+        .collect::<Vec<_>>();
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(anyhow::anyhow!(errors.join("; "))),
+    }
+}
+
+/// Refreshes the system information, including processes, containers and their associated ports.
 /// This method spawns a separate thread to refresh the ports, as it speeds up the overall refresh process.
 /// It makes overall refreshes ~2x faster (initial refresh is slower though).
-fn optimized_refresh(sys: &mut System, users: &mut Users) -> ProcessPorts {
-    let ports_refresh = std::thread::spawn(ProcessPorts::new_refreshed);
+/// Containers that could be inspected are returned even when inspection of others failed.
+fn optimized_refresh(
+    sys: &mut System,
+    users: &mut Users,
+) -> (ProcessPorts, Vec<Container>, Result<()>) {
+    let ports_refresh = std::thread::spawn(|| {
+        let (containers, inspection) = inspect_containers();
+        let ports = ProcessPorts::new_refreshed(&containers);
+        (ports, containers, inspection)
+    });
     sys.refresh_processes_specifics(
         sysinfo::ProcessesToUpdate::All,
         true,
@@ -379,7 +648,13 @@ fn optimized_refresh(sys: &mut System, users: &mut Users) -> ProcessPorts {
     );
 
     users.refresh();
-    ports_refresh.join().unwrap_or_default()
+    ports_refresh.join().unwrap_or_else(|_| {
+        (
+            ProcessPorts::default(),
+            vec![],
+            Err(anyhow::anyhow!("Ports refresh thread panicked")),
+        )
+    })
 }
 
 #[cfg(test)]
@@ -427,6 +702,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_treat_zombie_and_reused_pid_as_not_alive() {
+        assert!(is_alive(ProcessStatus::Run, 100, Some(100)));
+        assert!(is_alive(ProcessStatus::Sleep, 100, None));
+        assert!(!is_alive(ProcessStatus::Zombie, 100, Some(100)));
+        assert!(!is_alive(ProcessStatus::Run, 200, Some(100)));
+    }
+
+    #[test]
+    fn should_report_every_refresh_failure() {
+        let combined = combine_errors([
+            Err(anyhow::anyhow!("docker inspect failed: no such container")),
+            Ok(()),
+            Err(anyhow::anyhow!(
+                "Pod names are unavailable, crictl ps failed"
+            )),
+        ]);
+
+        assert_eq!(
+            combined.unwrap_err().to_string(),
+            "docker inspect failed: no such container; Pod names are unavailable, crictl ps failed"
+        );
+        assert!(combine_errors([Ok(()), Ok(())]).is_ok());
+    }
+
     #[cfg(target_family = "unix")]
     #[test]
     fn should_determine_correct_kill_signal_for_unix() {
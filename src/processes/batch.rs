@@ -0,0 +1,93 @@
+use super::{KillCheck, KillGuard, KillTarget, Process, ProcessSearchResults};
+
+/// Processes that will be killed when every search result is signaled at once.
+/// Protected processes are never killed in batch, they are listed as skipped instead
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchKill {
+    pub query: String,
+    pub targets: Vec<Process>,
+    // process with reason why it is protected
+    pub skipped: Vec<(Process, String)>,
+}
+
+impl BatchKill {
+    pub fn plan(query: &str, results: &ProcessSearchResults, guard: &KillGuard) -> Self {
+        let mut targets = vec![];
+        let mut skipped = vec![];
+        for prc in results.iter().map(|item| &item.process) {
+            match guard.check(prc) {
+                KillCheck::Allowed | KillCheck::Confirm => targets.push(prc.clone()),
+                KillCheck::ConfirmTyped(reason) | KillCheck::Refused(reason) => {
+                    skipped.push((prc.clone(), reason))
+                }
+            }
+        }
+        Self {
+            query: query.to_string(),
+            targets,
+            skipped,
+        }
+    }
+
+    pub fn kill_targets(&self) -> Vec<KillTarget> {
+        self.targets
+            .iter()
+            .map(|prc| KillTarget {
+                pid: prc.pid,
+                start_time: prc.start_time,
+                process_type: prc.process_type.clone(),
+                name: prc.cmd.clone(),
+                args: prc.args.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::processes::{MatchData, MatchType, MatchedBy, ProcessType, ResultItem};
+
+    use super::*;
+
+    #[test]
+    fn should_skip_protected_processes() {
+        let results = ProcessSearchResults {
+            items: vec![
+                result_item(Process::test(4242)),
+                result_item(Process::test(1)),
+                result_item(Process {
+                    user_name: "root".to_string(),
+                    ..Process::test(4343)
+                }),
+            ],
+        };
+
+        let plan = BatchKill::plan("node", &results, &KillGuard::default());
+
+        assert_eq!(
+            plan.targets.iter().map(|prc| prc.pid).collect::<Vec<_>>(),
+            vec![4242]
+        );
+        assert_eq!(
+            plan.skipped
+                .iter()
+                .map(|(prc, _)| prc.pid)
+                .collect::<Vec<_>>(),
+            vec![1, 4343]
+        );
+        assert_eq!(
+            plan.kill_targets(),
+            vec![KillTarget {
+                pid: 4242,
+                start_time: 0,
+                process_type: ProcessType::Native,
+                name: "node".to_string(),
+                args: String::new(),
+            }]
+        );
+    }
+
+    fn result_item(process: Process) -> ResultItem {
+        ResultItem::new(MatchData::new(MatchedBy::Cmd, MatchType::Exact), process)
+    }
+}
@@ -1,59 +1,140 @@
-use super::utils::create_listener;
+use super::KillError;
+use super::ports::PortBinding;
 
-use listeners::{Listener, Protocol};
-use regex::Regex;
+use anyhow::{Context, Result, anyhow};
+use listeners::{Protocol, SocketState};
+use serde::Deserialize;
 
 use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::process::{Command, Stdio};
 
-pub(super) fn get_container_pids() -> HashMap<u32, String> {
-    let mut container_ids = get_container_ids();
-    if container_ids.is_empty() {
-        return HashMap::new();
-    }
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Container {
+    pub id: String,
+    pub pid: u32,
+    pub ports: Vec<PortBinding>,
+}
 
-    let mut container_pids = HashMap::with_capacity(container_ids.len());
-    let pids = get_process_information_of_containers(&container_ids);
+// NOTE: only fields we need are deserialized, `docker inspect` output is huge
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedContainer {
+    id: String,
+    state: InspectedState,
+    #[serde(default)]
+    network_settings: InspectedNetworkSettings,
+}
 
-    for pid in pids {
-        let pid = pid.parse::<u32>().unwrap();
-        container_pids.insert(pid, container_ids.remove(0));
-    }
+// only ID is read, so stopped containers are not taken for ones that failed to be inspected
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedId {
+    id: String,
+}
 
-    container_pids
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedState {
+    pid: u32,
 }
 
-pub(super) fn kill_container(container_id: &str) -> bool {
-    let output = Command::new("docker")
-        .arg("kill")
-        .arg(container_id)
-        .output()
-        .map_or(String::new(), |output| {
-            String::from_utf8(output.stdout).map_or(String::new(), |val| val)
-        });
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedNetworkSettings {
+    #[serde(default)]
+    ports: HashMap<String, Option<Vec<InspectedPortBinding>>>,
+}
 
-    !output.is_empty()
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedPortBinding {
+    #[serde(default)]
+    host_ip: String,
+    #[serde(default)]
+    host_port: String,
 }
 
-pub(super) fn get_container_ports() -> HashSet<Listener> {
+/// Returns containers that could be inspected, error tells about the ones that could not
+pub(super) fn inspect_containers() -> (Vec<Container>, Result<()>) {
     let container_ids = get_container_ids();
-    let mut container_ports = HashSet::with_capacity(container_ids.len());
-
     if container_ids.is_empty() {
-        return container_ports;
+        return (vec![], Ok(()));
     }
 
-    for line in get_network_information_of_containers(&container_ids) {
-        let Some(listeners) = extract_network_information_from_line(&line) else {
-            continue;
-        };
+    let output = match Command::new("docker")
+        .arg("inspect")
+        .args(["--format", "{{json .}}"])
+        .args(&container_ids)
+        .output()
+        .context("Failed to run docker inspect")
+    {
+        Ok(output) => output,
+        Err(err) => return (vec![], Err(err)),
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (containers, skipped) = parse_inspect_output(&stdout);
 
-        container_ports.extend(listeners);
+    let mut errors = vec![];
+    //NOTE: containers may stop between 'docker ps' and 'docker inspect', inspect fails then
+    // but still prints the ones it found, the rest are reported by ID
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let failed = failed_ids(&container_ids, &stdout);
+        errors.push(match failed.is_empty() {
+            true => format!("docker inspect failed: {}", stderr.trim()),
+            false => format!(
+                "docker inspect failed for containers {}: {}",
+                failed.join(", "),
+                stderr.trim()
+            ),
+        });
+    }
+    if !skipped.is_empty() {
+        errors.push(format!(
+            "Skipped {} containers, docker inspect output could not be parsed: {}",
+            skipped.len(),
+            skipped.join("; ")
+        ));
     }
+    let inspection = match errors.is_empty() {
+        true => Ok(()),
+        false => Err(anyhow!(errors.join("; "))),
+    };
+    (containers, inspection)
+}
+
+pub(super) fn get_container_pids(containers: &[Container]) -> HashMap<u32, String> {
+    containers
+        .iter()
+        .map(|container| (container.pid, container.id.clone()))
+        .collect()
+}
 
-    container_ports
+pub(super) fn get_container_ports(containers: &[Container]) -> Vec<PortBinding> {
+    containers
+        .iter()
+        .flat_map(|container| container.ports.iter().cloned())
+        .collect()
+}
+
+pub(super) fn kill_container(container_id: &str) -> Result<(), KillError> {
+    let output = Command::new("docker")
+        .arg("kill")
+        .arg(container_id)
+        .output()
+        .map_err(|err| KillError::ContainerRuntime {
+            stderr: format!("failed to run docker: {err}"),
+        })?;
+    match output.status.success() {
+        true => Ok(()),
+        false => Err(KillError::ContainerRuntime {
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }),
+    }
 }
 
+// NOTE: failures here are silent on purpose, docker may be not installed or its daemon not running
 fn get_container_ids() -> Vec<String> {
     let container_ids = Command::new("docker")
         .arg("ps")
@@ -72,108 +153,213 @@ fn get_container_ids() -> Vec<String> {
         .collect::<Vec<String>>()
 }
 
-fn get_process_information_of_containers(container_ids: &Vec<String>) -> Vec<String> {
-    let container_information = Command::new("docker")
-        .arg("inspect")
-        .args(["-f", "{{.State.Pid}}"])
-        .args(container_ids)
-        .stderr(Stdio::null())
-        .output()
-        .map_or(String::new(), |output| {
-            String::from_utf8(output.stdout).map_or(String::new(), |val| val)
-        });
+// Every line is separate container, malformed ones are skipped with their errors returned
+fn parse_inspect_output(output: &str) -> (Vec<Container>, Vec<String>) {
+    let mut skipped = vec![];
+    let containers = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(
+            |line| match serde_json::from_str::<InspectedContainer>(line) {
+                Ok(inspected) => to_container(inspected),
+                Err(err) => {
+                    skipped.push(err.to_string());
+                    None
+                }
+            },
+        )
+        .collect();
+    (containers, skipped)
+}
 
-    container_information
-        .split('\n')
-        .map(|information| {
-            information
-                .trim_start_matches(" \"")
-                .trim_end_matches('"')
-                .to_string()
+// Short IDs (like docker shows them) of containers docker inspect printed nothing about
+fn failed_ids(container_ids: &[String], output: &str) -> Vec<String> {
+    let inspected = output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<InspectedId>(line.trim()).ok())
+        .map(|inspected| inspected.id)
+        .collect::<HashSet<_>>();
+    container_ids
+        .iter()
+        .filter(|id| !inspected.contains(*id))
+        .map(|id| id.chars().take(12).collect())
+        .collect()
+}
+
+// Containers that are not running have PID 0 and are skipped
+fn to_container(inspected: InspectedContainer) -> Option<Container> {
+    let pid = inspected.state.pid;
+    if pid == 0 {
+        return None;
+    }
+    let ports = inspected
+        .network_settings
+        .ports
+        .iter()
+        .filter_map(|(container_port, bindings)| {
+            let (container_port, protocol) = parse_container_port(container_port)?;
+            Some(
+                bindings
+                    .iter()
+                    .flatten()
+                    .filter_map(move |binding| {
+                        Some(PortBinding {
+                            pid,
+                            socket: parse_host_addr(binding)?,
+                            protocol,
+                            container_port: Some(container_port),
+                            state: SocketState::Listen,
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            )
         })
-        .filter(|information| !information.is_empty())
-        .collect::<Vec<String>>()
+        .flatten()
+        .collect();
+    Some(Container {
+        id: inspected.id,
+        pid,
+        ports,
+    })
 }
 
-fn get_network_information_of_containers(container_ids: &Vec<String>) -> Vec<String> {
-    let container_information = Command::new("docker")
-        .arg("inspect")
-        .args(["-f", "pid: \'{{.State.Pid}}\';ports: \'{{range $p, $conf := .NetworkSettings.Ports}}\'{{$p}}\'->\'{{(index $conf 0).HostIp}}:{{(index $conf 0).HostPort}}\'{{end}}\';"])
-        .args(container_ids)
-        .stderr(Stdio::null())
-        .output()
-        .map_or(String::new(), |output| String::from_utf8(output.stdout)
-            .map_or(String::new(), |val| val));
+// Port keys look like '80/tcp', protocols that listeners can't represent (i.e. sctp) are skipped
+fn parse_container_port(container_port: &str) -> Option<(u16, Protocol)> {
+    let (port, protocol) = container_port.rsplit_once('/')?;
+    let protocol = match protocol {
+        "tcp" => Protocol::TCP,
+        "udp" => Protocol::UDP,
+        _ => return None,
+    };
+    Some((port.parse().ok()?, protocol))
+}
 
-    container_information
-        .split('\n')
-        .map(|information| {
-            information
-                .trim_start_matches(" '")
-                .trim_end_matches('\'')
-                .to_string()
-        })
-        .filter(|information| !information.is_empty())
-        .collect::<Vec<String>>()
+fn parse_host_addr(binding: &InspectedPortBinding) -> Option<SocketAddr> {
+    let port = binding.host_port.parse::<u16>().ok()?;
+    let ip = match binding.host_ip.as_str() {
+        "" => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        ip => ip.parse().ok()?,
+    };
+    Some(SocketAddr::new(ip, port))
 }
 
-fn extract_network_information_from_line(line: &str) -> Option<Vec<Listener>> {
-    let regex_to_extract_information_from_line =
-        Regex::new(r"(?U)^pid: '(?<pid>\d+)';ports: '(?<ports>.*)';$").unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_inspect_output() {
+        let output = r#"
+{"Id":"abc","State":{"Pid":12801},"NetworkSettings":{"Ports":{"80/tcp":[{"HostIp":"0.0.0.0","HostPort":"8080"},{"HostIp":"::","HostPort":"8080"}],"53/udp":[{"HostIp":"","HostPort":"5353"}],"443/tcp":null}}}
+{"Id":"def","State":{"Pid":12900},"NetworkSettings":{"Ports":{}}}
+"#;
 
-    let line_information = regex_to_extract_information_from_line.captures(line)?;
+        let (mut containers, skipped) = parse_inspect_output(output);
+        assert!(skipped.is_empty());
+        containers[0].ports.sort_by_key(|p| p.socket);
 
-    let pid = line_information
-        .name("pid")
-        .map(|pid| pid.as_str().parse::<u32>().unwrap())
-        .unwrap();
-    let ports = line_information
-        .name("ports")
-        .map(|ports| ports.as_str())
-        .unwrap();
+        assert_eq!(
+            containers,
+            vec![
+                Container {
+                    id: "abc".to_string(),
+                    pid: 12801,
+                    ports: vec![
+                        published(12801, "0.0.0.0:5353", 53, Protocol::UDP),
+                        published(12801, "0.0.0.0:8080", 80, Protocol::TCP),
+                        published(12801, "[::]:8080", 80, Protocol::TCP),
+                    ],
+                },
+                Container {
+                    id: "def".to_string(),
+                    pid: 12900,
+                    ports: vec![],
+                },
+            ]
+        );
+    }
 
-    let regex_to_extract_port_information =
-        Regex::new(r"(?U)'(:?\d+)/(?<protocol>(:?tcp)|(:?udp))'->'(?<addr>.*)'").unwrap();
-    if !regex_to_extract_port_information.is_match(ports) {
-        return None;
+    #[test]
+    fn should_skip_unsupported_protocols_and_stopped_containers() {
+        let output = r#"
+{"Id":"abc","State":{"Pid":12801},"NetworkSettings":{"Ports":{"80/sctp":[{"HostIp":"0.0.0.0","HostPort":"8080"}]}}}
+{"Id":"def","State":{"Pid":0},"NetworkSettings":{"Ports":{}}}
+"#;
+
+        let (containers, _) = parse_inspect_output(output);
+
+        assert_eq!(
+            containers,
+            vec![Container {
+                id: "abc".to_string(),
+                pid: 12801,
+                ports: vec![],
+            }]
+        );
     }
 
-    let mut listeners = Vec::new();
-    for capture in regex_to_extract_port_information.captures_iter(ports) {
-        let protocol = match capture
-            .name("protocol")
-            .map(|protocol| protocol.as_str())
-            .unwrap()
-        {
-            "tcp" => Protocol::TCP,
-            "udp" => Protocol::UDP,
-            protocol => panic!(
-                "Error while mapping {} to protocol. This should not be possible",
-                protocol
-            ),
-        };
-        let addr = capture.name("addr").map(|addr| addr.as_str()).unwrap();
+    #[test]
+    fn should_skip_malformed_lines_of_inspect_output() {
+        let output = r#"
+pid: '12801';ports: '';
+{"Id":"def","State":{"Pid":12900},"NetworkSettings":{"Ports":{}}}
+"#;
 
-        listeners.push(create_listener(pid, addr, protocol));
+        let (containers, skipped) = parse_inspect_output(output);
+
+        assert_eq!(
+            containers,
+            vec![Container {
+                id: "def".to_string(),
+                pid: 12900,
+                ports: vec![],
+            }]
+        );
+        assert_eq!(skipped.len(), 1);
     }
 
-    Some(listeners)
-}
+    #[test]
+    fn should_tell_which_containers_could_not_be_inspected() {
+        let container_ids = ["abc", "5f1e2d3c4b5a69788796a5b4c3d2e1f0", "def"]
+            .map(String::from)
+            .to_vec();
+        let output = r#"
+{"Id":"abc","State":{"Pid":12801},"NetworkSettings":{"Ports":{}}}
+{"Id":"def","State":{"Pid":0}}
+"#;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert_eq!(failed_ids(&container_ids, output), vec!["5f1e2d3c4b5a"]);
+    }
 
     #[test]
-    fn test_extract_network_information_from_line() {
-        let line = "pid: '12801';ports: ''80/tcp'->'0.0.0.0:8080''80/udp'->'0.0.0.0:8080'';";
-        let expected_listeners = vec![
-            create_listener(12801, "0.0.0.0:8080", Protocol::TCP),
-            create_listener(12801, "0.0.0.0:8080", Protocol::UDP),
+    fn should_keep_container_ids_paired_with_pids() {
+        let containers = vec![
+            Container {
+                id: "abc".to_string(),
+                pid: 1,
+                ports: vec![],
+            },
+            Container {
+                id: "def".to_string(),
+                pid: 2,
+                ports: vec![],
+            },
         ];
 
-        let listeners = extract_network_information_from_line(line);
+        let pids = get_container_pids(&containers);
+
+        assert_eq!(pids.get(&1).map(String::as_str), Some("abc"));
+        assert_eq!(pids.get(&2).map(String::as_str), Some("def"));
+    }
 
-        assert_eq!(listeners, Some(expected_listeners));
+    fn published(pid: u32, host: &str, container_port: u16, protocol: Protocol) -> PortBinding {
+        PortBinding {
+            pid,
+            socket: host.parse().unwrap(),
+            protocol,
+            container_port: Some(container_port),
+            state: SocketState::Listen,
+        }
     }
 }
@@ -0,0 +1,238 @@
+use anyhow::{Result, bail};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::Process;
+use crate::config::keymappings::with_all_variants;
+
+with_all_variants! {
+    /// Process value copy template can refer to, i.e. '{pid}'
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Placeholder {
+        Pid,
+        Parent,
+        User,
+        Cmd,
+        CmdPath,
+        Args,
+        Ports,
+        PortBindings,
+        Cwd,
+        State,
+        Container,
+    }
+}
+
+impl Placeholder {
+    fn name(&self) -> &'static str {
+        match self {
+            Placeholder::Pid => "pid",
+            Placeholder::Parent => "parent",
+            Placeholder::User => "user",
+            Placeholder::Cmd => "cmd",
+            Placeholder::CmdPath => "cmd_path",
+            Placeholder::Args => "args",
+            Placeholder::Ports => "ports",
+            Placeholder::PortBindings => "port_bindings",
+            Placeholder::Cwd => "cwd",
+            Placeholder::State => "state",
+            Placeholder::Container => "container",
+        }
+    }
+
+    //NOTE: values process doesn't have (i.e. ports) are rendered as empty text
+    fn value(&self, prc: &Process) -> String {
+        match self {
+            Placeholder::Pid => prc.pid.to_string(),
+            Placeholder::Parent => prc.parent_as_string(),
+            Placeholder::User => prc.user_name.clone(),
+            Placeholder::Cmd => prc.cmd.clone(),
+            Placeholder::CmdPath => prc.exe().to_string(),
+            Placeholder::Args => prc.args.clone(),
+            Placeholder::Ports => prc.ports.clone().unwrap_or_default(),
+            Placeholder::PortBindings => prc.port_bindings.clone().unwrap_or_default(),
+            Placeholder::Cwd => prc.cwd.clone().unwrap_or_default(),
+            Placeholder::State => prc.state.clone(),
+            Placeholder::Container => prc.container().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+/// Text copied to clipboard with process values put in place of placeholders, i.e. "kill -9 {pid}"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CopyTemplate {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl CopyTemplate {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut segments = vec![];
+        let mut text = String::new();
+        let mut chars = source.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                //NOTE: doubled braces are taken literally, like in format! macro
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed {
+                        bail!("Placeholder '{{{name}' is missing '}}' in copy template '{source}'");
+                    }
+                    let Some(placeholder) =
+                        Placeholder::ALL.iter().copied().find(|p| p.name() == name)
+                    else {
+                        bail!(
+                            "Unknown placeholder '{{{name}}}' in copy template '{source}', available ones are: {}",
+                            Placeholder::ALL.iter().map(Placeholder::name).join(", ")
+                        );
+                    };
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Placeholder(placeholder));
+                }
+                '}' => bail!("Unmatched '}}' in copy template '{source}'"),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            segments,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn render(&self, prc: &Process) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Placeholder(placeholder) => placeholder.value(prc),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<String> for CopyTemplate {
+    type Error = anyhow::Error;
+
+    fn try_from(source: String) -> Result<Self> {
+        Self::parse(&source)
+    }
+}
+
+impl From<CopyTemplate> for String {
+    fn from(template: CopyTemplate) -> Self {
+        template.source
+    }
+}
+
+/// Template from [copy_templates] section of config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedCopyTemplate {
+    pub name: String,
+    pub template: CopyTemplate,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_render_process_values() {
+        let template = CopyTemplate::parse("{cmd_path} {args}").unwrap();
+
+        assert_eq!(template.render(&some_process()), "/usr/bin/node server.js");
+        assert_eq!(
+            CopyTemplate::parse("kill -9 {pid}")
+                .unwrap()
+                .render(&some_process()),
+            "kill -9 42"
+        );
+    }
+
+    #[test]
+    fn should_render_missing_values_as_empty_text() {
+        let template = CopyTemplate::parse("ports: {ports}").unwrap();
+
+        assert_eq!(template.render(&some_process()), "ports: ");
+    }
+
+    #[test]
+    fn should_keep_escaped_braces() {
+        let template = CopyTemplate::parse("{{\"pid\": {pid}}}").unwrap();
+
+        assert_eq!(template.render(&some_process()), "{\"pid\": 42}");
+    }
+
+    #[test]
+    fn should_reject_unknown_placeholder() {
+        let err = CopyTemplate::parse("kill {process}")
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            err.starts_with("Unknown placeholder '{process}' in copy template 'kill {process}'")
+        );
+        assert!(CopyTemplate::parse("kill }").is_err());
+    }
+
+    #[test]
+    fn should_reject_unterminated_placeholder() {
+        let err = CopyTemplate::parse("kill -9 {pid").unwrap_err().to_string();
+
+        assert_eq!(
+            err,
+            "Placeholder '{pid' is missing '}' in copy template 'kill -9 {pid'"
+        );
+        assert!(CopyTemplate::parse("{").is_err());
+    }
+
+    #[test]
+    fn should_accept_every_placeholder() {
+        for placeholder in Placeholder::ALL.iter().copied() {
+            let template = CopyTemplate::parse(&format!("{{{}}}", placeholder.name())).unwrap();
+
+            assert_eq!(template.segments, vec![Segment::Placeholder(placeholder)]);
+        }
+    }
+
+    fn some_process() -> Process {
+        Process {
+            parent_pid: Some(1),
+            cmd_path: Some("/usr/bin/node".to_string()),
+            args: "server.js".to_string(),
+            ..Process::test(42)
+        }
+    }
+}
@@ -1,31 +1,87 @@
 use std::{
     collections::VecDeque,
-    sync::mpsc::{Receiver, RecvError, Sender},
+    sync::mpsc::{Receiver, RecvError, RecvTimeoutError, Sender},
+    time::{Duration, Instant},
 };
 
 use anyhow::Result;
+use sysinfo::Signal;
 
-use super::{IgnoreOptions, ProcessManager, ProcessSearchResults, ProcessType};
+use super::{
+    IgnoreOptions, KillError, KillHistory, PortCheck, PrivilegedKill, ProcessManager,
+    ProcessSearchResults, ProcessType, RestartCommand, SavedSearch, SchedulingChange,
+    determine_kill_signal, ignore_options_for,
+};
+
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+// how long process has to disappear after last signal, SIGKILL is not handled by process
+// but kernel may still need a moment to clean it up
+const EXIT_VERIFY_TIMEOUT: Duration = Duration::from_secs(1);
 
 pub struct ProcssAsyncService {
     process_manager: ProcessManager,
     ignore_options: IgnoreOptions,
+    kill_options: KillOptions,
     last_query: String,
+    // name of saved search last query came from, it decides which processes are ignored
+    last_saved_search: Option<String>,
+    saved_searches: Vec<SavedSearch>,
+    pending_kills: Vec<PendingKill>,
+    pending_batch_kills: Vec<PendingBatchKill>,
+    // refresh is done on every search, failure is reported only when it starts
+    refresh_failing: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillOptions {
+    // time process has to exit after SIGTERM before it is killed with SIGKILL, zero disables escalation
+    pub grace_period: Duration,
+    pub history: KillHistory,
+}
+
+impl Default for KillOptions {
+    fn default() -> Self {
+        Self {
+            grace_period: Duration::from_secs(3),
+            history: KillHistory::default(),
+        }
+    }
 }
 
 impl ProcssAsyncService {
-    pub fn new(process_manager: ProcessManager, ignore_options: IgnoreOptions) -> Self {
+    pub fn new(
+        process_manager: ProcessManager,
+        ignore_options: IgnoreOptions,
+        kill_options: KillOptions,
+    ) -> Self {
         Self {
             process_manager,
             ignore_options,
+            kill_options,
             last_query: String::new(),
+            last_saved_search: None,
+            saved_searches: vec![],
+            pending_kills: vec![],
+            pending_batch_kills: vec![],
+            refresh_failing: false,
         }
     }
 
-    pub fn find_processes(&mut self, query: &str) -> ProcessSearchResults {
+    //NOTE: saved searches may ignore different processes, they are picked by name
+    pub fn with_saved_searches(mut self, saved_searches: Vec<SavedSearch>) -> Self {
+        self.saved_searches = saved_searches;
+        self
+    }
+
+    pub fn find_processes(
+        &mut self,
+        query: &str,
+        saved_search: Option<&str>,
+    ) -> ProcessSearchResults {
         self.last_query = query.to_string();
-        self.process_manager
-            .find_processes(query, &self.ignore_options)
+        self.last_saved_search = saved_search.map(str::to_string);
+        let ignore = ignore_options_for(saved_search, &self.ignore_options, &self.saved_searches);
+        self.process_manager.find_processes(query, ignore)
     }
 
     pub fn run_as_background_process(self) -> (Sender<Operations>, Receiver<OperationResult>) {
@@ -37,43 +93,564 @@ impl ProcssAsyncService {
         (operations_sender, result_reveiver)
     }
 
-    fn refresh_and_find_processes(&mut self, query: &str) -> ProcessSearchResults {
-        self.process_manager.refresh();
-        self.find_processes(query)
+    fn refresh_and_find_processes(
+        &mut self,
+        query: &str,
+        saved_search: Option<&str>,
+        result_sender: &Sender<OperationResult>,
+    ) -> ProcessSearchResults {
+        self.refresh(result_sender);
+        self.find_processes(query, saved_search)
     }
 
-    fn rerun_last_search(&mut self) -> ProcessSearchResults {
-        self.process_manager.refresh();
+    fn rerun_last_search(
+        &mut self,
+        result_sender: &Sender<OperationResult>,
+    ) -> ProcessSearchResults {
+        self.refresh(result_sender);
+        let ignore = ignore_options_for(
+            self.last_saved_search.as_deref(),
+            &self.ignore_options,
+            &self.saved_searches,
+        );
         self.process_manager
-            .find_processes(&self.last_query, &self.ignore_options)
+            .find_processes(&self.last_query, ignore)
+    }
+
+    fn kill_process(
+        &mut self,
+        process_type: &ProcessType,
+        graceful: bool,
+        process: KilledProcess,
+        result_sender: &Sender<OperationResult>,
+    ) {
+        self.start_kill(
+            process_type,
+            graceful,
+            process,
+            AfterKill::Report,
+            result_sender,
+        );
+    }
+
+    // command line is read before kill as it is gone with the process
+    fn restart_process(&mut self, process: KilledProcess, result_sender: &Sender<OperationResult>) {
+        let Some(command) = self.process_manager.restart_command(process.pid) else {
+            send_result(
+                OperationResult::Error(format!(
+                    "Can't restart {} : PID {}, its command line is not accessible",
+                    process.name, process.pid
+                )),
+                result_sender,
+            );
+            return;
+        };
+        self.start_kill(
+            &ProcessType::Native,
+            true,
+            process,
+            AfterKill::Restart(command),
+            result_sender,
+        );
+    }
+
+    // Kill is reported in stages, failures are sent as results.
+    // Process is only signaled here, its exit is awaited by poll_pending_kills
+    fn start_kill(
+        &mut self,
+        process_type: &ProcessType,
+        graceful: bool,
+        process: KilledProcess,
+        after_kill: AfterKill,
+        result_sender: &Sender<OperationResult>,
+    ) {
+        let started_at = Instant::now();
+        let pid = process.pid;
+        //NOTE: start time guards against new process that reused PID being taken for killed one
+        let start_time = self.process_manager.start_time(pid);
+        let signal = kill_signal(process_type, graceful);
+        let kill_result = self
+            .process_manager
+            .kill_process(pid, process_type, graceful);
+        self.record(&process, signal, &kill_result, result_sender);
+        if let Err(err) = kill_result {
+            let result = kill_error_result(err, process, signal, start_time, &after_kill);
+            send_result(result, result_sender);
+            return;
+        }
+        let mut kill = PendingKill {
+            process,
+            process_type: process_type.clone(),
+            start_time,
+            signal,
+            stage: KillStage::Verify(started_at + EXIT_VERIFY_TIMEOUT),
+            started_at,
+            after_kill,
+        };
+        //NOTE: containers are not signaled directly, docker kill returns when container is stopped
+        if matches!(process_type, ProcessType::Container { .. }) {
+            self.finish_kill(kill, result_sender);
+            return;
+        }
+        send_result(
+            OperationResult::KillSignalSent {
+                process: kill.process.clone(),
+                signal,
+            },
+            result_sender,
+        );
+        let grace_period = self.kill_options.grace_period;
+        if signal == Signal::Term && !grace_period.is_zero() {
+            kill.stage = KillStage::Grace(started_at + grace_period);
+        }
+        self.pending_kills.push(kill);
+    }
+
+    fn has_pending_kills(&self) -> bool {
+        !self.pending_kills.is_empty() || !self.pending_batch_kills.is_empty()
+    }
+
+    // Checks whether awaited processes exited, it is called between operations
+    // so searches are not blocked while killed processes are awaited
+    fn poll_pending_kills(&mut self, result_sender: &Sender<OperationResult>) {
+        let now = Instant::now();
+        for kill in std::mem::take(&mut self.pending_kills) {
+            if let Some(kill) = self.poll_kill(kill, now, result_sender) {
+                self.pending_kills.push(kill);
+            }
+        }
+        for kill in std::mem::take(&mut self.pending_batch_kills) {
+            if let Some(kill) = self.poll_batch_kill(kill, now, result_sender) {
+                self.pending_batch_kills.push(kill);
+            }
+        }
+    }
+
+    // Returns kill back when process is still awaited
+    fn poll_kill(
+        &mut self,
+        mut kill: PendingKill,
+        now: Instant,
+        result_sender: &Sender<OperationResult>,
+    ) -> Option<PendingKill> {
+        let pid = kill.process.pid;
+        if !self.process_manager.is_running(pid, kill.start_time) {
+            self.finish_kill(kill, result_sender);
+            return None;
+        }
+        match kill.stage {
+            KillStage::Grace(deadline) if now >= deadline => {
+                //NOTE: process may exit right after grace period, then kill fails but that's fine
+                let escalation = self
+                    .process_manager
+                    .kill_process(pid, &kill.process_type, false);
+                self.record(&kill.process, Signal::Kill, &escalation, result_sender);
+                if let Err(err) = escalation
+                    && self.process_manager.is_running(pid, kill.start_time)
+                {
+                    let result = kill_error_result(
+                        err,
+                        kill.process,
+                        Signal::Kill,
+                        kill.start_time,
+                        &kill.after_kill,
+                    );
+                    send_result(result, result_sender);
+                    return None;
+                }
+                kill.signal = Signal::Kill;
+                kill.stage = KillStage::Verify(now + EXIT_VERIFY_TIMEOUT);
+                send_result(
+                    OperationResult::KillEscalated(kill.process.clone()),
+                    result_sender,
+                );
+                Some(kill)
+            }
+            KillStage::Verify(deadline) if now >= deadline => {
+                send_result(
+                    OperationResult::ProcessStillAlive {
+                        process: kill.process,
+                        signal: kill.signal,
+                    },
+                    result_sender,
+                );
+                None
+            }
+            _ => Some(kill),
+        }
+    }
+
+    fn finish_kill(&mut self, kill: PendingKill, result_sender: &Sender<OperationResult>) {
+        let result = match kill.after_kill {
+            AfterKill::Report => OperationResult::ProcessKilled {
+                results: self.rerun_last_search(result_sender),
+                process: kill.process,
+                exited_after: kill.started_at.elapsed(),
+            },
+            AfterKill::Restart(command) => {
+                let spawned = self.process_manager.spawn(&command);
+                let results = self.rerun_last_search(result_sender);
+                match spawned {
+                    Ok(new_pid) => OperationResult::ProcessRestarted {
+                        results,
+                        process: kill.process,
+                        new_pid,
+                    },
+                    Err(err) => OperationResult::ProcessRestartFailed {
+                        results,
+                        process: kill.process,
+                        error: format!("{err:#}"),
+                    },
+                }
+            }
+        };
+        send_result(result, result_sender);
+    }
+
+    fn kill_processes(
+        &mut self,
+        targets: Vec<KillTarget>,
+        graceful: bool,
+        result_sender: &Sender<OperationResult>,
+    ) {
+        let mut failed = vec![];
+        let mut signaled = vec![];
+        for target in targets {
+            let start_time = self.process_manager.start_time(target.pid);
+            let process = KilledProcess {
+                pid: target.pid,
+                name: target.name,
+                args: target.args,
+            };
+            //NOTE: targets were listed before confirmation, their PIDs may be reused meanwhile
+            match start_time {
+                Some(start_time) if start_time == target.start_time => {}
+                Some(_) => {
+                    failed.push((process, KillError::PidReused));
+                    continue;
+                }
+                None => {
+                    failed.push((process, KillError::NoSuchProcess));
+                    continue;
+                }
+            }
+            let kill_result =
+                self.process_manager
+                    .kill_process(target.pid, &target.process_type, graceful);
+            let signal = kill_signal(&target.process_type, graceful);
+            self.record(&process, signal, &kill_result, result_sender);
+            match kill_result {
+                Ok(()) => signaled.push(SignaledProcess {
+                    process,
+                    //NOTE: docker kill returns when container is stopped, there is nothing to wait for
+                    is_container: matches!(target.process_type, ProcessType::Container { .. }),
+                    start_time,
+                }),
+                Err(err) => failed.push((process, err)),
+            }
+        }
+        let sent = signaled
+            .iter()
+            .map(|prc| prc.process.clone())
+            .collect::<Vec<_>>();
+        signaled.retain(|prc| !prc.is_container);
+        let now = Instant::now();
+        let grace_period = self.kill_options.grace_period;
+        let stage = if determine_kill_signal(graceful) == Signal::Term && !grace_period.is_zero() {
+            KillStage::Grace(now + grace_period)
+        } else {
+            KillStage::Verify(now + EXIT_VERIFY_TIMEOUT)
+        };
+        self.pending_batch_kills.push(PendingBatchKill {
+            sent,
+            failed,
+            running: signaled,
+            stage,
+        });
+    }
+
+    // Returns kill back when some of processes are still awaited
+    fn poll_batch_kill(
+        &mut self,
+        mut kill: PendingBatchKill,
+        now: Instant,
+        result_sender: &Sender<OperationResult>,
+    ) -> Option<PendingBatchKill> {
+        kill.running.retain(|prc| {
+            self.process_manager
+                .is_running(prc.process.pid, prc.start_time)
+        });
+        match kill.stage {
+            _ if kill.running.is_empty() => {}
+            KillStage::Grace(deadline) if now >= deadline => {
+                for prc in kill.running.iter() {
+                    //NOTE: process may exit right after grace period, it is verified below anyway
+                    let escalation = self.process_manager.kill_process(
+                        prc.process.pid,
+                        &ProcessType::Native,
+                        false,
+                    );
+                    self.record(&prc.process, Signal::Kill, &escalation, result_sender);
+                }
+                kill.stage = KillStage::Verify(now + EXIT_VERIFY_TIMEOUT);
+                return Some(kill);
+            }
+            KillStage::Verify(deadline) if now >= deadline => {}
+            _ => return Some(kill),
+        }
+        let still_alive = kill
+            .running
+            .into_iter()
+            .map(|prc| prc.process)
+            .collect::<Vec<_>>();
+        let killed = kill
+            .sent
+            .into_iter()
+            .filter(|prc| !still_alive.contains(prc))
+            .collect();
+        let results = self.rerun_last_search(result_sender);
+        send_result(
+            OperationResult::ProcessesKilled {
+                results,
+                killed,
+                still_alive,
+                failed: kill.failed,
+            },
+            result_sender,
+        );
+        None
+    }
+
+    fn signal_process(
+        &mut self,
+        signal: Signal,
+        process: KilledProcess,
+        result_sender: &Sender<OperationResult>,
+    ) {
+        let start_time = self.process_manager.start_time(process.pid);
+        let signal_result = self.process_manager.signal_process(process.pid, signal);
+        self.record(&process, signal, &signal_result, result_sender);
+        let result = match signal_result {
+            Ok(()) => OperationResult::ProcessSignaled {
+                process,
+                signal,
+                start_time,
+            },
+            Err(err) => kill_error_result(err, process, signal, start_time, &AfterKill::Report),
+        };
+        send_result(result, result_sender);
+    }
+
+    fn change_scheduling(
+        &mut self,
+        targets: Vec<KilledProcess>,
+        change: SchedulingChange,
+        result_sender: &Sender<OperationResult>,
+    ) {
+        let mut changed = vec![];
+        let mut failed = vec![];
+        for process in targets {
+            match self.process_manager.change_scheduling(process.pid, &change) {
+                Ok(()) => changed.push(process),
+                Err(err) => failed.push((process, format!("{err:#}"))),
+            }
+        }
+        let result = OperationResult::SchedulingChanged {
+            results: self.rerun_last_search(result_sender),
+            change,
+            changed,
+            failed,
+        };
+        send_result(result, result_sender);
+    }
+
+    // history write failures are reported but never stop the kill
+    fn record(
+        &self,
+        process: &KilledProcess,
+        signal: Signal,
+        result: &Result<(), KillError>,
+        result_sender: &Sender<OperationResult>,
+    ) {
+        let result = match result {
+            Ok(()) => "delivered".to_string(),
+            Err(err) => format!("failed: {err}"),
+        };
+        if let Err(err) = self.kill_options.history.record(process, signal, &result) {
+            send_result(OperationResult::Error(format!("{err:#}")), result_sender);
+        }
+    }
+
+    // Refresh failures are not fatal, search is still done on whatever was refreshed.
+    // Failure is reported once, it is reported again only after refresh succeeded in between
+    fn refresh(&mut self, result_sender: &Sender<OperationResult>) {
+        let refresh = self.process_manager.refresh();
+        if let Err(err) = &refresh
+            && !self.refresh_failing
+        {
+            send_result(OperationResult::Error(format!("{err:#}")), result_sender);
+        }
+        self.refresh_failing = refresh.is_err();
     }
 }
 
 pub enum Operations {
-    Search(String),
+    Search {
+        query: String,
+        // name of saved search query was picked from
+        saved_search: Option<String>,
+    },
     KillProcess {
         pid: u32,
         process_type: ProcessType,
         graceful: bool,
         name: String,
+        args: String,
+    },
+    // kills every target with one operation, processes are signaled first and then awaited together
+    KillProcesses {
+        targets: Vec<KillTarget>,
+        graceful: bool,
+    },
+    CheckPort(u16),
+    // sends single signal without waiting for process to exit, i.e. SIGSTOP or SIGCONT
+    SignalProcess {
+        pid: u32,
+        name: String,
+        args: String,
+        signal: Signal,
+    },
+    // changes nice value of every target (setpriority)
+    Renice {
+        targets: Vec<KilledProcess>,
+        nice: i32,
+    },
+    // changes CPUs every target is allowed to run on (sched_setaffinity)
+    SetAffinity {
+        targets: Vec<KilledProcess>,
+        cpus: Vec<usize>,
+    },
+    // kills process and starts it again with the same command line, only native processes
+    RestartProcess {
+        pid: u32,
+        name: String,
+        args: String,
     },
     Shutdown,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct KillTarget {
+    pub pid: u32,
+    // start time of process when it was listed, process with other start time reused its PID
+    pub start_time: u64,
+    pub process_type: ProcessType,
+    pub name: String,
+    pub args: String,
+}
+
+// process signaled by batch kill which is awaited to exit
+struct SignaledProcess {
+    process: KilledProcess,
+    is_container: bool,
+    start_time: Option<u64>,
+}
+
+// kill of single process which is awaited to exit
+struct PendingKill {
+    process: KilledProcess,
+    process_type: ProcessType,
+    start_time: Option<u64>,
+    // last signal sent to process
+    signal: Signal,
+    stage: KillStage,
+    started_at: Instant,
+    after_kill: AfterKill,
+}
+
+struct PendingBatchKill {
+    sent: Vec<KilledProcess>,
+    failed: Vec<(KilledProcess, KillError)>,
+    running: Vec<SignaledProcess>,
+    stage: KillStage,
+}
+
+enum KillStage {
+    // process has time to exit after SIGTERM, it is sent SIGKILL after deadline
+    Grace(Instant),
+    // process has to disappear before deadline after last signal
+    Verify(Instant),
+}
+
+enum AfterKill {
+    Report,
+    Restart(RestartCommand),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct KilledProcess {
     pub pid: u32,
     pub name: String,
+    pub args: String,
 }
 
 #[derive(Debug)]
 pub enum OperationResult {
+    // kill is reported in stages: signal sent, optional escalation to SIGKILL and final result
+    KillSignalSent {
+        process: KilledProcess,
+        signal: Signal,
+    },
+    // process ignored SIGTERM during grace period and was sent SIGKILL
+    KillEscalated(KilledProcess),
     ProcessKilled {
         results: ProcessSearchResults,
         process: KilledProcess,
+        exited_after: Duration,
+    },
+    // signal was delivered but process did not exit
+    ProcessStillAlive {
+        process: KilledProcess,
+        signal: Signal,
+    },
+    ProcessKillFailed {
+        process: KilledProcess,
+        signal: Signal,
+        error: KillError,
+    },
+    // user is not allowed to signal process, kill may be retried with privilege helper
+    KillPermissionDenied(PrivilegedKill),
+    ProcessesKilled {
+        results: ProcessSearchResults,
+        killed: Vec<KilledProcess>,
+        still_alive: Vec<KilledProcess>,
+        failed: Vec<(KilledProcess, KillError)>,
+    },
+    ProcessSignaled {
+        process: KilledProcess,
+        signal: Signal,
+        start_time: Option<u64>,
+    },
+    ProcessRestarted {
+        results: ProcessSearchResults,
+        process: KilledProcess,
+        new_pid: u32,
+    },
+    // process was killed but could not be started again
+    ProcessRestartFailed {
+        results: ProcessSearchResults,
+        process: KilledProcess,
+        error: String,
+    },
+    // scheduling is changed for every target, the ones it failed for are reported with reason
+    SchedulingChanged {
+        results: ProcessSearchResults,
+        change: SchedulingChange,
+        changed: Vec<KilledProcess>,
+        failed: Vec<(KilledProcess, String)>,
     },
-    ProcessKillFailed(KilledProcess),
     SearchCompleted(ProcessSearchResults),
+    PortChecked(PortCheck),
     Error(String),
 }
 
@@ -83,7 +660,9 @@ fn process_loop(
     result_sender: Sender<OperationResult>,
 ) {
     loop {
-        let operations = receive_operations(&operations_reveiver);
+        //NOTE: killed processes are awaited between operations, so loop wakes up to check them
+        let poll_interval = service.has_pending_kills().then_some(EXIT_POLL_INTERVAL);
+        let operations = receive_operations(&operations_reveiver, poll_interval);
         if let Err(err) = operations {
             send_result(
                 OperationResult::Error(format!("Daemon received error from channel : {err}")),
@@ -93,8 +672,15 @@ fn process_loop(
         }
         for operation in operations.unwrap() {
             match operation {
-                Operations::Search(query) => {
-                    let result = service.refresh_and_find_processes(&query);
+                Operations::Search {
+                    query,
+                    saved_search,
+                } => {
+                    let result = service.refresh_and_find_processes(
+                        &query,
+                        saved_search.as_deref(),
+                        &result_sender,
+                    );
                     send_result(OperationResult::SearchCompleted(result), &result_sender);
                 }
                 Operations::KillProcess {
@@ -102,46 +688,69 @@ fn process_loop(
                     process_type,
                     graceful,
                     name,
+                    args,
                 } => {
-                    let process = KilledProcess { pid, name };
-                    if service
-                        .process_manager
-                        .kill_process(pid, &process_type, graceful)
-                    {
-                        let mut search_results = service.rerun_last_search();
-                        //NOTE: cache refresh takes time and process may reappear in list!
-                        search_results.remove(pid);
-                        send_result(
-                            OperationResult::ProcessKilled {
-                                results: search_results,
-                                process,
-                            },
-                            &result_sender,
-                        );
-                    } else {
-                        send_result(OperationResult::ProcessKillFailed(process), &result_sender);
-                    }
+                    let process = KilledProcess { pid, name, args };
+                    service.kill_process(&process_type, graceful, process, &result_sender);
+                }
+                Operations::KillProcesses { targets, graceful } => {
+                    service.kill_processes(targets, graceful, &result_sender);
+                }
+                Operations::CheckPort(port) => {
+                    service.refresh(&result_sender);
+                    let check = service.process_manager.check_port(port);
+                    send_result(OperationResult::PortChecked(check), &result_sender);
+                }
+                Operations::SignalProcess {
+                    pid,
+                    name,
+                    args,
+                    signal,
+                } => {
+                    let process = KilledProcess { pid, name, args };
+                    service.signal_process(signal, process, &result_sender);
+                }
+                Operations::Renice { targets, nice } => {
+                    let change = SchedulingChange::Nice(nice);
+                    service.change_scheduling(targets, change, &result_sender);
+                }
+                Operations::SetAffinity { targets, cpus } => {
+                    let change = SchedulingChange::Affinity(cpus);
+                    service.change_scheduling(targets, change, &result_sender);
+                }
+                Operations::RestartProcess { pid, name, args } => {
+                    let process = KilledProcess { pid, name, args };
+                    service.restart_process(process, &result_sender);
                 }
                 Operations::Shutdown => {
                     return;
                 }
             }
         }
+        service.poll_pending_kills(&result_sender);
     }
 }
 
-// Receive operations from the channel, coalesce multiple search operations into one
+// Receive operations from the channel, coalesce multiple search operations into one.
+// With poll interval no operations are returned when none arrived in time
 fn receive_operations(
     operations_reveiver: &Receiver<Operations>,
+    poll_interval: Option<Duration>,
 ) -> Result<VecDeque<Operations>, RecvError> {
-    //
-
     let mut stack = VecDeque::new();
-    stack.push_back(operations_reveiver.recv()?);
+    let first_operation = match poll_interval {
+        None => operations_reveiver.recv()?,
+        Some(timeout) => match operations_reveiver.recv_timeout(timeout) {
+            Ok(operation) => operation,
+            Err(RecvTimeoutError::Timeout) => return Ok(stack),
+            Err(RecvTimeoutError::Disconnected) => return Err(RecvError),
+        },
+    };
+    stack.push_back(first_operation);
 
     while let Ok(next_operation) = operations_reveiver.try_recv() {
-        if matches!(&stack.back(), Some(Operations::Search(_)))
-            && matches!(&next_operation, Operations::Search(_))
+        if matches!(&stack.back(), Some(Operations::Search { .. }))
+            && matches!(&next_operation, Operations::Search { .. })
         {
             stack.pop_back();
         }
@@ -150,6 +759,40 @@ fn receive_operations(
     Ok(stack)
 }
 
+//NOTE: docker kill sends SIGKILL to container by default
+fn kill_signal(process_type: &ProcessType, graceful: bool) -> Signal {
+    match process_type {
+        ProcessType::Container { .. } => Signal::Kill,
+        _ => determine_kill_signal(graceful),
+    }
+}
+
+// Privileged retry only signals process, so it is not offered for restart which would be lost
+fn kill_error_result(
+    error: KillError,
+    process: KilledProcess,
+    signal: Signal,
+    start_time: Option<u64>,
+    after_kill: &AfterKill,
+) -> OperationResult {
+    match error {
+        KillError::PermissionDenied if matches!(after_kill, AfterKill::Report) => {
+            OperationResult::KillPermissionDenied(PrivilegedKill {
+                pid: process.pid,
+                start_time,
+                name: process.name,
+                args: process.args,
+                signal,
+            })
+        }
+        error => OperationResult::ProcessKillFailed {
+            process,
+            signal,
+            error,
+        },
+    }
+}
+
 fn send_result(result: OperationResult, result_sender: &Sender<OperationResult>) {
     result_sender
         .send(result)
@@ -160,10 +803,71 @@ fn send_result(result: OperationResult, result_sender: &Sender<OperationResult>)
 mod tests {
     use std::{sync::mpsc::RecvTimeoutError, time::Duration};
 
+    use sysinfo::Signal;
+
     use crate::processes::{
-        IgnoreOptions, ProcessManager, ProcessSearchResults, ProcessType, ProcssAsyncService,
+        IgnoreOptions, KillError, KillOptions, PortCheck, PrivilegedKill, ProcessManager,
+        ProcessSearchResults, ProcessType, ProcssAsyncService, SavedSearch, SchedulingChange,
     };
 
+    #[test]
+    fn find_processes_uses_ignore_options_of_saved_search() {
+        // given
+        let saved_ignore = IgnoreOptions {
+            ignore_other_users: false,
+            ..Default::default()
+        };
+        let mut process_manager = ProcessManager::faux();
+        faux::when!(process_manager.find_processes("java", saved_ignore.clone()))
+            .then(|_| ProcessSearchResults::empty());
+
+        let mut service = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .with_saved_searches(vec![SavedSearch {
+            name: "all-java".to_string(),
+            query: "java".to_string(),
+            ignore: Some(saved_ignore),
+        }]);
+
+        // when
+        let actual = service.find_processes("java", Some("all-java"));
+
+        // then
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn find_processes_uses_global_ignore_options_for_query_typed_by_hand() {
+        // given
+        let mut process_manager = ProcessManager::faux();
+        faux::when!(process_manager.find_processes("java", IgnoreOptions::default()))
+            .then(|_| ProcessSearchResults::empty());
+
+        let mut service = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .with_saved_searches(vec![SavedSearch {
+            name: "all-java".to_string(),
+            query: "java".to_string(),
+            ignore: Some(IgnoreOptions {
+                ignore_other_users: false,
+                ..Default::default()
+            }),
+        }]);
+
+        // when
+        let actual = service.find_processes("java", None);
+
+        // then
+        assert!(actual.is_empty());
+        assert_eq!(service.last_saved_search, None);
+    }
+
     #[test]
     fn find_processes_remembers_last_search() {
         // given
@@ -172,10 +876,14 @@ mod tests {
         faux::when!(process_manager.find_processes("query", ignore_options))
             .then(|_| ProcessSearchResults::empty());
 
-        let mut service = ProcssAsyncService::new(process_manager, IgnoreOptions::default());
+        let mut service = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        );
 
         // when
-        let actual = service.find_processes("query");
+        let actual = service.find_processes("query", None);
 
         // then
         assert_eq!(service.last_query, "query");
@@ -189,12 +897,19 @@ mod tests {
         let mut process_manager = ProcessManager::faux();
         faux::when!(process_manager.find_processes("query", ignore_options))
             .then(|_| ProcessSearchResults::empty());
-        faux::when!(process_manager.refresh()).once().then(|_| {});
+        faux::when!(process_manager.refresh())
+            .once()
+            .then(|_| Ok(()));
 
-        let mut service = ProcssAsyncService::new(process_manager, IgnoreOptions::default());
+        let mut service = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        );
+        let (result_sender, _result_receiver) = std::sync::mpsc::channel();
 
         // when
-        let actual = service.refresh_and_find_processes("query");
+        let actual = service.refresh_and_find_processes("query", None, &result_sender);
 
         // then
         assert_eq!(service.last_query, "query");
@@ -208,13 +923,20 @@ mod tests {
         let mut process_manager = ProcessManager::faux();
         faux::when!(process_manager.find_processes("last_query", ignore_options))
             .then(|_| ProcessSearchResults::empty());
-        faux::when!(process_manager.refresh()).once().then(|_| {});
+        faux::when!(process_manager.refresh())
+            .once()
+            .then(|_| Ok(()));
 
-        let mut service = ProcssAsyncService::new(process_manager, IgnoreOptions::default());
+        let mut service = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        );
         service.last_query = "last_query".to_string();
+        let (result_sender, _result_receiver) = std::sync::mpsc::channel();
 
         // when
-        let actual = service.rerun_last_search();
+        let actual = service.rerun_last_search(&result_sender);
 
         // then
         assert_eq!(service.last_query, "last_query");
@@ -228,18 +950,69 @@ mod tests {
         let mut process_manager = ProcessManager::faux();
         faux::when!(process_manager.find_processes("query", ignore_options))
             .then(|_| ProcessSearchResults::empty());
-        faux::when!(process_manager.refresh()).once().then(|_| {});
+        faux::when!(process_manager.refresh())
+            .once()
+            .then(|_| Ok(()));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::Search {
+                query: "query".to_string(),
+                saved_search: None,
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::SearchCompleted(_)
+        ));
+    }
+
+    #[test]
+    fn should_report_refresh_error_and_still_complete_search() {
+        // given
+        let ignore_options = IgnoreOptions::default();
+        let mut process_manager = ProcessManager::faux();
+        faux::when!(process_manager.find_processes("query", ignore_options))
+            .then(|_| ProcessSearchResults::empty());
+        faux::when!(process_manager.refresh())
+            .once()
+            .then(|_| Err(anyhow::anyhow!("docker inspect failed")));
 
-        let (operation_sender, result_receiver) =
-            ProcssAsyncService::new(process_manager, IgnoreOptions::default())
-                .run_as_background_process();
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
 
         // when
         operation_sender
-            .send(crate::processes::Operations::Search("query".to_string()))
+            .send(crate::processes::Operations::Search {
+                query: "query".to_string(),
+                saved_search: None,
+            })
             .unwrap();
 
         // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::Error(err) if err == "docker inspect failed"
+        ));
         let actual = result_receiver
             .recv_timeout(Duration::from_millis(500))
             .unwrap();
@@ -249,6 +1022,35 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn should_report_refresh_error_once_until_it_clears() {
+        // given
+        let mut process_manager = ProcessManager::faux();
+        let refreshes = std::sync::atomic::AtomicUsize::new(0);
+        faux::when!(process_manager.refresh()).then(move |_| {
+            match refreshes.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+                2 => Ok(()),
+                _ => Err(anyhow::anyhow!("docker inspect failed")),
+            }
+        });
+        let mut service = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        );
+        let (result_sender, result_receiver) = std::sync::mpsc::channel();
+
+        // when
+        (0..4).for_each(|_| service.refresh(&result_sender));
+
+        // then
+        let errors = result_receiver
+            .try_iter()
+            .filter(|result| matches!(result, crate::processes::OperationResult::Error(_)))
+            .count();
+        assert_eq!(errors, 2);
+    }
+
     #[test]
     fn should_handle_background_kill_process_operation() {
         // given
@@ -258,14 +1060,21 @@ mod tests {
         let graceful = true;
         let name = "pik".to_string();
         faux::when!(process_manager.kill_process(pid, &ProcessType::Native, graceful))
-            .then_return(true);
+            .then(|_| Ok(()));
+        faux::when!(process_manager.start_time(pid)).then_return(Some(1));
+        faux::when!(process_manager.is_running(pid, Some(1))).then_return(false);
         faux::when!(process_manager.find_processes("", ignore_options))
             .then(|_| ProcessSearchResults::empty());
-        faux::when!(process_manager.refresh()).once().then(|_| {});
+        faux::when!(process_manager.refresh())
+            .once()
+            .then(|_| Ok(()));
 
-        let (operation_sender, result_receiver) =
-            ProcssAsyncService::new(process_manager, IgnoreOptions::default())
-                .run_as_background_process();
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
 
         // when
         operation_sender
@@ -274,43 +1083,69 @@ mod tests {
                 process_type: ProcessType::Native,
                 graceful,
                 name: name.clone(),
+                args: String::new(),
             })
             .unwrap();
 
         // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::KillSignalSent {
+                process: crate::processes::KilledProcess { pid: 1000, .. },
+                signal: Signal::Term,
+            }
+        ));
         let actual = result_receiver
             .recv_timeout(Duration::from_millis(500))
             .unwrap();
         assert!(matches!(
             actual,
             crate::processes::OperationResult::ProcessKilled {
-                process: crate::processes::KilledProcess { pid: 1000, name: _ },
+                process: crate::processes::KilledProcess { pid: 1000, .. },
                 ..
             }
         ));
     }
 
     #[test]
-    fn should_handle_background_kill_process_fail_operation() {
+    fn should_start_process_again_after_it_exits() {
         // given
+        let ignore_options = IgnoreOptions::default();
         let mut process_manager = ProcessManager::faux();
         let pid = 1000;
-        let graceful = false;
-        let name = "pik".to_string();
-        faux::when!(process_manager.kill_process(pid, &ProcessType::Native, graceful))
-            .then_return(false);
+        let command = crate::processes::RestartCommand::new(
+            "/usr/bin/node".into(),
+            &["node".to_string(), "server.js".to_string()],
+            Some("/app".into()),
+        );
+        let expected_command = command.clone();
+        faux::when!(process_manager.restart_command(pid)).then(move |_| Some(command.clone()));
+        faux::when!(process_manager.kill_process(pid, &ProcessType::Native, true)).then(|_| Ok(()));
+        faux::when!(process_manager.start_time(pid)).then_return(Some(1));
+        faux::when!(process_manager.is_running(pid, Some(1))).then_return(false);
+        faux::when!(process_manager.spawn(expected_command)).then(|_| Ok(2000));
+        faux::when!(process_manager.find_processes("", ignore_options))
+            .then(|_| ProcessSearchResults::empty());
+        faux::when!(process_manager.refresh())
+            .once()
+            .then(|_| Ok(()));
 
-        let (operation_sender, result_receiver) =
-            ProcssAsyncService::new(process_manager, IgnoreOptions::default())
-                .run_as_background_process();
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
 
         // when
         operation_sender
-            .send(crate::processes::Operations::KillProcess {
+            .send(crate::processes::Operations::RestartProcess {
                 pid,
-                process_type: ProcessType::Native,
-                graceful,
-                name: name.clone(),
+                name: "node".to_string(),
+                args: "server.js".to_string(),
             })
             .unwrap();
 
@@ -320,20 +1155,722 @@ mod tests {
             .unwrap();
         assert!(matches!(
             actual,
-            crate::processes::OperationResult::ProcessKillFailed(crate::processes::KilledProcess {
-                pid: 1000,
-                name: _
-            })
+            crate::processes::OperationResult::KillSignalSent {
+                signal: Signal::Term,
+                ..
+            }
+        ));
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::ProcessRestarted {
+                process: crate::processes::KilledProcess { pid: 1000, .. },
+                new_pid: 2000,
+                ..
+            }
         ));
     }
 
     #[test]
-    fn should_handle_background_kill_shutdown_operation() {
+    fn should_not_offer_privileged_retry_for_restart() {
         // given
-        let process_manager = ProcessManager::faux();
-        let (operation_sender, result_receiver) =
-            ProcssAsyncService::new(process_manager, IgnoreOptions::default())
-                .run_as_background_process();
+        let mut process_manager = ProcessManager::faux();
+        let pid = 1000;
+        let command = crate::processes::RestartCommand::new(
+            "/usr/sbin/nginx".into(),
+            &["nginx".to_string()],
+            None,
+        );
+        faux::when!(process_manager.restart_command(pid)).then(move |_| Some(command.clone()));
+        faux::when!(process_manager.kill_process(pid, &ProcessType::Native, true))
+            .then(|_| Err(KillError::PermissionDenied));
+        faux::when!(process_manager.start_time(pid)).then_return(Some(1));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::RestartProcess {
+                pid,
+                name: "nginx".to_string(),
+                args: String::new(),
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::ProcessKillFailed {
+                error: KillError::PermissionDenied,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn should_not_kill_process_that_can_not_be_restarted() {
+        // given
+        let mut process_manager = ProcessManager::faux();
+        faux::when!(process_manager.restart_command(1000)).then(|_| None);
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::RestartProcess {
+                pid: 1000,
+                name: "kthreadd".to_string(),
+                args: String::new(),
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::Error(err) if err.contains("kthreadd")
+        ));
+    }
+
+    #[test]
+    fn should_escalate_to_force_kill_when_process_ignores_term() {
+        // given
+        let ignore_options = IgnoreOptions::default();
+        let mut process_manager = ProcessManager::faux();
+        let pid = 1000;
+        faux::when!(process_manager.kill_process(pid, &ProcessType::Native, true))
+            .once()
+            .then(|_| Ok(()));
+        faux::when!(process_manager.kill_process(pid, &ProcessType::Native, false))
+            .once()
+            .then(|_| Ok(()));
+        faux::when!(process_manager.start_time(pid)).then_return(Some(1));
+        //NOTE: most recent stub is matched first, process exits after KILL
+        faux::when!(process_manager.is_running(pid, Some(1))).then_return(false);
+        faux::when!(process_manager.is_running(pid, Some(1)))
+            .times(3)
+            .then_return(true);
+        faux::when!(process_manager.find_processes("", ignore_options))
+            .then(|_| ProcessSearchResults::empty());
+        faux::when!(process_manager.refresh())
+            .once()
+            .then(|_| Ok(()));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions {
+                grace_period: Duration::from_millis(100),
+                ..KillOptions::default()
+            },
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::KillProcess {
+                pid,
+                process_type: ProcessType::Native,
+                graceful: true,
+                name: "pik".to_string(),
+                args: String::new(),
+            })
+            .unwrap();
+
+        // then
+        let results = (0..3)
+            .map(|_| {
+                result_receiver
+                    .recv_timeout(Duration::from_millis(500))
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            results[0],
+            crate::processes::OperationResult::KillSignalSent {
+                signal: Signal::Term,
+                ..
+            }
+        ));
+        assert!(matches!(
+            results[1],
+            crate::processes::OperationResult::KillEscalated(crate::processes::KilledProcess {
+                pid: 1000,
+                ..
+            })
+        ));
+        assert!(matches!(
+            results[2],
+            crate::processes::OperationResult::ProcessKilled { .. }
+        ));
+    }
+
+    #[test]
+    fn should_report_process_still_alive_after_signal() {
+        // given
+        let mut process_manager = ProcessManager::faux();
+        let pid = 1000;
+        faux::when!(process_manager.kill_process(pid, &ProcessType::Native, false))
+            .once()
+            .then(|_| Ok(()));
+        faux::when!(process_manager.start_time(pid)).then_return(Some(1));
+        faux::when!(process_manager.is_running(pid, Some(1))).then_return(true);
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::KillProcess {
+                pid,
+                process_type: ProcessType::Native,
+                graceful: false,
+                name: "pik".to_string(),
+                args: String::new(),
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::KillSignalSent {
+                signal: Signal::Kill,
+                ..
+            }
+        ));
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(2000))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::ProcessStillAlive {
+                process: crate::processes::KilledProcess { pid: 1000, .. },
+                signal: Signal::Kill,
+            }
+        ));
+    }
+
+    #[test]
+    fn should_complete_search_while_killed_process_is_awaited() {
+        // given
+        let ignore_options = IgnoreOptions::default();
+        let mut process_manager = ProcessManager::faux();
+        let pid = 1000;
+        faux::when!(process_manager.kill_process(pid, &ProcessType::Native, true))
+            .once()
+            .then(|_| Ok(()));
+        faux::when!(process_manager.start_time(pid)).then_return(Some(1));
+        faux::when!(process_manager.is_running(pid, Some(1))).then_return(true);
+        faux::when!(process_manager.find_processes("pik", ignore_options))
+            .then(|_| ProcessSearchResults::empty());
+        faux::when!(process_manager.refresh()).then(|_| Ok(()));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions {
+                grace_period: Duration::from_secs(10),
+                ..KillOptions::default()
+            },
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::KillProcess {
+                pid,
+                process_type: ProcessType::Native,
+                graceful: true,
+                name: "pik".to_string(),
+                args: String::new(),
+            })
+            .unwrap();
+        operation_sender
+            .send(crate::processes::Operations::Search {
+                query: "pik".to_string(),
+                saved_search: None,
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::KillSignalSent {
+                signal: Signal::Term,
+                ..
+            }
+        ));
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::SearchCompleted(_)
+        ));
+    }
+
+    #[test]
+    fn should_handle_background_kill_process_fail_operation() {
+        // given
+        let mut process_manager = ProcessManager::faux();
+        let pid = 1000;
+        let graceful = false;
+        let name = "pik".to_string();
+        faux::when!(process_manager.kill_process(pid, &ProcessType::Native, graceful))
+            .then(|_| Err(KillError::NoSuchProcess));
+        faux::when!(process_manager.start_time(pid)).then_return(Some(1));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::KillProcess {
+                pid,
+                process_type: ProcessType::Native,
+                graceful,
+                name: name.clone(),
+                args: String::new(),
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::ProcessKillFailed {
+                process: crate::processes::KilledProcess { pid: 1000, .. },
+                signal: Signal::Kill,
+                error: KillError::NoSuchProcess,
+            }
+        ));
+    }
+
+    #[test]
+    fn should_report_permission_denied_when_kill_is_not_allowed() {
+        // given
+        let mut process_manager = ProcessManager::faux();
+        let pid = 1;
+        faux::when!(process_manager.kill_process(pid, &ProcessType::Native, true))
+            .then(|_| Err(KillError::PermissionDenied));
+        faux::when!(process_manager.start_time(pid)).then_return(Some(1));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::KillProcess {
+                pid,
+                process_type: ProcessType::Native,
+                graceful: true,
+                name: "systemd".to_string(),
+                args: String::new(),
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::KillPermissionDenied(PrivilegedKill {
+                pid: 1,
+                start_time: Some(1),
+                name,
+                signal: Signal::Term,
+                ..
+            }) if name == "systemd"
+        ));
+    }
+
+    #[test]
+    fn should_handle_background_signal_process_operation() {
+        // given
+        let mut process_manager = ProcessManager::faux();
+        let pid = 1000;
+        faux::when!(process_manager.start_time(pid)).then_return(Some(7));
+        faux::when!(process_manager.signal_process(pid, Signal::Stop)).then(|_| Ok(()));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::SignalProcess {
+                pid,
+                name: "java".to_string(),
+                args: String::new(),
+                signal: Signal::Stop,
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::ProcessSignaled {
+                process: crate::processes::KilledProcess { pid: 1000, .. },
+                signal: Signal::Stop,
+                start_time: Some(7),
+            }
+        ));
+    }
+
+    #[test]
+    fn should_record_sent_signal_in_history() {
+        // given
+        let history_file = std::env::temp_dir()
+            .join(format!("pik-daemon-history-{}", std::process::id()))
+            .join("history.log");
+        let history = crate::processes::KillHistory::new(Some(history_file.clone()));
+        let mut process_manager = ProcessManager::faux();
+        let pid = 1000;
+        faux::when!(process_manager.start_time(pid)).then_return(None);
+        faux::when!(process_manager.signal_process(pid, Signal::Stop))
+            .then(|_| Err(KillError::NoSuchProcess));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions {
+                history: history.clone(),
+                ..KillOptions::default()
+            },
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::SignalProcess {
+                pid,
+                name: "java".to_string(),
+                args: "-jar app.jar".to_string(),
+                signal: Signal::Stop,
+            })
+            .unwrap();
+        result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+
+        // then
+        let entries = history.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].pid, pid);
+        assert_eq!(entries[0].args, "-jar app.jar");
+        assert_eq!(entries[0].signal, "SIGSTOP");
+        assert_eq!(
+            entries[0].result,
+            "failed: no such process, it has already exited"
+        );
+        std::fs::remove_dir_all(history_file.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn should_renice_every_target_and_refresh_results() {
+        // given
+        let ignore_options = IgnoreOptions::default();
+        let mut process_manager = ProcessManager::faux();
+        faux::when!(process_manager.change_scheduling(_, SchedulingChange::Nice(10)))
+            .times(2)
+            .then(|_| Ok(()));
+        faux::when!(process_manager.find_processes("", ignore_options))
+            .then(|_| ProcessSearchResults::empty());
+        faux::when!(process_manager.refresh())
+            .once()
+            .then(|_| Ok(()));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::Renice {
+                targets: vec![killed_process(1000, "java"), killed_process(1001, "node")],
+                nice: 10,
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        let crate::processes::OperationResult::SchedulingChanged {
+            change,
+            changed,
+            failed,
+            ..
+        } = actual
+        else {
+            panic!("Expected scheduling change result, got {actual:?}");
+        };
+        assert_eq!(change, SchedulingChange::Nice(10));
+        assert_eq!(
+            changed.iter().map(|prc| prc.pid).collect::<Vec<_>>(),
+            vec![1000, 1001]
+        );
+        assert!(failed.is_empty());
+    }
+
+    #[test]
+    fn should_report_failed_affinity_change() {
+        // given
+        let ignore_options = IgnoreOptions::default();
+        let mut process_manager = ProcessManager::faux();
+        faux::when!(process_manager.change_scheduling(1000, _))
+            .then(|_| Err(anyhow::anyhow!("no such process, it has already exited")));
+        faux::when!(process_manager.change_scheduling(1001, _)).then(|_| Ok(()));
+        faux::when!(process_manager.find_processes("", ignore_options))
+            .then(|_| ProcessSearchResults::empty());
+        faux::when!(process_manager.refresh()).then(|_| Ok(()));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::SetAffinity {
+                targets: vec![killed_process(1000, "java"), killed_process(1001, "node")],
+                cpus: vec![0, 1],
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::SchedulingChanged {
+                change: SchedulingChange::Affinity(cpus),
+                changed,
+                failed,
+                ..
+            } if cpus == vec![0, 1]
+                && changed == vec![killed_process(1001, "node")]
+                && failed.len() == 1
+                && failed[0].0.pid == 1000
+                && failed[0].1.contains("no such process")
+        ));
+    }
+
+    #[test]
+    fn should_kill_all_targets_with_one_operation() {
+        // given
+        let ignore_options = IgnoreOptions::default();
+        let mut process_manager = ProcessManager::faux();
+        faux::when!(process_manager.start_time(_)).then(|pid| Some(pid as u64));
+        faux::when!(process_manager.kill_process(1000, &ProcessType::Native, true))
+            .then(|_| Ok(()));
+        faux::when!(process_manager.kill_process(1001, &ProcessType::Native, true))
+            .then(|_| Err(KillError::NoSuchProcess));
+        faux::when!(process_manager.is_running(1000, Some(1000))).then_return(false);
+        faux::when!(process_manager.find_processes("", ignore_options))
+            .then(|_| ProcessSearchResults::empty());
+        faux::when!(process_manager.refresh()).then(|_| Ok(()));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::KillProcesses {
+                targets: vec![kill_target(1000), kill_target(1001)],
+                graceful: true,
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::ProcessesKilled {
+                killed,
+                still_alive,
+                failed,
+                ..
+            } if killed.len() == 1
+                && killed[0].pid == 1000
+                && still_alive.is_empty()
+                && failed == vec![(
+                    crate::processes::KilledProcess {
+                        pid: 1001,
+                        name: "node".to_string(),
+                        args: String::new(),
+                    },
+                    KillError::NoSuchProcess
+                )]
+        ));
+    }
+
+    #[test]
+    fn should_not_signal_targets_whose_pid_was_reused_or_gone() {
+        // given
+        let ignore_options = IgnoreOptions::default();
+        let mut process_manager = ProcessManager::faux();
+        //NOTE: kill_process is not mocked, signaling any target would panic
+        faux::when!(process_manager.start_time(1000)).then_return(Some(5));
+        faux::when!(process_manager.start_time(1001)).then_return(None);
+        faux::when!(process_manager.find_processes("", ignore_options))
+            .then(|_| ProcessSearchResults::empty());
+        faux::when!(process_manager.refresh()).then(|_| Ok(()));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::KillProcesses {
+                targets: vec![kill_target(1000), kill_target(1001)],
+                graceful: true,
+            })
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::ProcessesKilled {
+                killed,
+                still_alive,
+                failed,
+                ..
+            } if killed.is_empty()
+                && still_alive.is_empty()
+                && failed == vec![
+                    (killed_process(1000, "node"), KillError::PidReused),
+                    (killed_process(1001, "node"), KillError::NoSuchProcess),
+                ]
+        ));
+    }
+
+    fn killed_process(pid: u32, name: &str) -> crate::processes::KilledProcess {
+        crate::processes::KilledProcess {
+            pid,
+            name: name.to_string(),
+            args: String::new(),
+        }
+    }
+
+    fn kill_target(pid: u32) -> crate::processes::KillTarget {
+        crate::processes::KillTarget {
+            pid,
+            start_time: pid as u64,
+            process_type: ProcessType::Native,
+            name: "node".to_string(),
+            args: String::new(),
+        }
+    }
+
+    #[test]
+    fn should_handle_background_check_port_operation() {
+        // given
+        let mut process_manager = ProcessManager::faux();
+        faux::when!(process_manager.check_port(8080)).then(|port| PortCheck {
+            port,
+            owners: vec![],
+            unowned: vec![],
+        });
+        faux::when!(process_manager.refresh())
+            .once()
+            .then(|_| Ok(()));
+
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
+
+        // when
+        operation_sender
+            .send(crate::processes::Operations::CheckPort(8080))
+            .unwrap();
+
+        // then
+        let actual = result_receiver
+            .recv_timeout(Duration::from_millis(500))
+            .unwrap();
+        assert!(matches!(
+            actual,
+            crate::processes::OperationResult::PortChecked(PortCheck { port: 8080, .. })
+        ));
+    }
+
+    #[test]
+    fn should_handle_background_kill_shutdown_operation() {
+        // given
+        let process_manager = ProcessManager::faux();
+        let (operation_sender, result_receiver) = ProcssAsyncService::new(
+            process_manager,
+            IgnoreOptions::default(),
+            KillOptions::default(),
+        )
+        .run_as_background_process();
 
         // when
         operation_sender
@@ -19,6 +19,7 @@ pub enum SearchBy {
     Everywhere,
     Pid,
     ProcessFamily,
+    Pod,
     None,
 }
 
@@ -31,6 +32,7 @@ impl QueryFilter {
             Some('~') => (SearchBy::Everywhere, &query[1..]),
             Some('=') => (SearchBy::Pid, &query[1..]),
             Some('@') => (SearchBy::ProcessFamily, &query[1..]),
+            Some('%') => (SearchBy::Pod, &query[1..]),
             Some(_) => (SearchBy::Cmd, query),
             None => (SearchBy::None, query),
         };
@@ -42,7 +44,37 @@ impl QueryFilter {
         }
     }
 
-    pub(super) fn accept(&self, prc: &impl ProcessInfo, ports: Option<&str>) -> Option<MatchData> {
+    //NOTE: '|' separates alternative queries, i.e. ':3000|:8080', '\|' is literal '|'.
+    // Empty alternatives are skipped, so query made of separators only matches nothing
+    pub fn alternatives(query: &str) -> Vec<Self> {
+        let mut alternatives = vec![String::new()];
+        let mut chars = query.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if chars.peek() == Some(&'|') => {
+                    chars.next();
+                    alternatives.last_mut().unwrap().push('|');
+                }
+                '|' => alternatives.push(String::new()),
+                c => alternatives.last_mut().unwrap().push(c),
+            }
+        }
+        if alternatives.len() == 1 {
+            return vec![Self::new(&alternatives[0])];
+        }
+        alternatives
+            .iter()
+            .filter(|alternative| !alternative.trim().is_empty())
+            .map(|alternative| Self::new(alternative))
+            .collect()
+    }
+
+    pub(super) fn accept(
+        &self,
+        prc: &impl ProcessInfo,
+        ports: Option<&str>,
+        pod: Option<&str>,
+    ) -> Option<MatchData> {
         match self.search_by {
             SearchBy::Cmd => self.fuzzy_match(prc.cmd(), MatchedBy::Cmd),
             SearchBy::Path => self.fuzzy_match_opt(prc.cmd_path(), MatchedBy::Path),
@@ -52,13 +84,13 @@ impl QueryFilter {
             SearchBy::Port => self.contains_match_opt(ports, MatchedBy::Port),
             SearchBy::Pid => self.exact_match_u32(prc.pid(), MatchedBy::Pid),
             SearchBy::ProcessFamily => self.exact_match_process_family(prc),
+            SearchBy::Pod => self.fuzzy_match_opt(pod, MatchedBy::Pod),
             SearchBy::Everywhere => self
                 .fuzzy_match(prc.cmd(), MatchedBy::Cmd)
                 .or_else(|| self.fuzzy_match_opt(prc.cmd_path(), MatchedBy::Path))
                 .or_else(|| self.fuzzy_match_opt(ports, MatchedBy::Port))
-                .or_else(|| {
-                    self.fuzzy_match_opt(get_process_args(prc).as_deref(), MatchedBy::Args)
-                }),
+                .or_else(|| self.fuzzy_match_opt(get_process_args(prc).as_deref(), MatchedBy::Args))
+                .or_else(|| self.fuzzy_match_opt(pod, MatchedBy::Pod)),
             SearchBy::None => Some(MatchData::new(
                 MatchedBy::ProcessExistence,
                 MatchType::Exists,
@@ -108,9 +140,13 @@ impl QueryFilter {
             return Some(MatchData::new(matched_by, MatchType::Exists));
         }
 
+        // positions are char based, ports may contain multi byte chars like '→'
         let positions: Vec<_> = s
             .find(&self.query)
-            .map(|start| (start..start + self.query.len()).collect())
+            .map(|byte_start| {
+                let start = s[..byte_start].chars().count();
+                (start..start + self.query.chars().count()).collect()
+            })
             .unwrap_or_default();
 
         if positions.is_empty() {
@@ -128,7 +164,7 @@ impl QueryFilter {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IgnoreOptions {
     //NOTE: On linux threads can be listed as processes and thus needs filtering
     pub ignore_threads: bool,
@@ -165,6 +201,37 @@ impl PartialEq for IgnoreOptions {
 
 impl Eq for IgnoreOptions {}
 
+/// True when query only checks existence (i.e. '', '/' or ':'), it selects nearly every process then
+//NOTE: query made of separators matches nothing, it is not worth killing either
+pub fn selects_by_existence_only(query: &str) -> bool {
+    QueryFilter::alternatives(query)
+        .iter()
+        .all(|filter| filter.query.trim().is_empty())
+}
+
+/// Query saved in config under a name, it may ignore different processes than other queries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+    // None when global ignore options are used
+    pub ignore: Option<IgnoreOptions>,
+}
+
+/// Ignore options of saved search with given name, global ones otherwise
+//NOTE: search is picked by name, query typed by hand uses global options even if it is the same
+pub fn ignore_options_for<'a>(
+    saved_search: Option<&str>,
+    global: &'a IgnoreOptions,
+    saved_searches: &'a [SavedSearch],
+) -> &'a IgnoreOptions {
+    saved_searches
+        .iter()
+        .find(|search| Some(search.name.as_str()) == saved_search)
+        .and_then(|search| search.ignore.as_ref())
+        .unwrap_or(global)
+}
+
 pub(super) struct IgnoreProcessesFilter<'a> {
     opt: &'a IgnoreOptions,
     current_user_id: &'a Uid,
@@ -229,11 +296,75 @@ pub mod tests {
         assert_eq!(filter.search_by, SearchBy::ProcessFamily);
         assert_eq!(filter.query, "1234");
 
+        let filter = QueryFilter::new("%Default/nginx");
+        assert_eq!(filter.search_by, SearchBy::Pod);
+        assert_eq!(filter.query, "default/nginx");
+
         let filter = QueryFilter::new("");
         assert_eq!(filter.search_by, SearchBy::None);
         assert_eq!(filter.query, "");
     }
 
+    #[test]
+    fn should_split_query_into_alternatives() {
+        let filters = QueryFilter::alternatives(":3000|:5173||/node");
+        assert_eq!(
+            filters
+                .iter()
+                .map(|f| (&f.search_by, f.query.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (&SearchBy::Port, "3000"),
+                (&SearchBy::Port, "5173"),
+                (&SearchBy::Path, "node")
+            ]
+        );
+
+        assert!(QueryFilter::alternatives("|").is_empty());
+        assert!(QueryFilter::alternatives(" || ").is_empty());
+        assert_eq!(QueryFilter::alternatives("")[0].search_by, SearchBy::None);
+    }
+
+    #[test]
+    fn should_detect_queries_selecting_by_existence_only() {
+        for query in ["", " ", "/", ":", "-", "%", ":|/", "|"] {
+            assert!(selects_by_existence_only(query), "{query:?}");
+        }
+        for query in ["node", ":8080", "/|node", "%default"] {
+            assert!(!selects_by_existence_only(query), "{query:?}");
+        }
+    }
+
+    #[test]
+    fn should_keep_escaped_separator_in_query() {
+        let filters = QueryFilter::alternatives("-a\\|b|node");
+
+        assert_eq!(
+            filters
+                .iter()
+                .map(|f| (&f.search_by, f.query.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(&SearchBy::Args, "a|b"), (&SearchBy::Cmd, "node")]
+        );
+    }
+
+    #[test]
+    fn should_use_ignore_options_of_saved_search() {
+        let global = IgnoreOptions::default();
+        let saved = vec![SavedSearch {
+            name: "all-java".to_string(),
+            query: "java".to_string(),
+            ignore: Some(IgnoreOptions {
+                ignore_other_users: false,
+                ..Default::default()
+            }),
+        }];
+
+        assert!(!ignore_options_for(Some("all-java"), &global, &saved).ignore_other_users);
+        assert!(ignore_options_for(Some("java"), &global, &saved).ignore_other_users);
+        assert!(ignore_options_for(None, &global, &saved).ignore_other_users);
+    }
+
     #[test]
     fn query_filter_search_by_cmd() {
         let filter = QueryFilter::new("test");
@@ -241,21 +372,21 @@ pub mod tests {
             cmd: "TeSt".to_string(),
             ..Default::default()
         };
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Cmd);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Cmd);
 
         process.cmd = "test".to_string();
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Cmd);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Cmd);
 
         process.cmd = "TEST".to_string();
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Cmd);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Cmd);
 
         process.cmd = "Testificator".to_string();
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Cmd);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Cmd);
 
         process.cmd = "online_TESTER".to_string();
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Cmd);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Cmd);
         process.cmd = "xxx".to_string();
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
     }
 
     #[test]
@@ -265,33 +396,33 @@ pub mod tests {
             cmd_path: Some("/TeSt".to_string()),
             ..Default::default()
         };
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Path);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Path);
 
         // tests that fuzzy search works
         process.cmd_path = Some("/taest".to_string());
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Path);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Path);
 
         process.cmd_path = Some("/test".to_string());
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Path);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Path);
 
         process.cmd_path = Some("/TEST".to_string());
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Path);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Path);
 
         process.cmd_path = Some("/testing_dir".to_string());
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Path);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Path);
 
         process.cmd_path = Some("/cargo/tests".to_string());
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Path);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Path);
 
         process.cmd_path = Some("/xxx".to_string());
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
 
         // '/' accepts all non empty paths
         let filter = QueryFilter::new("/");
         process.cmd_path = Some("/xxx".to_string());
-        assert_existence_match(filter.accept(&process, None), MatchedBy::Path);
+        assert_existence_match(filter.accept(&process, None, None), MatchedBy::Path);
         process.cmd_path = None;
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
     }
 
     #[test]
@@ -300,29 +431,29 @@ pub mod tests {
         let mut process = MockProcessInfo::default();
 
         process = process.with_args(&["-TeSt"]);
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Args);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Args);
 
         process = process.with_args(&["-test"]);
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Args);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Args);
 
         process = process.with_args(&["-TEST"]);
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Args);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Args);
 
         process = process.with_args(&["arg1, arg2, --testifier"]);
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Args);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Args);
 
         process = process.with_args(&["testimony"]);
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Args);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Args);
 
         process = process.with_args(&["-xxx"]);
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
 
         // '-' accepts all non empty args
         let filter = QueryFilter::new("-");
         process = process.with_args(&["-arg"]);
-        assert_existence_match(filter.accept(&process, None), MatchedBy::Args);
+        assert_existence_match(filter.accept(&process, None, None), MatchedBy::Args);
         process = process.with_args(&[]);
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
     }
 
     #[test]
@@ -333,7 +464,7 @@ pub mod tests {
             args: vec!["-test".into(), "-xxx".into()],
             ..Default::default()
         };
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
     }
 
     #[test]
@@ -341,23 +472,33 @@ pub mod tests {
         let filter = QueryFilter::new(":12");
         let process = MockProcessInfo::default();
 
-        assert_contains_match(filter.accept(&process, Some("1234")), MatchedBy::Port);
+        assert_contains_match(filter.accept(&process, Some("1234"), None), MatchedBy::Port);
 
-        assert_contains_match(filter.accept(&process, Some("3312")), MatchedBy::Port);
+        assert_contains_match(filter.accept(&process, Some("3312"), None), MatchedBy::Port);
 
-        assert_contains_match(filter.accept(&process, Some("5125")), MatchedBy::Port);
+        assert_contains_match(filter.accept(&process, Some("5125"), None), MatchedBy::Port);
 
         assert_contains_match(
-            filter.accept(&process, Some("1111, 2222, 1234")),
+            filter.accept(&process, Some("1111, 2222, 1234"), None),
             MatchedBy::Port,
         );
 
-        assert_eq!(filter.accept(&process, Some("7777")), None);
+        assert_eq!(filter.accept(&process, Some("7777"), None), None);
+
+        // matches container port of published port mapping
+        let filter = QueryFilter::new(":80");
+        let matched = filter.accept(&process, Some("9090→80/tcp"), None);
+        assert_eq!(
+            matched.map(|m| m.match_type),
+            Some(MatchType::Contains {
+                positions: vec![5, 6]
+            })
+        );
 
         //':' accepts all non empty ports
         let filter = QueryFilter::new(":");
-        assert_existence_match(filter.accept(&process, Some("5125")), MatchedBy::Port);
-        assert_eq!(filter.accept(&process, None), None);
+        assert_existence_match(filter.accept(&process, Some("5125"), None), MatchedBy::Port);
+        assert_eq!(filter.accept(&process, None, None), None);
     }
 
     #[test]
@@ -368,9 +509,9 @@ pub mod tests {
             ..Default::default()
         };
 
-        assert_exact_match(filter.accept(&process, None), MatchedBy::Pid);
+        assert_exact_match(filter.accept(&process, None, None), MatchedBy::Pid);
         process.pid = 12345;
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
     }
 
     #[test]
@@ -380,19 +521,43 @@ pub mod tests {
             pid: 1234,
             ..Default::default()
         };
-        assert_exact_match(filter.accept(&process, None), MatchedBy::Pid);
+        assert_exact_match(filter.accept(&process, None, None), MatchedBy::Pid);
 
         process.pid = 555;
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
 
         process.parent_pid = Some(1234);
-        assert_exact_match(filter.accept(&process, None), MatchedBy::ParentPid);
+        assert_exact_match(filter.accept(&process, None, None), MatchedBy::ParentPid);
 
         process.parent_pid = Some(555);
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
 
         process.parent_pid = None;
-        assert_eq!(filter.accept(&process, None), None);
+        assert_eq!(filter.accept(&process, None, None), None);
+    }
+
+    #[test]
+    fn query_filter_search_by_pod() {
+        let filter = QueryFilter::new("%nginx");
+        let process = MockProcessInfo::default();
+
+        assert_fuzzy_match(
+            filter.accept(&process, None, Some("default/nginx-7c5/nginx")),
+            MatchedBy::Pod,
+        );
+        assert_eq!(
+            filter.accept(&process, None, Some("kube-system/coredns/coredns")),
+            None
+        );
+        assert_eq!(filter.accept(&process, None, None), None);
+
+        //'%' accepts all pod processes
+        let filter = QueryFilter::new("%");
+        assert_existence_match(
+            filter.accept(&process, None, Some("2b1c-44aa")),
+            MatchedBy::Pod,
+        );
+        assert_eq!(filter.accept(&process, None, None), None);
     }
 
     #[test]
@@ -402,24 +567,24 @@ pub mod tests {
             cmd: "TEST".into(),
             ..Default::default()
         };
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Cmd);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Cmd);
 
         let process = MockProcessInfo {
             cmd_path: Some("/tEsT".into()),
             ..Default::default()
         };
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Path);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Path);
 
         let process = MockProcessInfo {
             args: vec!["-TeSt".into()],
             ..Default::default()
         };
-        assert_fuzzy_match(filter.accept(&process, None), MatchedBy::Args);
+        assert_fuzzy_match(filter.accept(&process, None, None), MatchedBy::Args);
 
         let process = MockProcessInfo::default();
 
         let filter = QueryFilter::new("~80");
-        assert_fuzzy_match(filter.accept(&process, Some("8080")), MatchedBy::Port);
+        assert_fuzzy_match(filter.accept(&process, Some("8080"), None), MatchedBy::Port);
 
         let filter = QueryFilter::new("~any");
         let process = MockProcessInfo {
@@ -428,26 +593,38 @@ pub mod tests {
             args: vec!["-xxx".into()],
             ..Default::default()
         };
-        assert_eq!(filter.accept(&process, Some("1234")), None);
+        assert_eq!(filter.accept(&process, Some("1234"), None), None);
     }
 
     #[test]
     fn query_filter_search_by_none() {
         let filter = QueryFilter::new("");
         let mut process = MockProcessInfo::default();
-        assert_existence_match(filter.accept(&process, None), MatchedBy::ProcessExistence);
+        assert_existence_match(
+            filter.accept(&process, None, None),
+            MatchedBy::ProcessExistence,
+        );
 
         process.cmd = "TeSt".to_string();
-        assert_existence_match(filter.accept(&process, None), MatchedBy::ProcessExistence);
+        assert_existence_match(
+            filter.accept(&process, None, None),
+            MatchedBy::ProcessExistence,
+        );
 
         process.cmd_path = Some("/TeSt".to_string());
-        assert_existence_match(filter.accept(&process, None), MatchedBy::ProcessExistence);
+        assert_existence_match(
+            filter.accept(&process, None, None),
+            MatchedBy::ProcessExistence,
+        );
 
         process = process.with_args(&["-TeSt"]);
-        assert_existence_match(filter.accept(&process, None), MatchedBy::ProcessExistence);
+        assert_existence_match(
+            filter.accept(&process, None, None),
+            MatchedBy::ProcessExistence,
+        );
 
         assert_existence_match(
-            filter.accept(&process, Some("1234")),
+            filter.accept(&process, Some("1234"), None),
             MatchedBy::ProcessExistence,
         );
     }
@@ -0,0 +1,227 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+#[cfg(not(unix))]
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use sysinfo::{Signal, Users};
+
+use super::{KilledProcess, signal_name};
+
+/// Single signal sent by pik, stored as one json line in history file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: String,
+    // user who ran pik (by real uid), not owner of signaled process
+    pub user: String,
+    // user who escalated with sudo, taken from environment so it is only a hint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sudo_user: Option<String>,
+    pub pid: u32,
+    pub cmd: String,
+    pub args: String,
+    pub signal: String,
+    pub result: String,
+}
+
+/// Append-only log of signals sent by pik, disabled when there is no history file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KillHistory {
+    history_file: Option<PathBuf>,
+}
+
+impl KillHistory {
+    pub fn new(history_file: Option<PathBuf>) -> Self {
+        Self { history_file }
+    }
+
+    pub fn record(&self, process: &KilledProcess, signal: Signal, result: &str) -> Result<()> {
+        let Some(path) = &self.history_file else {
+            return Ok(());
+        };
+        let entry = HistoryEntry {
+            timestamp: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+            user: real_user(),
+            sudo_user: std::env::var("SUDO_USER")
+                .ok()
+                .filter(|user| !user.is_empty()),
+            pid: process.pid,
+            cmd: process.name.clone(),
+            args: process.args.clone(),
+            signal: signal_name(signal).to_string(),
+            result: result.to_string(),
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create state directory: {dir:?}"))?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open kill history: {path:?}"))?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)
+            .with_context(|| format!("Failed to write kill history: {path:?}"))
+    }
+
+    /// Entries in order they were recorded, lines that can't be parsed are skipped
+    pub fn entries(&self) -> Result<Vec<HistoryEntry>> {
+        let Some(path) = &self.history_file else {
+            return Ok(vec![]);
+        };
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read kill history: {path:?}"))?;
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+impl HistoryEntry {
+    pub fn to_line(&self) -> String {
+        format!(
+            "{} {} {} {} {} {} {}",
+            self.timestamp,
+            self.actor(),
+            self.signal,
+            self.pid,
+            self.cmd,
+            self.args,
+            self.result
+        )
+    }
+
+    /// User who sent signal, i.e. 'root (sudo alice)'
+    pub fn actor(&self) -> String {
+        match &self.sudo_user {
+            Some(sudo_user) => format!("{} (sudo {sudo_user})", self.user),
+            None => self.user.clone(),
+        }
+    }
+}
+
+//NOTE: environment variables like USER can be set to anything, only uid tells who runs pik
+#[cfg(unix)]
+fn real_user() -> String {
+    // SAFETY: getuid always succeeds and has no memory safety requirements
+    let uid = unsafe { libc::getuid() };
+    Users::new_with_refreshed_list()
+        .iter()
+        .find(|user| **user.id() == uid)
+        .map_or_else(|| uid.to_string(), |user| user.name().to_string())
+}
+
+#[cfg(not(unix))]
+fn real_user() -> String {
+    let mut sys = System::new();
+    let Ok(pid) = sysinfo::get_current_pid() else {
+        return "unknown".to_string();
+    };
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing().with_user(UpdateKind::Always),
+    );
+    let Some(uid) = sys.process(pid).and_then(|prc| prc.user_id()) else {
+        return "unknown".to_string();
+    };
+    Users::new_with_refreshed_list()
+        .get_user_by_id(uid)
+        .map_or_else(|| "unknown".to_string(), |user| user.name().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_append_entries_to_history_file() {
+        let history_file = std::env::temp_dir()
+            .join(format!("pik-history-{}", std::process::id()))
+            .join("history.log");
+        let history = KillHistory::new(Some(history_file.clone()));
+
+        history
+            .record(&some_process(42), Signal::Term, "delivered")
+            .unwrap();
+        history
+            .record(&some_process(43), Signal::Kill, "failed: no such process")
+            .unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&history_file)
+            .and_then(|mut file| writeln!(file, "broken line"))
+            .unwrap();
+
+        let entries = history.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].pid, 42);
+        assert_eq!(entries[0].signal, "SIGTERM");
+        assert_eq!(entries[0].args, "--port 3000");
+        assert_eq!(entries[1].result, "failed: no such process");
+        #[cfg(unix)]
+        assert_eq!(entries[0].user, expected_user());
+        std::fs::remove_dir_all(history_file.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn should_do_nothing_without_history_file() {
+        let history = KillHistory::default();
+
+        history
+            .record(&some_process(42), Signal::Term, "delivered")
+            .unwrap();
+
+        assert!(history.entries().unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_show_sudo_user_next_to_real_user() {
+        let entry = HistoryEntry {
+            timestamp: "2024-10-18T10:00:00+02:00".to_string(),
+            user: "root".to_string(),
+            sudo_user: Some("alice".to_string()),
+            pid: 42,
+            cmd: "node".to_string(),
+            args: "--port 3000".to_string(),
+            signal: "SIGTERM".to_string(),
+            result: "delivered".to_string(),
+        };
+        let line = serde_json::to_string(&entry).unwrap();
+        let without_sudo = line.replace(r#""sudo_user":"alice","#, "");
+
+        assert_eq!(
+            entry.to_line(),
+            "2024-10-18T10:00:00+02:00 root (sudo alice) SIGTERM 42 node --port 3000 delivered"
+        );
+        assert_eq!(
+            serde_json::from_str::<HistoryEntry>(&without_sudo)
+                .unwrap()
+                .actor(),
+            "root"
+        );
+    }
+
+    // 'id -un' reads user of real uid too, it ignores USER variable
+    #[cfg(unix)]
+    fn expected_user() -> String {
+        let output = std::process::Command::new("id")
+            .arg("-un")
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    fn some_process(pid: u32) -> KilledProcess {
+        KilledProcess {
+            pid,
+            name: "node".to_string(),
+            args: "--port 3000".to_string(),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt::Display;
+
+use sysinfo::Signal;
+
+use super::signal_name;
+
+/// Reason why process could not be killed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KillError {
+    NoSuchProcess,
+    // PID was reused by other process since it was listed, it is not signaled
+    PidReused,
+    PermissionDenied,
+    UnsupportedSignal(Signal),
+    // docker (or other runtime) failed, stderr is passed to user as is
+    ContainerRuntime { stderr: String },
+    Other(String),
+}
+
+impl KillError {
+    pub(super) fn from_io(err: std::io::Error, signal: Signal) -> Self {
+        #[cfg(unix)]
+        if err.raw_os_error() == Some(libc::ESRCH) {
+            return Self::NoSuchProcess;
+        }
+        match err.kind() {
+            std::io::ErrorKind::NotFound => Self::NoSuchProcess,
+            std::io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+            std::io::ErrorKind::Unsupported => Self::UnsupportedSignal(signal),
+            _ => Self::Other(err.to_string()),
+        }
+    }
+}
+
+impl Display for KillError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSuchProcess => write!(f, "no such process, it has already exited"),
+            Self::PidReused => write!(
+                f,
+                "process has exited and its PID belongs to other process now, it was not signaled"
+            ),
+            Self::PermissionDenied => write!(
+                f,
+                "permission denied, process has to be killed by its owner or root"
+            ),
+            Self::UnsupportedSignal(signal) => write!(
+                f,
+                "{} is not supported on this platform",
+                signal_name(*signal)
+            ),
+            Self::ContainerRuntime { stderr } if stderr.is_empty() => write!(
+                f,
+                "container runtime failed, check that docker is installed and running"
+            ),
+            Self::ContainerRuntime { stderr } => write!(f, "container runtime failed: {stderr}"),
+            Self::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for KillError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_io_errors_to_kill_errors() {
+        let kill_error = |kind: std::io::ErrorKind| KillError::from_io(kind.into(), Signal::Term);
+
+        assert_eq!(
+            kill_error(std::io::ErrorKind::NotFound),
+            KillError::NoSuchProcess
+        );
+        assert_eq!(
+            kill_error(std::io::ErrorKind::PermissionDenied),
+            KillError::PermissionDenied
+        );
+        assert_eq!(
+            kill_error(std::io::ErrorKind::Unsupported),
+            KillError::UnsupportedSignal(Signal::Term)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_map_esrch_to_no_such_process() {
+        let err = std::io::Error::from_raw_os_error(libc::ESRCH);
+
+        assert_eq!(
+            KillError::from_io(err, Signal::Kill),
+            KillError::NoSuchProcess
+        );
+    }
+
+    #[test]
+    fn should_describe_container_runtime_failure() {
+        let err = KillError::ContainerRuntime {
+            stderr: "Error response from daemon: No such container: abc".to_string(),
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "container runtime failed: Error response from daemon: No such container: abc"
+        );
+    }
+}
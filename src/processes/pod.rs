@@ -0,0 +1,752 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PodInfo {
+    pub uid: String,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+    pub container_name: Option<String>,
+    pub container_id: Option<String>,
+}
+
+impl PodInfo {
+    /// Returns 'namespace/pod/container', falls back to pod UID when CRI runtime was not reachable
+    pub fn display_name(&self) -> String {
+        match (&self.namespace, &self.name) {
+            (Some(namespace), Some(name)) => match &self.container_name {
+                Some(container_name) => format!("{namespace}/{name}/{container_name}"),
+                None => format!("{namespace}/{name}"),
+            },
+            _ => self.uid.clone(),
+        }
+    }
+}
+
+// Sockets of CRI runtimes used by local clusters, k3s runs its own containerd
+const CRI_SOCKETS: [&str; 4] = [
+    "/run/containerd/containerd.sock",
+    "/run/k3s/containerd/containerd.sock",
+    "/run/crio/crio.sock",
+    "/var/run/cri-dockerd.sock",
+];
+
+// crictl gives up connecting after 2s by default, runtime that accepted connection may still hang
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(3);
+// runtime that failed is asked again after backoff that doubles with every failure
+const RETRY_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Pods of processes, it is refreshed on every search so lookups are cached:
+/// cgroup is read once per process and CRI runtime is asked only when new container shows up.
+/// Runtime is asked with crictl, without it pods are known by UID only
+pub(super) struct PodCache {
+    proc_dir: PathBuf,
+    cri_sockets: Vec<PathBuf>,
+    crictl: PathBuf,
+    lookup_timeout: Duration,
+    retry_backoff: Duration,
+    // start time tells apart new process that reused PID
+    processes: HashMap<u32, (u64, Option<PodInfo>)>,
+    // containers CRI runtime answered about, unknown ones are not asked about again
+    looked_up_containers: HashSet<String>,
+    // last answer of CRI runtime, new processes in known containers are labeled from it
+    cri_containers: Vec<CriContainer>,
+    // failed lookups by socket, runtime is not asked again until its backoff passes
+    failed_lookups: HashMap<PathBuf, FailedLookup>,
+}
+
+struct FailedLookup {
+    error: String,
+    failures: u32,
+    retry_at: Instant,
+}
+
+impl Default for PodCache {
+    fn default() -> Self {
+        Self::new("/proc", &CRI_SOCKETS, "crictl")
+    }
+}
+
+impl PodCache {
+    fn new(
+        proc_dir: impl Into<PathBuf>,
+        cri_sockets: &[impl AsRef<Path>],
+        crictl: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            proc_dir: proc_dir.into(),
+            cri_sockets: cri_sockets
+                .iter()
+                .map(|path| path.as_ref().to_path_buf())
+                .collect(),
+            crictl: crictl.into(),
+            lookup_timeout: LOOKUP_TIMEOUT,
+            retry_backoff: RETRY_BACKOFF,
+            processes: HashMap::new(),
+            looked_up_containers: HashSet::new(),
+            cri_containers: vec![],
+            failed_lookups: HashMap::new(),
+        }
+    }
+
+    /// Looks up pods of new processes (pid, start time), processes that are gone are forgotten.
+    /// Error is returned while no CRI runtime could be asked for pod names, pods are known by UID then
+    pub(super) fn refresh(&mut self, processes: impl Iterator<Item = (u32, u64)>) -> Result<()> {
+        let mut cached = std::mem::take(&mut self.processes);
+        self.processes = processes
+            .map(|(pid, start_time)| {
+                let pod = match cached.remove(&pid) {
+                    Some((cached_start_time, pod)) if cached_start_time == start_time => pod,
+                    _ => read_pod_cgroup(&self.proc_dir, pid),
+                };
+                (pid, (start_time, pod))
+            })
+            .collect();
+        let new_containers = self
+            .processes
+            .values()
+            .filter_map(|(_, pod)| pod.as_ref()?.container_id.clone())
+            .filter(|id| !self.looked_up_containers.contains(id))
+            .collect::<Vec<_>>();
+        let mut lookup = Ok(());
+        if !new_containers.is_empty() {
+            lookup = self.list_cri_containers().map(|containers| {
+                self.cri_containers = containers;
+                self.looked_up_containers.extend(new_containers);
+            });
+        }
+        let cri_containers = std::mem::take(&mut self.cri_containers);
+        for pod in self.pods_mut().filter(|pod| pod.name.is_none()) {
+            enrich_with_cri_labels(pod, &cri_containers);
+        }
+        self.cri_containers = cri_containers;
+        lookup.map_err(|err| anyhow!("Pod names are unavailable, {err}"))
+    }
+
+    // Asks runtimes one by one until one answers, runtimes still in backoff are not asked
+    fn list_cri_containers(&mut self) -> Result<Vec<CriContainer>, String> {
+        let mut errors = vec![];
+        for socket in self.cri_sockets.iter().filter(|path| is_socket(path)) {
+            let now = Instant::now();
+            if let Some(failed) = self.failed_lookups.get(socket)
+                && now < failed.retry_at
+            {
+                errors.push(failed.error.clone());
+                continue;
+            }
+            match get_cri_containers(&self.crictl, socket, self.lookup_timeout) {
+                Ok(containers) => {
+                    self.failed_lookups.remove(socket);
+                    return Ok(containers);
+                }
+                Err(err) => {
+                    let failures = self
+                        .failed_lookups
+                        .get(socket)
+                        .map_or(1, |failed| failed.failures + 1);
+                    let error = format!("{err:#}");
+                    errors.push(error.clone());
+                    self.failed_lookups.insert(
+                        socket.clone(),
+                        FailedLookup {
+                            error,
+                            failures,
+                            retry_at: now + backoff(self.retry_backoff, failures),
+                        },
+                    );
+                }
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(vec![]),
+            false => Err(errors.join("; ")),
+        }
+    }
+
+    pub(super) fn get(&self, pid: &u32) -> Option<&PodInfo> {
+        self.processes.get(pid).and_then(|(_, pod)| pod.as_ref())
+    }
+
+    fn pods_mut(&mut self) -> impl Iterator<Item = &mut PodInfo> {
+        self.processes
+            .values_mut()
+            .filter_map(|(_, pod)| pod.as_mut())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_pod_cgroup(proc_dir: &Path, pid: u32) -> Option<PodInfo> {
+    let cgroup = std::fs::read_to_string(proc_dir.join(pid.to_string()).join("cgroup")).ok()?;
+    parse_pod_cgroup(&cgroup)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_pod_cgroup(_proc_dir: &Path, _pid: u32) -> Option<PodInfo> {
+    None
+}
+
+/// Extracts pod UID and container ID from /proc/<pid>/cgroup content.
+/// Supports both systemd and cgroupfs drivers, i.e.
+/// - 0::/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod<uid>.slice/cri-containerd-<id>.scope
+/// - 0::/kubepods/besteffort/pod<uid>/<id>
+fn parse_pod_cgroup(cgroup: &str) -> Option<PodInfo> {
+    cgroup.lines().find_map(|line| {
+        let path = line.splitn(3, ':').nth(2)?;
+        if !path.contains("kubepods") {
+            return None;
+        }
+        let mut segments = path.split('/').skip_while(|s| pod_uid(s).is_none());
+        let uid = pod_uid(segments.next()?)?;
+        let container_id = segments.next().and_then(container_id);
+        Some(PodInfo {
+            uid,
+            namespace: None,
+            name: None,
+            container_name: None,
+            container_id,
+        })
+    })
+}
+
+fn pod_uid(segment: &str) -> Option<String> {
+    let uid = match segment.strip_suffix(".slice") {
+        // systemd driver escapes '-' in UID as '_'
+        Some(slice) => slice.rsplit_once("-pod")?.1.replace('_', "-"),
+        None => segment.strip_prefix("pod")?.to_string(),
+    };
+    (!uid.is_empty()).then_some(uid)
+}
+
+fn container_id(segment: &str) -> Option<String> {
+    let segment = segment.strip_suffix(".scope").unwrap_or(segment);
+    let id = ["cri-containerd-", "crio-", "docker-", "containerd-"]
+        .iter()
+        .find_map(|prefix| segment.strip_prefix(prefix))
+        .unwrap_or(segment);
+    // skips runtime helpers like 'crio-conmon-<id>'
+    (!id.is_empty() && id.chars().all(|c| c.is_ascii_hexdigit())).then(|| id.to_string())
+}
+
+fn backoff(base: Duration, failures: u32) -> Duration {
+    base.saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+        .min(MAX_RETRY_BACKOFF)
+}
+
+#[cfg(unix)]
+fn is_socket(path: &Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    std::fs::metadata(path).is_ok_and(|meta| meta.file_type().is_socket())
+}
+
+#[cfg(not(unix))]
+fn is_socket(_path: &Path) -> bool {
+    false
+}
+
+#[derive(Deserialize, Default)]
+struct CriContainers {
+    #[serde(default)]
+    containers: Vec<CriContainer>,
+}
+
+/// Container known to CRI runtime, kubernetes names are kept in its labels
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+struct CriContainer {
+    id: String,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+
+fn get_cri_containers(
+    crictl: &Path,
+    socket: &Path,
+    timeout: Duration,
+) -> Result<Vec<CriContainer>> {
+    let mut command = Command::new(crictl);
+    command
+        .arg("--runtime-endpoint")
+        .arg(format!("unix://{}", socket.display()))
+        .args(["ps", "-o", "json"]);
+    let output = run_with_timeout(command, timeout)?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "crictl ps failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    parse_crictl_output(&output.stdout)
+}
+
+// crictl is killed when it does not answer in time, its pipes are read aside so it never blocks on them
+fn run_with_timeout(mut command: Command, timeout: Duration) -> Result<Output> {
+    let mut child = match command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return Err(anyhow!("crictl is not installed"));
+        }
+        Err(err) => return Err(err).context("Failed to run crictl"),
+    };
+    let read_stdout = read_aside(child.stdout.take());
+    let read_stderr = read_aside(child.stderr.take());
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().context("Failed to wait for crictl")? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            child.kill().ok();
+            child.wait().ok();
+            return Err(anyhow!(
+                "crictl did not answer within {}ms",
+                timeout.as_millis()
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    Ok(Output {
+        status,
+        stdout: read_stdout.join().unwrap_or_default(),
+        stderr: read_stderr.join().unwrap_or_default(),
+    })
+}
+
+fn read_aside(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = vec![];
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buf).ok();
+        }
+        buf
+    })
+}
+
+fn parse_crictl_output(stdout: &[u8]) -> Result<Vec<CriContainer>> {
+    serde_json::from_slice::<CriContainers>(stdout)
+        .map(|listed| listed.containers)
+        .context("crictl ps output could not be parsed")
+}
+
+fn enrich_with_cri_labels(pod: &mut PodInfo, cri_containers: &[CriContainer]) {
+    let Some(container_id) = pod.container_id.as_deref() else {
+        return;
+    };
+    let Some(cri_container) = cri_containers.iter().find(|c| c.id == container_id) else {
+        return;
+    };
+    let label = |name: &str| cri_container.labels.get(name).cloned();
+    pod.namespace = label("io.kubernetes.pod.namespace");
+    pod.name = label("io.kubernetes.pod.name");
+    pod.container_name = label("io.kubernetes.container.name");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTAINER_ID: &str = "4f1c7e3b5a0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b7c6d5e4f";
+
+    #[test]
+    fn should_parse_systemd_driver_cgroup() {
+        let cgroup = format!(
+            "0::/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod2b1c_44aa.slice/cri-containerd-{CONTAINER_ID}.scope"
+        );
+
+        let pod = parse_pod_cgroup(&cgroup).unwrap();
+
+        assert_eq!(pod.uid, "2b1c-44aa");
+        assert_eq!(pod.container_id.as_deref(), Some(CONTAINER_ID));
+    }
+
+    #[test]
+    fn should_parse_cgroupfs_driver_cgroup() {
+        let cgroup = format!(
+            "12:pids:/kubepods/burstable/pod2b1c-44aa/{CONTAINER_ID}\n0::/kubepods/burstable/pod2b1c-44aa/{CONTAINER_ID}"
+        );
+
+        let pod = parse_pod_cgroup(&cgroup).unwrap();
+
+        assert_eq!(pod.uid, "2b1c-44aa");
+        assert_eq!(pod.container_id.as_deref(), Some(CONTAINER_ID));
+    }
+
+    #[test]
+    fn should_parse_kind_node_cgroup() {
+        let cgroup = format!(
+            "0::/system.slice/docker-abc.scope/kubelet.slice/kubelet-kubepods.slice/kubelet-kubepods-pod2b1c_44aa.slice/crio-{CONTAINER_ID}.scope"
+        );
+
+        let pod = parse_pod_cgroup(&cgroup).unwrap();
+
+        assert_eq!(pod.uid, "2b1c-44aa");
+        assert_eq!(pod.container_id.as_deref(), Some(CONTAINER_ID));
+    }
+
+    #[test]
+    fn should_skip_runtime_helpers_and_non_pod_cgroups() {
+        let cgroup = format!(
+            "0::/kubepods.slice/kubepods-pod2b1c_44aa.slice/crio-conmon-{CONTAINER_ID}.scope"
+        );
+        let pod = parse_pod_cgroup(&cgroup).unwrap();
+        assert_eq!(pod.container_id, None);
+
+        assert_eq!(
+            parse_pod_cgroup("0::/user.slice/user-1000.slice/session-2.scope"),
+            None
+        );
+        assert_eq!(parse_pod_cgroup("0::/kubepods.slice"), None);
+    }
+
+    #[test]
+    fn should_enrich_pod_with_cri_labels() {
+        let cri_containers = vec![nginx_container()];
+        let mut pod = PodInfo {
+            uid: "2b1c-44aa".to_string(),
+            namespace: None,
+            name: None,
+            container_name: None,
+            container_id: Some(CONTAINER_ID.to_string()),
+        };
+
+        enrich_with_cri_labels(&mut pod, &cri_containers);
+
+        assert_eq!(pod.display_name(), "default/nginx-7c5/nginx");
+    }
+
+    #[test]
+    fn should_fallback_to_uid_when_pod_is_not_known_to_cri() {
+        let pod = PodInfo {
+            uid: "2b1c-44aa".to_string(),
+            namespace: None,
+            name: None,
+            container_name: None,
+            container_id: None,
+        };
+
+        assert_eq!(pod.display_name(), "2b1c-44aa");
+    }
+
+    #[test]
+    fn should_parse_crictl_output() {
+        //NOTE: output of 'crictl ps -o json' run against containerd of kind node
+        let output = format!(
+            r#"{{
+  "containers": [
+    {{
+      "id": "{CONTAINER_ID}",
+      "podSandboxId": "9b2f6c1de0a4b7c3f5e8d9a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1",
+      "metadata": {{
+        "name": "nginx",
+        "attempt": 0
+      }},
+      "image": {{
+        "image": "sha256:3b25b682ea82b2db3cc4fd48db818be788ee3f902ac7378090cf2624ec2442df",
+        "annotations": {{
+        }},
+        "userSpecifiedImage": "",
+        "runtimeHandler": ""
+      }},
+      "imageRef": "sha256:3b25b682ea82b2db3cc4fd48db818be788ee3f902ac7378090cf2624ec2442df",
+      "state": "CONTAINER_RUNNING",
+      "createdAt": "1729260412339187402",
+      "labels": {{
+        "io.kubernetes.container.name": "nginx",
+        "io.kubernetes.pod.name": "nginx-7c5",
+        "io.kubernetes.pod.namespace": "default",
+        "io.kubernetes.pod.uid": "2b1c-44aa"
+      }},
+      "annotations": {{
+        "io.kubernetes.container.hash": "a0c5ca5f",
+        "io.kubernetes.container.restartCount": "0",
+        "io.kubernetes.container.terminationMessagePath": "/dev/termination-log",
+        "io.kubernetes.container.terminationMessagePolicy": "File",
+        "io.kubernetes.pod.terminationGracePeriod": "30"
+      }}
+    }}
+  ]
+}}"#
+        );
+
+        let containers = parse_crictl_output(output.as_bytes()).unwrap();
+
+        assert_eq!(containers.len(), 1);
+        assert_eq!(containers[0].id, CONTAINER_ID);
+        assert_eq!(
+            containers[0].labels.get("io.kubernetes.pod.name"),
+            nginx_container().labels.get("io.kubernetes.pod.name")
+        );
+        assert!(parse_crictl_output(b"{}").unwrap().is_empty());
+        assert!(parse_crictl_output(b"FATA[0000] connect: permission denied").is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn should_look_up_pods_once_per_process_and_container() {
+        // given
+        let dir = std::env::temp_dir().join(format!("pik-cri-runtime-{}", std::process::id()));
+        let proc_dir = dir.join("proc");
+        for (pid, cgroup) in [
+            (
+                1000,
+                format!("0::/kubepods/besteffort/pod2b1c-44aa/{CONTAINER_ID}"),
+            ),
+            (1001, "0::/user.slice/user-1000.slice".to_string()),
+        ] {
+            std::fs::create_dir_all(proc_dir.join(pid.to_string())).unwrap();
+            std::fs::write(proc_dir.join(pid.to_string()).join("cgroup"), cgroup).unwrap();
+        }
+        let socket = runtime_socket(&dir);
+        let crictl = fake_crictl(&dir, &socket);
+        //NOTE: paths that are missing or are not sockets are not passed to crictl
+        let regular_file = dir.join("crio.sock");
+        std::fs::write(&regular_file, "").unwrap();
+        let mut cache = PodCache::new(
+            &proc_dir,
+            &[dir.join("missing.sock"), regular_file, socket],
+            &crictl,
+        );
+
+        // when
+        let first_refresh = cache.refresh([(1000, 1), (1001, 1)].into_iter());
+        let first_lookup = cache.get(&1000).map(PodInfo::display_name);
+        //NOTE: cgroup is not read again for the same process
+        std::fs::remove_dir_all(proc_dir.join("1000")).unwrap();
+        cache.refresh([(1000, 1), (1001, 1)].into_iter()).unwrap();
+        let cached_lookup = cache.get(&1000).map(PodInfo::display_name);
+        cache.refresh([(1000, 2), (1001, 1)].into_iter()).unwrap();
+        let reused_pid_lookup = cache.get(&1000).cloned();
+        std::fs::create_dir_all(proc_dir.join("1002")).unwrap();
+        std::fs::write(
+            proc_dir.join("1002").join("cgroup"),
+            format!("0::/kubepods/besteffort/pod2b1c-44aa/{CONTAINER_ID}"),
+        )
+        .unwrap();
+        cache.refresh([(1001, 1), (1002, 1)].into_iter()).unwrap();
+        let same_container_lookup = cache.get(&1002).map(PodInfo::display_name);
+        let crictl_calls = crictl_calls(&dir);
+        std::fs::remove_dir_all(&dir).ok();
+
+        // then
+        assert!(first_refresh.is_ok());
+        assert_eq!(first_lookup.as_deref(), Some("default/nginx-7c5/nginx"));
+        assert_eq!(cached_lookup.as_deref(), Some("default/nginx-7c5/nginx"));
+        assert_eq!(reused_pid_lookup, None);
+        assert_eq!(
+            same_container_lookup.as_deref(),
+            Some("default/nginx-7c5/nginx")
+        );
+        assert_eq!(cache.get(&1001), None);
+        assert_eq!(crictl_calls, 1);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn should_ask_runtime_again_until_it_answers() {
+        // given
+        let dir = std::env::temp_dir().join(format!("pik-cri-denied-{}", std::process::id()));
+        let proc_dir = pod_proc_dir(&dir);
+        let socket = runtime_socket(&dir);
+        let crictl = fake_crictl(&dir, &socket);
+        //NOTE: fake crictl is denied access to socket while 'denied' file exists
+        std::fs::write(dir.join("denied"), "").unwrap();
+        let mut cache = PodCache::new(&proc_dir, &[&socket], &crictl);
+        cache.retry_backoff = Duration::ZERO;
+
+        // when
+        let refresh = cache.refresh([(1000, 1)].into_iter());
+        let next_refresh = cache.refresh([(1000, 1)].into_iter());
+        let denied_lookup = cache.get(&1000).map(PodInfo::display_name);
+        std::fs::remove_file(dir.join("denied")).unwrap();
+        let granted_refresh = cache.refresh([(1000, 1)].into_iter());
+        let granted_lookup = cache.get(&1000).map(PodInfo::display_name);
+        cache.refresh([(1000, 1)].into_iter()).unwrap();
+        let crictl_calls = crictl_calls(&dir);
+        std::fs::remove_dir_all(&dir).ok();
+
+        // then
+        let error = refresh.unwrap_err().to_string();
+        assert!(error.starts_with("Pod names are unavailable"));
+        assert!(error.contains("permission denied"));
+        assert!(next_refresh.is_err());
+        assert_eq!(denied_lookup.as_deref(), Some("2b1c-44aa"));
+        assert!(granted_refresh.is_ok());
+        assert_eq!(granted_lookup.as_deref(), Some("default/nginx-7c5/nginx"));
+        assert_eq!(crictl_calls, 3);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn should_not_ask_failing_runtime_again_until_backoff_passes() {
+        // given
+        let dir = std::env::temp_dir().join(format!("pik-cri-backoff-{}", std::process::id()));
+        let proc_dir = pod_proc_dir(&dir);
+        let socket = runtime_socket(&dir);
+        let crictl = fake_crictl(&dir, &socket);
+        std::fs::write(dir.join("denied"), "").unwrap();
+        let mut cache = PodCache::new(&proc_dir, &[&socket], &crictl);
+
+        // when
+        let refresh = cache.refresh([(1000, 1)].into_iter());
+        let backoff_refresh = cache.refresh([(1000, 1)].into_iter());
+        let crictl_calls = crictl_calls(&dir);
+        std::fs::remove_dir_all(&dir).ok();
+
+        // then
+        assert!(refresh.is_err());
+        assert_eq!(
+            backoff_refresh.unwrap_err().to_string(),
+            refresh.unwrap_err().to_string()
+        );
+        assert_eq!(crictl_calls, 1);
+        assert_eq!(backoff(RETRY_BACKOFF, 1), RETRY_BACKOFF);
+        assert_eq!(backoff(RETRY_BACKOFF, 3), RETRY_BACKOFF * 4);
+        assert_eq!(backoff(RETRY_BACKOFF, 40), MAX_RETRY_BACKOFF);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn should_give_up_on_runtime_that_does_not_answer() {
+        // given
+        let dir = std::env::temp_dir().join(format!("pik-cri-hang-{}", std::process::id()));
+        let proc_dir = pod_proc_dir(&dir);
+        let socket = runtime_socket(&dir);
+        let crictl = fake_crictl(&dir, &socket);
+        //NOTE: fake crictl hangs while 'hang' file exists
+        std::fs::write(dir.join("hang"), "").unwrap();
+        let mut cache = PodCache::new(&proc_dir, &[&socket], &crictl);
+        cache.lookup_timeout = Duration::from_millis(100);
+
+        // when
+        let started = Instant::now();
+        let refresh = cache.refresh([(1000, 1)].into_iter());
+        let took = started.elapsed();
+        std::fs::remove_dir_all(&dir).ok();
+
+        // then
+        assert!(took < Duration::from_secs(5));
+        assert_eq!(
+            refresh.unwrap_err().to_string(),
+            "Pod names are unavailable, crictl did not answer within 100ms"
+        );
+        assert_eq!(
+            cache.get(&1000).map(PodInfo::display_name).as_deref(),
+            Some("2b1c-44aa")
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn should_fallback_to_pod_uid_when_crictl_is_not_installed() {
+        // given
+        let dir = std::env::temp_dir().join(format!("pik-cri-no-crictl-{}", std::process::id()));
+        let proc_dir = pod_proc_dir(&dir);
+        let socket = runtime_socket(&dir);
+        let mut cache = PodCache::new(&proc_dir, &[&socket], dir.join("crictl"));
+
+        // when
+        let refresh = cache.refresh([(1000, 1)].into_iter());
+        std::fs::remove_dir_all(&dir).ok();
+
+        // then
+        assert_eq!(
+            refresh.unwrap_err().to_string(),
+            "Pod names are unavailable, crictl is not installed"
+        );
+        assert_eq!(
+            cache.get(&1000).map(PodInfo::display_name).as_deref(),
+            Some("2b1c-44aa")
+        );
+    }
+
+    /// Proc dir with process 1000 running in nginx pod container
+    #[cfg(target_os = "linux")]
+    fn pod_proc_dir(dir: &Path) -> PathBuf {
+        let proc_dir = dir.join("proc");
+        std::fs::create_dir_all(proc_dir.join("1000")).unwrap();
+        std::fs::write(
+            proc_dir.join("1000").join("cgroup"),
+            format!("0::/kubepods/besteffort/pod2b1c-44aa/{CONTAINER_ID}"),
+        )
+        .unwrap();
+        proc_dir
+    }
+
+    /// Socket file runtime would listen on, nothing answers on it as runtime is reached only through crictl
+    #[cfg(target_os = "linux")]
+    fn runtime_socket(dir: &Path) -> PathBuf {
+        let socket = dir.join("containerd.sock");
+        std::fs::create_dir_all(dir).unwrap();
+        //NOTE: bound socket file stays after listener is dropped
+        std::os::unix::net::UnixListener::bind(&socket).unwrap();
+        socket
+    }
+
+    /// Fake crictl answers like the real one, counts calls in 'calls' file,
+    /// fails like denied one while 'denied' file exists and hangs while 'hang' file exists
+    #[cfg(target_os = "linux")]
+    fn fake_crictl(dir: &Path, socket: &Path) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let crictl = dir.join("crictl");
+        std::fs::write(
+            &crictl,
+            format!(
+                r#"#!/bin/sh
+[ "$2" = "unix://{socket}" ] || exit 1
+echo call >> {dir}/calls
+if [ -e {dir}/hang ]; then
+  exec sleep 10
+fi
+if [ -e {dir}/denied ]; then
+  echo 'connect: permission denied' >&2
+  exit 1
+fi
+echo '{{"containers":[{{"id":"{CONTAINER_ID}","labels":{{"io.kubernetes.pod.namespace":"default","io.kubernetes.pod.name":"nginx-7c5","io.kubernetes.container.name":"nginx"}}}}]}}'
+"#,
+                socket = socket.display(),
+                dir = dir.display(),
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&crictl, std::fs::Permissions::from_mode(0o755)).unwrap();
+        crictl
+    }
+
+    #[cfg(target_os = "linux")]
+    fn crictl_calls(dir: &Path) -> usize {
+        std::fs::read_to_string(dir.join("calls"))
+            .map(|calls| calls.lines().count())
+            .unwrap_or(0)
+    }
+
+    fn nginx_container() -> CriContainer {
+        CriContainer {
+            id: CONTAINER_ID.to_string(),
+            labels: HashMap::from([
+                (
+                    "io.kubernetes.pod.namespace".to_string(),
+                    "default".to_string(),
+                ),
+                (
+                    "io.kubernetes.pod.name".to_string(),
+                    "nginx-7c5".to_string(),
+                ),
+                (
+                    "io.kubernetes.container.name".to_string(),
+                    "nginx".to_string(),
+                ),
+            ]),
+        }
+    }
+}
@@ -1,74 +1,420 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::net::{IpAddr, SocketAddr};
 
-use super::container::get_container_ports;
+use super::Process;
+use super::container::{Container, get_container_ports};
 use itertools::Itertools;
-use listeners::Listener;
+use listeners::{Listener, Protocol, SocketState};
 
 #[derive(Default)]
 pub struct ProcessPorts {
-    ports: HashMap<u32, String>,
+    ports: HashMap<u32, PortsInfo>,
+    bindings: Vec<PortBinding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PortsInfo {
+    // one entry per port with bind scope indicator, i.e. '8080(lo)'
+    pub summary: String,
+    // every bound address, i.e. '127.0.0.1:8080/tcp, [::1]:8080/tcp'
+    pub bindings: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct PortBinding {
+    pub pid: u32,
+    pub socket: SocketAddr,
+    pub protocol: Protocol,
+    // port inside container that host port is published to
+    pub container_port: Option<u16>,
+    pub state: SocketState,
+}
+
+impl From<Listener> for PortBinding {
+    fn from(listener: Listener) -> Self {
+        Self {
+            pid: listener.process.pid,
+            socket: listener.socket,
+            protocol: listener.protocol,
+            container_port: None,
+            state: listener.state,
+        }
+    }
+}
+
+/// Answers "who holds this port?", see [ProcessManager::check_port](super::ProcessManager::check_port)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortCheck {
+    pub port: u16,
+    pub owners: Vec<PortOwner>,
+    pub unowned: Vec<UnownedSocket>,
+}
+
+impl PortCheck {
+    pub fn is_free(&self) -> bool {
+        self.owners.is_empty() && self.unowned.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortOwner {
+    pub process: Process,
+    // i.e. '0.0.0.0:8080/tcp' or '0.0.0.0:8080→80/tcp' for published container ports
+    pub binding: String,
+    pub state: String,
+}
+
+/// Socket that is still bound to port but has no process anymore, i.e. TIME_WAIT after server was stopped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnownedSocket {
+    pub binding: String,
+    pub state: String,
+}
+
+impl PortBinding {
+    fn port(&self) -> String {
+        match self.container_port {
+            Some(container_port) => format!(
+                "{}→{container_port}/{}",
+                self.socket.port(),
+                protocol_name(self.protocol)
+            ),
+            None => self.socket.port().to_string(),
+        }
+    }
+}
+
+impl Display for PortBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.container_port {
+            Some(container_port) => write!(
+                f,
+                "{}→{container_port}/{}",
+                self.socket,
+                protocol_name(self.protocol)
+            ),
+            None => write!(f, "{}/{}", self.socket, protocol_name(self.protocol)),
+        }
+    }
+}
+
+fn protocol_name(protocol: Protocol) -> &'static str {
+    match protocol {
+        Protocol::TCP => "tcp",
+        Protocol::UDP => "udp",
+    }
+}
+
+/// Where port is reachable from. Loopback only bindings are frequent cause of
+/// "can't reach my dev server" issues so they are marked in ports column
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BindScope {
+    AllInterfaces,
+    Loopback,
+    Interfaces(Vec<IpAddr>),
+}
+
+impl BindScope {
+    fn of(addresses: impl Iterator<Item = IpAddr>) -> Self {
+        let mut interfaces = vec![];
+        for ip in addresses.map(|ip| ip.to_canonical()) {
+            if ip.is_unspecified() {
+                return BindScope::AllInterfaces;
+            }
+            if !ip.is_loopback() {
+                interfaces.push(ip);
+            }
+        }
+        if interfaces.is_empty() {
+            return BindScope::Loopback;
+        }
+        interfaces.sort();
+        interfaces.dedup();
+        BindScope::Interfaces(interfaces)
+    }
+
+    fn indicator(&self) -> String {
+        match self {
+            BindScope::AllInterfaces => String::new(),
+            BindScope::Loopback => "(lo)".to_string(),
+            //NOTE: '|' would split copied summary into alternative queries
+            BindScope::Interfaces(ips) => format!("({})", ips.iter().join(",")),
+        }
+    }
 }
 
 impl ProcessPorts {
-    pub fn new_refreshed() -> ProcessPorts {
-        let mut listeners = listeners::get_all()
+    pub fn new_refreshed(containers: &[Container]) -> ProcessPorts {
+        let listeners = listeners::get_all()
             //NOTE: we ignore errors coming from listeners
             .unwrap_or_default();
-        listeners.extend(get_container_ports());
+        let bindings = listeners
+            .into_iter()
+            .map(PortBinding::from)
+            .chain(get_container_ports(containers))
+            .collect::<Vec<_>>();
 
         Self {
-            ports: create_sorted_process_ports(listeners),
+            ports: create_sorted_process_ports(bindings.iter().cloned()),
+            bindings,
         }
     }
 
-    pub fn get(&self, pid: &u32) -> Option<&String> {
+    pub fn get(&self, pid: &u32) -> Option<&PortsInfo> {
         self.ports.get(pid)
     }
+
+    pub(super) fn bindings_on_port(&self, port: u16) -> impl Iterator<Item = &PortBinding> {
+        self.bindings
+            .iter()
+            .filter(move |b| b.socket.port() == port)
+            .sorted_by_key(|b| (b.pid, b.socket))
+    }
+}
+
+/// Finds sockets bound to port that are not owned by any process.
+/// Kernel keeps them (mostly in TIME_WAIT) for a while and they still may block the port
+#[cfg(target_os = "linux")]
+pub(super) fn find_unowned_sockets(port: u16) -> Vec<UnownedSocket> {
+    ["/proc/net/tcp", "/proc/net/tcp6"]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .flat_map(|content| parse_unowned_sockets(&content, port))
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(super) fn find_unowned_sockets(_port: u16) -> Vec<UnownedSocket> {
+    vec![]
+}
+
+// Line format: 'sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode ...'
+// sockets without owner have inode 0
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_unowned_sockets(content: &str, port: u16) -> Vec<UnownedSocket> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let (local_address, state, inode) = (fields.get(1)?, fields.get(3)?, fields.get(9)?);
+            if *inode != "0" {
+                return None;
+            }
+            let socket = parse_proc_net_address(local_address)?;
+            (socket.port() == port).then(|| UnownedSocket {
+                binding: format!("{socket}/tcp"),
+                state: tcp_state_name(state).to_string(),
+            })
+        })
+        .collect()
+}
+
+// Address is hex encoded IP in host byte order (in 4 byte groups for IPv6) and hex port, i.e. '0100007F:1F90'
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_net_address(address: &str) -> Option<SocketAddr> {
+    let (ip, port) = address.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut octets = vec![];
+    for group in ip.as_bytes().chunks(8) {
+        let group = u32::from_str_radix(std::str::from_utf8(group).ok()?, 16).ok()?;
+        octets.extend(group.to_ne_bytes());
+    }
+    let ip = match octets.len() {
+        4 => IpAddr::from(<[u8; 4]>::try_from(octets).ok()?),
+        16 => IpAddr::from(<[u8; 16]>::try_from(octets).ok()?),
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn tcp_state_name(state: &str) -> &'static str {
+    match state {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECEIVED",
+        "04" => "FIN_WAIT_1",
+        "05" => "FIN_WAIT_2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSED",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        _ => "UNKNOWN",
+    }
 }
 
 //NOTE: we sort this so order of ports is deterministic and doesn't change during refresh
-fn create_sorted_process_ports(ports: HashSet<Listener>) -> HashMap<u32, String> {
-    ports
+fn create_sorted_process_ports(
+    bindings: impl IntoIterator<Item = PortBinding>,
+) -> HashMap<u32, PortsInfo> {
+    bindings
         .into_iter()
-        .map(|l| (l.process.pid, l.socket.port()))
+        .map(|b| (b.pid, b))
         .into_group_map()
         .into_iter()
-        .map(|(pid, ports)| (pid, ports.into_iter().sorted_by(|a, b| a.cmp(b)).join(", ")))
+        .map(|(pid, bindings)| (pid, create_ports_info(bindings)))
         .collect()
 }
 
+fn create_ports_info(mut bindings: Vec<PortBinding>) -> PortsInfo {
+    bindings.sort_by_key(|b| {
+        (
+            b.socket.port(),
+            b.container_port,
+            protocol_name(b.protocol),
+            b.socket.ip(),
+        )
+    });
+    // same port bound on many addresses (i.e. 0.0.0.0 and ::) is shown once
+    let summary = bindings
+        .iter()
+        .chunk_by(|b| b.port())
+        .into_iter()
+        .map(|(port, same_port)| {
+            let scope = BindScope::of(same_port.map(|b| b.socket.ip()));
+            format!("{port}{}", scope.indicator())
+        })
+        .join(", ");
+    let bindings = bindings.iter().map(|b| b.to_string()).dedup().join(", ");
+    PortsInfo { summary, bindings }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
-
-    use listeners::{Listener, Process};
-
     use super::*;
 
     #[test]
     fn should_create_sorted_process_ports() {
         let value = [
-            create_listener(1, 8080),
-            create_listener(1, 100),
-            create_listener(1, 50),
-            create_listener(2, 1234),
+            create_binding(1, "0.0.0.0:8080"),
+            create_binding(1, "0.0.0.0:100"),
+            create_binding(1, "0.0.0.0:50"),
+            create_binding(2, "0.0.0.0:1234"),
         ];
-        let process_ports = create_sorted_process_ports(HashSet::from(value));
+        let process_ports = create_sorted_process_ports(value);
         assert_eq!(process_ports.len(), 2);
-        assert_eq!(process_ports.get(&1).unwrap(), "50, 100, 8080");
-        assert_eq!(process_ports.get(&2).unwrap(), "1234");
+        assert_eq!(process_ports.get(&1).unwrap().summary, "50, 100, 8080");
+        assert_eq!(process_ports.get(&2).unwrap().summary, "1234");
     }
 
-    fn create_listener(pid: u32, port: u16) -> Listener {
-        Listener {
-            process: Process {
-                pid,
-                name: format!("p1{pid}"),
-                path: format!("p1{pid}"),
+    #[test]
+    fn should_show_container_port_mapping() {
+        let value = [
+            PortBinding {
+                container_port: Some(80),
+                ..create_binding(1, "0.0.0.0:8080")
             },
-            socket: format!("127.0.0.1:{port}").parse().unwrap(),
-            protocol: listeners::Protocol::TCP,
-            state: listeners::SocketState::Listen,
+            PortBinding {
+                container_port: Some(53),
+                protocol: Protocol::UDP,
+                ..create_binding(1, "0.0.0.0:5353")
+            },
+        ];
+        let ports = create_sorted_process_ports(value).remove(&1).unwrap();
+        assert_eq!(ports.summary, "5353→53/udp, 8080→80/tcp");
+        assert_eq!(ports.bindings, "0.0.0.0:5353→53/udp, 0.0.0.0:8080→80/tcp");
+    }
+
+    #[test]
+    fn should_merge_same_port_bound_on_many_addresses() {
+        let value = [
+            create_binding(1, "0.0.0.0:8080"),
+            create_binding(1, "[::1]:8080"),
+            create_binding(1, "[::]:8080"),
+        ];
+        let ports = create_sorted_process_ports(value).remove(&1).unwrap();
+        assert_eq!(ports.summary, "8080");
+        assert_eq!(
+            ports.bindings,
+            "0.0.0.0:8080/tcp, [::]:8080/tcp, [::1]:8080/tcp"
+        );
+    }
+
+    #[test]
+    fn should_mark_loopback_only_and_interface_bindings() {
+        let value = [
+            create_binding(1, "127.0.0.1:3000"),
+            create_binding(1, "[::1]:3000"),
+            create_binding(1, "[::ffff:127.0.0.1]:4000"),
+            create_binding(1, "192.168.1.5:5000"),
+            create_binding(1, "127.0.0.1:5000"),
+            create_binding(1, "0.0.0.0:6000"),
+            create_binding(1, "192.168.1.5:7000"),
+            create_binding(1, "10.0.0.2:7000"),
+        ];
+        let ports = create_sorted_process_ports(value).remove(&1).unwrap();
+        assert_eq!(
+            ports.summary,
+            "3000(lo), 4000(lo), 5000(192.168.1.5), 6000, 7000(10.0.0.2,192.168.1.5)"
+        );
+    }
+
+    fn create_binding(pid: u32, socket: &str) -> PortBinding {
+        PortBinding {
+            pid,
+            socket: socket.parse().unwrap(),
+            protocol: Protocol::TCP,
+            container_port: None,
+            state: SocketState::Listen,
         }
     }
+
+    #[test]
+    fn should_find_bindings_on_port() {
+        let process_ports = ProcessPorts {
+            ports: HashMap::new(),
+            bindings: vec![
+                create_binding(2, "0.0.0.0:8080"),
+                create_binding(1, "127.0.0.1:9090"),
+                PortBinding {
+                    container_port: Some(80),
+                    ..create_binding(1, "[::]:8080")
+                },
+            ],
+        };
+
+        let owners = process_ports
+            .bindings_on_port(8080)
+            .map(|b| (b.pid, b.to_string()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            owners,
+            vec![
+                (1, "[::]:8080→80/tcp".to_string()),
+                (2, "0.0.0.0:8080/tcp".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn should_parse_unowned_sockets() {
+        let content = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4242 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1F90 0100007F:D2F0 06 00000000:00000000 03:00001770 00000000     0        0 0 3 0000000000000000
+   2: 0100007F:2382 0100007F:D2F2 06 00000000:00000000 03:00001770 00000000     0        0 0 3 0000000000000000";
+
+        let sockets = parse_unowned_sockets(content, 8080);
+
+        assert_eq!(
+            sockets,
+            vec![UnownedSocket {
+                binding: "127.0.0.1:8080/tcp".to_string(),
+                state: "TIME_WAIT".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_parse_ipv6_proc_net_address() {
+        assert_eq!(
+            parse_proc_net_address("00000000000000000000000001000000:1F90"),
+            Some("[::1]:8080".parse().unwrap())
+        );
+        assert_eq!(parse_proc_net_address("not-an-address"), None);
+    }
 }
@@ -0,0 +1,143 @@
+use std::process::Command;
+
+use itertools::Itertools;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
+
+use super::signal_name;
+
+/// Kill retried through helper like `sudo` when user is not allowed to signal process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivilegedKill {
+    pub pid: u32,
+    // start time of process that was denied, process with other start time reused its PID
+    pub start_time: Option<u64>,
+    pub name: String,
+    pub args: String,
+    pub signal: Signal,
+}
+
+impl PrivilegedKill {
+    /// Builds `<helper...> kill -s <signal> <pid>`, returns None when no helper is configured
+    /// or signal has no name `kill` would understand
+    pub fn command(&self, helper: &[String]) -> Option<Command> {
+        let (program, helper_args) = helper.split_first()?;
+        let mut command = Command::new(program);
+        command.args(helper_args).args(self.kill_args()?);
+        Some(command)
+    }
+
+    pub fn command_line(&self, helper: &[String]) -> Option<String> {
+        if helper.is_empty() {
+            return None;
+        }
+        Some(helper.iter().cloned().chain(self.kill_args()?).join(" "))
+    }
+
+    /// Helper runs as root, so it must not signal new process that reused PID meanwhile
+    pub fn is_same_process(&self) -> bool {
+        let pid = Pid::from_u32(self.pid);
+        let mut sys = System::new();
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+        sys.process(pid).is_some_and(|prc| {
+            self.start_time
+                .is_none_or(|start_time| start_time == prc.start_time())
+        })
+    }
+
+    fn kill_args(&self) -> Option<[String; 4]> {
+        let signal = signal_name(self.signal).strip_prefix("SIG")?;
+        Some([
+            "kill".to_string(),
+            "-s".to_string(),
+            signal.to_string(),
+            self.pid.to_string(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_build_helper_command() {
+        let kill = PrivilegedKill {
+            pid: 4242,
+            start_time: None,
+            name: "nginx".to_string(),
+            args: String::new(),
+            signal: Signal::Term,
+        };
+        let helper = vec!["sudo".to_string(), "-k".to_string()];
+
+        let command = kill.command(&helper).unwrap();
+
+        assert_eq!(command.get_program(), "sudo");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec!["-k", "kill", "-s", "TERM", "4242"]
+        );
+        assert_eq!(
+            kill.command_line(&helper).as_deref(),
+            Some("sudo -k kill -s TERM 4242")
+        );
+    }
+
+    #[test]
+    fn should_not_build_command_without_helper() {
+        let kill = PrivilegedKill {
+            pid: 4242,
+            start_time: None,
+            name: "nginx".to_string(),
+            args: String::new(),
+            signal: Signal::Kill,
+        };
+
+        assert!(kill.command(&[]).is_none());
+        assert!(kill.command_line(&[]).is_none());
+    }
+
+    #[test]
+    fn should_not_build_command_for_signal_without_name() {
+        let kill = PrivilegedKill {
+            pid: 4242,
+            start_time: None,
+            name: "nginx".to_string(),
+            args: String::new(),
+            signal: Signal::User1,
+        };
+        let helper = vec!["sudo".to_string()];
+
+        assert!(kill.command(&helper).is_none());
+        assert!(kill.command_line(&helper).is_none());
+    }
+
+    #[test]
+    fn should_tell_process_apart_from_one_that_reused_its_pid() {
+        let pid = std::process::id();
+        let mut sys = System::new();
+        sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]), true);
+        let start_time = sys.process(Pid::from_u32(pid)).unwrap().start_time();
+        let kill = |start_time| PrivilegedKill {
+            pid,
+            start_time,
+            name: "pik".to_string(),
+            args: String::new(),
+            signal: Signal::Term,
+        };
+
+        assert!(kill(Some(start_time)).is_same_process());
+        assert!(!kill(Some(start_time + 1)).is_same_process());
+        assert!(
+            !PrivilegedKill {
+                pid: u32::MAX / 2,
+                ..kill(None)
+            }
+            .is_same_process()
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::Process;
+
+/// What happens when user tries to kill protected process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectedAction {
+    // user has to type process name to kill it
+    #[default]
+    Confirm,
+    Refuse,
+}
+
+/// Decides if process may be killed right away
+#[derive(Debug, Clone)]
+pub struct KillGuard {
+    // ask for confirmation before killing any process
+    pub confirm_all: bool,
+    pub protected_action: ProtectedAction,
+    // matched against process name and path
+    pub patterns: Vec<Regex>,
+    pub root: bool,
+    pub init: bool,
+    pub pik: bool,
+    pub parent_shell: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KillCheck {
+    Allowed,
+    Confirm,
+    // reason why process is protected
+    ConfirmTyped(String),
+    Refused(String),
+}
+
+impl Default for KillGuard {
+    fn default() -> Self {
+        Self {
+            confirm_all: false,
+            protected_action: ProtectedAction::Confirm,
+            patterns: vec![],
+            root: true,
+            init: true,
+            pik: true,
+            parent_shell: true,
+        }
+    }
+}
+
+impl KillGuard {
+    pub fn check(&self, prc: &Process) -> KillCheck {
+        match (self.protection_reason(prc), self.protected_action) {
+            (Some(reason), ProtectedAction::Confirm) => KillCheck::ConfirmTyped(reason),
+            (Some(reason), ProtectedAction::Refuse) => KillCheck::Refused(reason),
+            (None, _) if self.confirm_all => KillCheck::Confirm,
+            (None, _) => KillCheck::Allowed,
+        }
+    }
+
+    fn protection_reason(&self, prc: &Process) -> Option<String> {
+        if self.init && prc.pid == 1 {
+            return Some("PID 1 keeps the whole system running".to_string());
+        }
+        if self.pik && prc.pid == std::process::id() {
+            return Some("it is pik itself".to_string());
+        }
+        if self.parent_shell && Some(prc.pid) == parent_shell_pid() {
+            return Some("it is the shell pik was started from".to_string());
+        }
+        if self.root && prc.user_name == "root" {
+            return Some("it is owned by root".to_string());
+        }
+        self.patterns
+            .iter()
+            .find(|pattern| {
+                pattern.is_match(&prc.cmd)
+                    || prc.cmd_path.as_deref().is_some_and(|p| pattern.is_match(p))
+            })
+            .map(|pattern| format!("it matches protected pattern '{pattern}'"))
+    }
+}
+
+#[cfg(unix)]
+fn parent_shell_pid() -> Option<u32> {
+    Some(std::os::unix::process::parent_id())
+}
+
+#[cfg(not(unix))]
+fn parent_shell_pid() -> Option<u32> {
+    None
+}
+
+impl PartialEq for KillGuard {
+    fn eq(&self, other: &Self) -> bool {
+        self.confirm_all == other.confirm_all
+            && self.protected_action == other.protected_action
+            && self.root == other.root
+            && self.init == other.init
+            && self.pik == other.pik
+            && self.parent_shell == other.parent_shell
+            && self
+                .patterns
+                .iter()
+                .map(|r| r.as_str())
+                .collect::<Vec<&str>>()
+                == other
+                    .patterns
+                    .iter()
+                    .map(|r| r.as_str())
+                    .collect::<Vec<&str>>()
+    }
+}
+
+impl Eq for KillGuard {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_allow_kill_of_not_protected_process() {
+        let guard = KillGuard::default();
+
+        assert_eq!(guard.check(&Process::test(4242)), KillCheck::Allowed);
+    }
+
+    #[test]
+    fn should_require_confirmation_when_configured() {
+        let guard = KillGuard {
+            confirm_all: true,
+            ..Default::default()
+        };
+
+        assert_eq!(guard.check(&Process::test(4242)), KillCheck::Confirm);
+    }
+
+    #[test]
+    fn should_protect_init_pik_and_root_processes() {
+        let guard = KillGuard::default();
+
+        assert!(matches!(
+            guard.check(&Process::test(1)),
+            KillCheck::ConfirmTyped(_)
+        ));
+        assert!(matches!(
+            guard.check(&Process::test(std::process::id())),
+            KillCheck::ConfirmTyped(_)
+        ));
+        assert!(matches!(
+            guard.check(&Process {
+                user_name: "root".to_string(),
+                ..Process::test(4242)
+            }),
+            KillCheck::ConfirmTyped(_)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_protect_parent_shell() {
+        let guard = KillGuard::default();
+        let shell = Process::test(std::os::unix::process::parent_id());
+
+        assert!(matches!(guard.check(&shell), KillCheck::ConfirmTyped(_)));
+    }
+
+    #[test]
+    fn should_refuse_kill_of_process_matching_pattern() {
+        let guard = KillGuard {
+            protected_action: ProtectedAction::Refuse,
+            patterns: vec![Regex::new("^/usr/bin/.*sh$").unwrap()],
+            ..Default::default()
+        };
+        let mut prc = Process::test(4242);
+        prc.cmd_path = Some("/usr/bin/zsh".to_string());
+
+        assert_eq!(
+            guard.check(&prc),
+            KillCheck::Refused("it matches protected pattern '^/usr/bin/.*sh$'".to_string())
+        );
+    }
+
+    #[test]
+    fn should_skip_disabled_rules() {
+        let guard = KillGuard {
+            root: false,
+            init: false,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            guard.check(&Process {
+                user_name: "root".to_string(),
+                ..Process::test(1)
+            }),
+            KillCheck::Allowed
+        );
+    }
+}
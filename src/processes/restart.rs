@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+/// Command line of process captured before it is killed, so the same process can be started again
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartCommand {
+    pub exe: PathBuf,
+    // arguments without program name
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+}
+
+impl RestartCommand {
+    pub fn new(exe: PathBuf, argv: &[String], cwd: Option<PathBuf>) -> Self {
+        //NOTE: linux marks replaced binary with ' (deleted)', i.e. after rebuild, new binary is started then
+        let exe = match exe
+            .to_str()
+            .and_then(|path| path.strip_suffix(" (deleted)"))
+        {
+            Some(path) => PathBuf::from(path),
+            None => exe,
+        };
+        Self {
+            exe,
+            args: argv.iter().skip(1).cloned().collect(),
+            cwd,
+        }
+    }
+
+    /// Spawns process detached from pik, it is not killed when pik exits and its output is discarded
+    pub fn spawn(&self) -> Result<u32> {
+        let mut command = Command::new(&self.exe);
+        command
+            .args(&self.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(cwd) = &self.cwd {
+            command.current_dir(cwd);
+        }
+        //NOTE: own process group keeps terminal signals like ctrl+c away from restarted process
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+        let mut child = command
+            .spawn()
+            .with_context(|| format!("Failed to start {:?}", self.exe))?;
+        let pid = child.id();
+        //NOTE: child has to be reaped when it exits, otherwise it stays as zombie while pik runs
+        std::thread::spawn(move || child.wait());
+        Ok(pid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_skip_program_name() {
+        let argv = vec![
+            "node".to_string(),
+            "server.js".to_string(),
+            "--port".to_string(),
+            "3000".to_string(),
+        ];
+
+        let command = RestartCommand::new(PathBuf::from("/usr/bin/node"), &argv, None);
+
+        assert_eq!(command.args, vec!["server.js", "--port", "3000"]);
+    }
+
+    #[test]
+    fn should_start_rebuilt_binary() {
+        let argv = vec!["server".to_string()];
+
+        let command = RestartCommand::new(PathBuf::from("/app/server (deleted)"), &argv, None);
+
+        assert_eq!(command.exe, PathBuf::from("/app/server"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_spawn_process_in_working_directory() {
+        let dir = std::env::temp_dir().join(format!("pik-restart-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let argv = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "pwd > cwd.txt".to_string(),
+        ];
+        let command = RestartCommand::new(PathBuf::from("/bin/sh"), &argv, Some(dir.clone()));
+
+        let pid = command.spawn().unwrap();
+
+        assert!(pid > 0);
+        let written = dir.join("cwd.txt");
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
+        while !std::fs::read_to_string(&written).is_ok_and(|cwd| !cwd.is_empty())
+            && std::time::Instant::now() < deadline
+        {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let cwd = std::fs::read_to_string(&written).unwrap();
+        assert_eq!(
+            PathBuf::from(cwd.trim()).canonicalize().unwrap(),
+            dir.canonicalize().unwrap()
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
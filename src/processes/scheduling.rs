@@ -0,0 +1,441 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use itertools::Itertools;
+
+const MIN_NICE: i32 = -20;
+const MAX_NICE: i32 = 19;
+// same as CPU_SETSIZE, kernel rejects CPUs that are not online anyway
+const MAX_CPUS: usize = 1024;
+
+/// Scheduling of process, it is read for single process on demand as it takes syscalls per process.
+/// Values are read from main thread, changes are applied to every thread
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scheduling {
+    pub nice: Option<i32>,
+    // CPUs process is allowed to run on, i.e. '0-3,6'
+    pub cpu_affinity: Option<String>,
+}
+
+impl Scheduling {
+    pub fn read(pid: u32) -> Self {
+        Self {
+            nice: get_nice(pid),
+            cpu_affinity: get_affinity(pid).map(|cpus| format_cpus(&cpus)),
+        }
+    }
+}
+
+/// Which scheduling property of process is changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulingKind {
+    Nice,
+    Affinity,
+}
+
+/// Scheduling change applied to process, process is not signaled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchedulingChange {
+    Nice(i32),
+    // sorted list of CPUs process is allowed to run on
+    Affinity(Vec<usize>),
+}
+
+impl SchedulingKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            SchedulingKind::Nice => "Nice",
+            SchedulingKind::Affinity => "CPUs",
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            SchedulingKind::Nice => "-20 (highest priority) to 19 (lowest priority)",
+            SchedulingKind::Affinity => "list of CPUs, i.e. '0-3,6'",
+        }
+    }
+
+    pub fn accepts(&self, c: char) -> bool {
+        match self {
+            SchedulingKind::Nice => c.is_ascii_digit() || c == '-',
+            SchedulingKind::Affinity => c.is_ascii_digit() || c == '-' || c == ',',
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<SchedulingChange, String> {
+        match self {
+            SchedulingKind::Nice => parse_nice(input),
+            SchedulingKind::Affinity => parse_affinity(input),
+        }
+    }
+}
+
+impl Display for SchedulingChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchedulingChange::Nice(nice) => write!(f, "nice {nice}"),
+            SchedulingChange::Affinity(cpus) => write!(f, "CPUs {}", format_cpus(cpus)),
+        }
+    }
+}
+
+fn parse_nice(input: &str) -> Result<SchedulingChange, String> {
+    match input.trim().parse::<i32>() {
+        Ok(nice) if (MIN_NICE..=MAX_NICE).contains(&nice) => Ok(SchedulingChange::Nice(nice)),
+        _ => Err(format!(
+            "Nice has to be between {MIN_NICE} and {MAX_NICE}, got '{input}'"
+        )),
+    }
+}
+
+fn parse_affinity(input: &str) -> Result<SchedulingChange, String> {
+    let invalid = || format!("Invalid CPU list '{input}', expected i.e. '0-3,6'");
+    let mut cpus = vec![];
+    for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (first, last),
+            None => (part, part),
+        };
+        let first = first.parse::<usize>().map_err(|_| invalid())?;
+        let last = last.parse::<usize>().map_err(|_| invalid())?;
+        if first > last || last >= MAX_CPUS {
+            return Err(invalid());
+        }
+        cpus.extend(first..=last);
+    }
+    if cpus.is_empty() {
+        return Err(invalid());
+    }
+    Ok(SchedulingChange::Affinity(
+        cpus.into_iter().sorted().dedup().collect(),
+    ))
+}
+
+/// Formats CPUs as ranges like taskset does, i.e. '0-3,6'
+pub fn format_cpus(cpus: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for &cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == cpu => *last = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(first, last)| match first == last {
+            true => first.to_string(),
+            false => format!("{first}-{last}"),
+        })
+        .join(",")
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn get_nice(pid: u32) -> Option<i32> {
+    //NOTE: pid 0 means calling process for getpriority
+    if pid == 0 {
+        return None;
+    }
+    //NOTE: -1 is valid nice, errno has to be checked to tell it from error
+    clear_errno();
+    // SAFETY: getpriority has no memory safety requirements, it only reads priority of given pid
+    let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS as _, pid as libc::id_t) };
+    let failed = nice == -1 && std::io::Error::last_os_error().raw_os_error() != Some(0);
+    (!failed).then_some(nice)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn get_nice(_pid: u32) -> Option<i32> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn clear_errno() {
+    // SAFETY: __errno_location returns valid pointer to errno of current thread
+    unsafe { *libc::__errno_location() = 0 };
+}
+
+#[cfg(target_os = "macos")]
+fn clear_errno() {
+    // SAFETY: __error returns valid pointer to errno of current thread
+    unsafe { *libc::__error() = 0 };
+}
+
+#[cfg(target_os = "linux")]
+fn get_affinity(pid: u32) -> Option<Vec<usize>> {
+    if pid == 0 {
+        return None;
+    }
+    // SAFETY: cpu_set_t is plain bit mask, all zeroes is valid empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    // SAFETY: set is valid for writes of size passed along with it
+    let result = unsafe {
+        libc::sched_getaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &mut set,
+        )
+    };
+    if result != 0 {
+        return None;
+    }
+    Some(
+        (0..MAX_CPUS)
+            // SAFETY: MAX_CPUS is CPU_SETSIZE, so every cpu is within set
+            .filter(|cpu| unsafe { libc::CPU_ISSET(*cpu, &set) })
+            .collect(),
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_affinity(_pid: u32) -> Option<Vec<usize>> {
+    None
+}
+
+/// Applies change to every thread of process as on Linux nice and affinity are kept per thread.
+/// Error tells how many threads were left unchanged when only some of them failed
+pub(super) fn apply(pid: u32, change: &SchedulingChange) -> Result<()> {
+    let threads = process_threads(pid);
+    let mut changed = 0;
+    let mut failed = vec![];
+    for tid in threads {
+        match apply_to_thread(tid, change) {
+            Ok(()) => changed += 1,
+            //NOTE: threads may exit meanwhile, only missing main thread means process is gone
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound && tid != pid => {}
+            Err(err) => failed.push((tid, err)),
+        }
+    }
+    match (failed.first(), changed) {
+        (None, _) => Ok(()),
+        (Some((_, err)), 0) => Err(describe_error(err, change)),
+        (Some((tid, err)), changed) => Err(anyhow::anyhow!(
+            "changed only {changed} of {} threads, thread {tid}: {}",
+            changed + failed.len(),
+            describe_error(err, change)
+        )),
+    }
+}
+
+fn apply_to_thread(tid: u32, change: &SchedulingChange) -> std::io::Result<()> {
+    match change {
+        SchedulingChange::Nice(nice) => set_nice(tid, *nice),
+        SchedulingChange::Affinity(cpus) => set_affinity(tid, cpus),
+    }
+}
+
+fn describe_error(err: &std::io::Error, change: &SchedulingChange) -> anyhow::Error {
+    match err.kind() {
+        std::io::ErrorKind::PermissionDenied => anyhow::anyhow!(
+            "permission denied, only root can raise priority or change other user's process"
+        ),
+        std::io::ErrorKind::NotFound => anyhow::anyhow!("no such process, it has already exited"),
+        std::io::ErrorKind::InvalidInput => {
+            anyhow::anyhow!("{change} is not valid for this system")
+        }
+        std::io::ErrorKind::Unsupported => {
+            anyhow::anyhow!("changing {change} is not supported on this system")
+        }
+        _ => anyhow::anyhow!("{err}"),
+    }
+}
+
+// Thread IDs of process, main thread has the same ID as process
+#[cfg(target_os = "linux")]
+fn process_threads(pid: u32) -> Vec<u32> {
+    let threads = std::fs::read_dir(format!("/proc/{pid}/task"))
+        .map(|tasks| {
+            tasks
+                .filter_map(|task| task.ok()?.file_name().to_str()?.parse::<u32>().ok())
+                .sorted()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    match threads.is_empty() {
+        true => vec![pid],
+        false => threads,
+    }
+}
+
+//NOTE: other systems apply nice to whole process
+#[cfg(not(target_os = "linux"))]
+fn process_threads(pid: u32) -> Vec<u32> {
+    vec![pid]
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn set_nice(pid: u32, nice: i32) -> std::io::Result<()> {
+    // SAFETY: setpriority has no memory safety requirements, it only changes priority of given pid
+    match unsafe { libc::setpriority(libc::PRIO_PROCESS as _, pid as libc::id_t, nice) } {
+        0 => Ok(()),
+        _ => Err(os_error()),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn set_nice(_pid: u32, _nice: i32) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(target_os = "linux")]
+fn set_affinity(pid: u32, cpus: &[usize]) -> std::io::Result<()> {
+    // SAFETY: cpu_set_t is plain bit mask, all zeroes is valid empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for cpu in cpus {
+        // SAFETY: parsed cpus are below MAX_CPUS which is CPU_SETSIZE, so every cpu is within set
+        unsafe { libc::CPU_SET(*cpu, &mut set) };
+    }
+    // SAFETY: set is valid for reads of size passed along with it
+    let result = unsafe {
+        libc::sched_setaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &set,
+        )
+    };
+    match result {
+        0 => Ok(()),
+        _ => Err(os_error()),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_affinity(_pid: u32, _cpus: &[usize]) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+//NOTE: ESRCH is not mapped to NotFound by std
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn os_error() -> std::io::Error {
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::ESRCH) => std::io::ErrorKind::NotFound.into(),
+        _ => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_nice_in_range() {
+        assert_eq!(
+            SchedulingKind::Nice.parse(" -5 "),
+            Ok(SchedulingChange::Nice(-5))
+        );
+        assert!(SchedulingKind::Nice.parse("20").is_err());
+        assert!(SchedulingKind::Nice.parse("-21").is_err());
+        assert!(SchedulingKind::Nice.parse("").is_err());
+    }
+
+    #[test]
+    fn should_parse_cpu_list() {
+        assert_eq!(
+            SchedulingKind::Affinity.parse("6,0-3,2"),
+            Ok(SchedulingChange::Affinity(vec![0, 1, 2, 3, 6]))
+        );
+        assert!(SchedulingKind::Affinity.parse("3-1").is_err());
+        assert!(SchedulingKind::Affinity.parse("0-").is_err());
+        assert!(SchedulingKind::Affinity.parse(",").is_err());
+        assert!(SchedulingKind::Affinity.parse("1024").is_err());
+    }
+
+    #[test]
+    fn should_format_cpus_as_ranges() {
+        assert_eq!(format_cpus(&[0, 1, 2, 3, 6, 8, 9]), "0-3,6,8-9");
+        assert_eq!(format_cpus(&[5]), "5");
+        assert_eq!(format_cpus(&[]), "");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn should_read_scheduling_of_current_process() {
+        let pid = std::process::id();
+
+        let nice = get_nice(pid).unwrap();
+        let cpus = get_affinity(pid).unwrap();
+
+        assert!(!cpus.is_empty());
+        //NOTE: current values are applied back so test process is not affected
+        apply(pid, &SchedulingChange::Nice(nice)).unwrap();
+        apply(pid, &SchedulingChange::Affinity(cpus)).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn should_change_every_thread_of_process() {
+        // given
+        let mut threaded = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "processes::scheduling::tests::threaded_process",
+                "--ignored",
+                "--nocapture",
+            ])
+            .env(THREADED_PROCESS, "1")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let pid = threaded.id();
+        let mut stdout = std::io::BufReader::new(threaded.stdout.take().unwrap());
+        let mut line = String::new();
+        while !line.contains("threads started") {
+            line.clear();
+            assert_ne!(
+                std::io::BufRead::read_line(&mut stdout, &mut line).unwrap(),
+                0
+            );
+        }
+        let cpu = get_affinity(pid).unwrap()[0];
+        let nice = (get_nice(pid).unwrap() + 1).min(MAX_NICE);
+
+        // when
+        let nice_change = apply(pid, &SchedulingChange::Nice(nice));
+        let affinity_change = apply(pid, &SchedulingChange::Affinity(vec![cpu]));
+        let threads = process_threads(pid);
+        let scheduling = threads
+            .iter()
+            .map(|tid| (get_nice(*tid), get_affinity(*tid)))
+            .collect::<Vec<_>>();
+        threaded.kill().unwrap();
+        threaded.wait().unwrap();
+
+        // then
+        assert!(nice_change.is_ok());
+        assert!(affinity_change.is_ok());
+        //NOTE: test harness thread and main thread are there too
+        assert!(threads.len() > 3);
+        assert!(
+            scheduling
+                .iter()
+                .all(|s| *s == (Some(nice), Some(vec![cpu])))
+        );
+    }
+
+    const THREADED_PROCESS: &str = "PIK_TEST_THREADED_PROCESS";
+
+    /// Process with several threads, it is run by test above as separate process
+    #[test]
+    #[ignore]
+    fn threaded_process() {
+        if std::env::var(THREADED_PROCESS).is_err() {
+            return;
+        }
+        let threads = (0..3)
+            .map(|_| std::thread::spawn(|| std::thread::sleep(std::time::Duration::from_secs(30))))
+            .collect::<Vec<_>>();
+        println!("threads started");
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().unwrap());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn should_fail_for_missing_process() {
+        let err = apply(u32::MAX / 2, &SchedulingChange::Nice(5)).unwrap_err();
+
+        assert_eq!(err.to_string(), "no such process, it has already exited");
+    }
+}
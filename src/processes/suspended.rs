@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Process stopped with SIGSTOP by pik, it stays here until it is resumed or exits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuspendedProcess {
+    pub pid: u32,
+    pub name: String,
+    #[serde(default)]
+    pub args: String,
+    // used to tell if pid was reused by another process after pik restart
+    pub start_time: Option<u64>,
+}
+
+/// Processes suspended by pik, kept for session and optionally persisted to state file
+#[derive(Debug, Default)]
+pub struct SuspendedProcesses {
+    entries: Vec<SuspendedProcess>,
+    state_file: Option<PathBuf>,
+}
+
+impl SuspendedProcesses {
+    //NOTE: broken or missing state file is not an error, list just starts empty
+    pub fn load(state_file: Option<PathBuf>) -> Self {
+        let entries = state_file
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self {
+            entries,
+            state_file,
+        }
+    }
+
+    pub fn entries(&self) -> &[SuspendedProcess] {
+        &self.entries
+    }
+
+    pub fn contains(&self, pid: u32) -> bool {
+        self.entries.iter().any(|entry| entry.pid == pid)
+    }
+
+    pub fn add(&mut self, process: SuspendedProcess) -> Result<()> {
+        self.entries.retain(|entry| entry.pid != process.pid);
+        self.entries.push(process);
+        self.persist()
+    }
+
+    pub fn remove(&mut self, pid: u32) -> Result<()> {
+        if !self.contains(pid) {
+            return Ok(());
+        }
+        self.entries.retain(|entry| entry.pid != pid);
+        self.persist()
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(&SuspendedProcess) -> bool) -> Result<()> {
+        let len = self.entries.len();
+        self.entries.retain(f);
+        match self.entries.len() == len {
+            true => Ok(()),
+            false => self.persist(),
+        }
+    }
+
+    fn persist(&self) -> Result<()> {
+        let Some(path) = &self.state_file else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create state directory: {dir:?}"))?;
+        }
+        let json = serde_json::to_string(&self.entries)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to save suspended processes to: {path:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_one_entry_per_pid() {
+        let mut suspended = SuspendedProcesses::default();
+
+        suspended.add(some_suspended(42, "node")).unwrap();
+        suspended.add(some_suspended(42, "deno")).unwrap();
+        suspended.add(some_suspended(7, "java")).unwrap();
+
+        assert_eq!(
+            suspended.entries(),
+            &[some_suspended(42, "deno"), some_suspended(7, "java")]
+        );
+        assert!(suspended.contains(7));
+    }
+
+    #[test]
+    fn should_persist_and_load_entries() {
+        let state_file = std::env::temp_dir()
+            .join(format!("pik-test-{}", std::process::id()))
+            .join("suspended.json");
+        let mut suspended = SuspendedProcesses::load(Some(state_file.clone()));
+        suspended.add(some_suspended(42, "node")).unwrap();
+        suspended.add(some_suspended(7, "java")).unwrap();
+        suspended.remove(42).unwrap();
+
+        let loaded = SuspendedProcesses::load(Some(state_file.clone()));
+
+        assert_eq!(loaded.entries(), &[some_suspended(7, "java")]);
+        std::fs::remove_dir_all(state_file.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn should_start_empty_when_state_file_is_broken() {
+        let state_file = std::env::temp_dir().join(format!("pik-broken-{}", std::process::id()));
+        std::fs::write(&state_file, "not json").unwrap();
+
+        let loaded = SuspendedProcesses::load(Some(state_file.clone()));
+
+        assert!(loaded.entries().is_empty());
+        std::fs::remove_file(state_file).unwrap();
+    }
+
+    fn some_suspended(pid: u32, name: &str) -> SuspendedProcess {
+        SuspendedProcess {
+            pid,
+            name: name.to_string(),
+            args: String::new(),
+            start_time: Some(1),
+        }
+    }
+}
@@ -3,8 +3,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use anyhow::{Context, Result, anyhow};
 use chrono::{DateTime, Local};
 use itertools::Itertools;
-use listeners::{Listener, Process, Protocol};
-use sysinfo::{System, Uid};
+use sysinfo::{Signal, System, Uid};
 
 use super::ProcessInfo;
 
@@ -23,9 +22,46 @@ pub(super) fn get_process_args(prc: &impl ProcessInfo) -> Option<String> {
     }
 }
 
-pub(super) fn process_run_time(run_duration_since_epoch: u64, now: SystemTime) -> String {
+/// Sends signal directly so OS error (i.e. EPERM) is not lost like in sysinfo
+#[cfg(unix)]
+pub(super) fn send_signal(pid: u32, signal: Signal) -> std::io::Result<()> {
+    let signal = match signal {
+        Signal::Term => libc::SIGTERM,
+        Signal::Kill => libc::SIGKILL,
+        Signal::Interrupt => libc::SIGINT,
+        Signal::Hangup => libc::SIGHUP,
+        Signal::Stop => libc::SIGSTOP,
+        Signal::Continue => libc::SIGCONT,
+        _ => return Err(std::io::ErrorKind::Unsupported.into()),
+    };
+    let pid = libc::pid_t::try_from(pid).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+    // SAFETY: kill has no memory safety requirements, it only sends signal to given pid
+    match unsafe { libc::kill(pid, signal) } {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error()),
+    }
+}
+
+// sysinfo is the only way to signal process here, so processes it knows are needed
+#[cfg(not(unix))]
+pub(super) fn send_signal(sys: &System, pid: u32, signal: Signal) -> std::io::Result<()> {
+    match sys
+        .process(sysinfo::Pid::from_u32(pid))
+        .map(|prc| prc.kill_with(signal))
+    {
+        Some(Some(true)) => Ok(()),
+        Some(Some(false)) => Err(std::io::Error::other("signal was not delivered")),
+        Some(None) => Err(std::io::ErrorKind::Unsupported.into()),
+        None => Err(std::io::ErrorKind::NotFound.into()),
+    }
+}
+
+pub(super) fn process_run_time(run_duration_since_epoch: u64, now: SystemTime) -> u64 {
     let now_since_epoch = now.duration_since(UNIX_EPOCH).unwrap().as_secs();
-    let seconds_diff = now_since_epoch.saturating_sub(run_duration_since_epoch);
+    now_since_epoch.saturating_sub(run_duration_since_epoch)
+}
+
+pub(super) fn format_run_time(seconds_diff: u64) -> String {
     let seconds = seconds_diff % 60;
     let hours = seconds_diff / 3600;
     let minutes = (seconds_diff % 3600) / 60;
@@ -51,19 +87,6 @@ pub(super) fn find_current_process_user(sys: &System) -> Result<Uid> {
         .context("Current process not found!")
 }
 
-pub(super) fn create_listener(pid: u32, socket_addr: &str, protocol: Protocol) -> Listener {
-    Listener {
-        process: Process {
-            pid,
-            name: String::new(),
-            path: String::new(),
-        },
-        socket: socket_addr.parse().unwrap(),
-        protocol,
-        state: listeners::SocketState::Listen,
-    }
-}
-
 #[cfg(test)]
 pub mod tests {
 
@@ -91,6 +114,9 @@ pub mod tests {
         pub memory: u64,
         pub start_time: u64,
         pub run_time: u64,
+        pub cpu_usage: f32,
+        pub status: String,
+        pub cwd: Option<String>,
     }
 
     impl ProcessInfo for MockProcessInfo {
@@ -133,6 +159,18 @@ pub mod tests {
         fn args(&self) -> Vec<&str> {
             self.args.iter().map(|a| a.as_str()).collect()
         }
+
+        fn cpu_usage(&self) -> f32 {
+            self.cpu_usage
+        }
+
+        fn status(&self) -> String {
+            self.status.clone()
+        }
+
+        fn cwd(&self) -> Option<&str> {
+            self.cwd.as_deref()
+        }
     }
 
     impl Default for MockProcessInfo {
@@ -148,6 +186,9 @@ pub mod tests {
                 memory: 0,
                 start_time: 0,
                 run_time: 0,
+                cpu_usage: 0.0,
+                status: "Runnable".to_string(),
+                cwd: None,
             }
         }
     }
@@ -186,7 +227,10 @@ pub mod tests {
     fn test_process_run_time() {
         let run_time = |hours: u64, minutes: u64, seconds: u64| {
             let duration = as_duration(hours, minutes, seconds);
-            process_run_time(duration.as_secs(), UNIX_EPOCH + duration.mul(2))
+            format_run_time(process_run_time(
+                duration.as_secs(),
+                UNIX_EPOCH + duration.mul(2),
+            ))
         };
         assert_eq!(run_time(0, 0, 0), "0s");
         assert_eq!(run_time(0, 0, 5), "5s");
@@ -216,4 +260,12 @@ pub mod tests {
     fn as_duration(hours: u64, minutes: u64, seconds: u64) -> Duration {
         Duration::from_secs(hours * 3600 + minutes * 60 + seconds)
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_reject_unsupported_signal() {
+        let result = send_signal(std::process::id(), Signal::Poll);
+
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::Unsupported);
+    }
 }
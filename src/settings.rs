@@ -1,42 +1,123 @@
 use ratatui::Viewport;
 
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use anyhow::{Context, Result};
+
 use crate::{
     args::{CliArgs, ScreenSizeOptions},
-    config::{AppConfig, ScreenSize, keymappings::KeyMappings, ui::UIConfig},
-    processes::IgnoreOptions,
+    config::{
+        AppConfig, KillConfig, ScreenSize, SearchConfig, keymappings::KeyMappings, state_dir,
+        ui::UIConfig,
+    },
+    processes::{
+        IgnoreOptions, KillGuard, KillHistory, KillOptions, NamedCopyTemplate, SavedSearch,
+    },
 };
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct AppSettings {
     pub query: String,
+    // name of saved search query comes from, set with --search
+    pub saved_search: Option<String>,
+    pub port: Option<u16>,
     pub viewport: Viewport,
     pub filter_opions: IgnoreOptions,
+    pub kill_options: KillOptions,
+    pub kill_guard: KillGuard,
+    pub privilege_helper: Vec<String>,
+    // None when suspended processes are kept only for session
+    pub suspended_state_file: Option<PathBuf>,
+    // None when there is no state dir, history is kept only for session then
+    pub search_history_file: Option<PathBuf>,
+    pub saved_searches: Vec<SavedSearch>,
+    pub copy_templates: Vec<NamedCopyTemplate>,
     pub ui_config: UIConfig,
     pub key_mappings: KeyMappings,
 }
 
 impl AppSettings {
-    pub fn from(config: AppConfig, cli_args: CliArgs) -> Self {
-        Self {
-            query: cli_args.query,
+    pub fn from(config: AppConfig, cli_args: CliArgs) -> Result<Self> {
+        let query = match &cli_args.search {
+            Some(name) => config
+                .searches
+                .get(name)
+                .map(|search| search.query().to_string())
+                .with_context(|| {
+                    format!("Search '{name}' is not defined in [searches] of config")
+                })?,
+            None => cli_args.query,
+        };
+        let filter_opions = IgnoreOptions {
+            ignore_threads: prefer_override(
+                config.ignore.threads,
+                cli_args.ignore.ignore_thread_processes,
+            ),
+            ignore_other_users: prefer_override(
+                config.ignore.other_users,
+                cli_args.ignore.ignore_other_users_processes,
+            ),
+            paths: prefer_override(config.ignore.paths, cli_args.ignore.paths),
+        };
+        Ok(Self {
+            query,
+            saved_search: cli_args.search,
+            port: cli_args.port,
             viewport: prefer_override(config.screen_size, cli_args.screen_size),
-            filter_opions: IgnoreOptions {
-                ignore_threads: prefer_override(
-                    config.ignore.threads,
-                    cli_args.ignore.ignore_thread_processes,
-                ),
-                ignore_other_users: prefer_override(
-                    config.ignore.other_users,
-                    cli_args.ignore.ignore_other_users_processes,
-                ),
-                paths: prefer_override(config.ignore.paths, cli_args.ignore.paths),
-            },
+            saved_searches: saved_searches(config.searches, &filter_opions),
+            copy_templates: config
+                .copy_templates
+                .into_iter()
+                .map(|(name, template)| NamedCopyTemplate { name, template })
+                .collect(),
+            filter_opions,
+            kill_guard: KillGuard::from(&config.kill),
+            privilege_helper: config.kill.privilege_helper.clone(),
+            suspended_state_file: config
+                .suspend
+                .persist
+                .then(state_dir)
+                .flatten()
+                .map(|dir| dir.join("suspended.json")),
+            search_history_file: config
+                .search
+                .history
+                .then(state_dir)
+                .flatten()
+                .map(|dir| dir.join("search_history")),
+            kill_options: KillOptions::from(config.kill),
             ui_config: config.ui,
             key_mappings: config.key_mappings,
-        }
+        })
     }
 }
 
+//NOTE: ignore options of saved search are resolved up front, unset ones are taken from global options
+fn saved_searches(
+    searches: BTreeMap<String, SearchConfig>,
+    global: &IgnoreOptions,
+) -> Vec<SavedSearch> {
+    searches
+        .into_iter()
+        .map(|(name, search)| match search {
+            SearchConfig::Query(query) => SavedSearch {
+                name,
+                query,
+                ignore: None,
+            },
+            SearchConfig::WithOptions { query, ignore } => SavedSearch {
+                name,
+                query,
+                ignore: Some(IgnoreOptions {
+                    ignore_threads: ignore.threads.unwrap_or(global.ignore_threads),
+                    ignore_other_users: ignore.other_users.unwrap_or(global.ignore_other_users),
+                    paths: global.paths.iter().cloned().chain(ignore.paths).collect(),
+                }),
+            },
+        })
+        .collect()
+}
+
 fn prefer_override<V, C, A>(config_value: C, override_opt: Option<A>) -> V
 where
     C: Into<V>,
@@ -48,6 +129,34 @@ where
     }
 }
 
+impl From<KillConfig> for KillOptions {
+    fn from(kill: KillConfig) -> Self {
+        Self {
+            grace_period: Duration::from_millis(kill.grace_period_ms),
+            history: KillHistory::new(
+                kill.history
+                    .then(state_dir)
+                    .flatten()
+                    .map(|dir| dir.join("history.log")),
+            ),
+        }
+    }
+}
+
+impl From<&KillConfig> for KillGuard {
+    fn from(kill: &KillConfig) -> Self {
+        Self {
+            confirm_all: kill.confirm,
+            protected_action: kill.protected_action,
+            patterns: kill.protected.patterns.clone(),
+            root: kill.protected.root,
+            init: kill.protected.init,
+            pik: kill.protected.pik,
+            parent_shell: kill.protected.parent_shell,
+        }
+    }
+}
+
 impl From<ScreenSize> for Viewport {
     fn from(ss: ScreenSize) -> Self {
         match ss {
@@ -73,7 +182,8 @@ mod tests {
 
     use crate::{
         args::{self},
-        config::{IgnoreConfig, default_config},
+        config::{IgnoreConfig, SearchOptionsConfig, default_config, parse_config},
+        processes::CopyTemplate,
     };
 
     use super::*;
@@ -107,25 +217,53 @@ mod tests {
         let config = AppConfig::default();
         let cli_args = CliArgs {
             query: "".to_string(),
+            search: None,
             screen_size: None,
             ignore: args::IgnoreOptions {
                 ignore_thread_processes: Some(false),
                 ignore_other_users_processes: Some(false),
                 paths: None,
             },
+            port: None,
+            kill_all: false,
+            dry_run: false,
+            yes: false,
             print_config: false,
+            history: false,
         };
-        let settings = AppSettings::from(config, cli_args);
+        let settings = AppSettings::from(config, cli_args).unwrap();
         assert_eq!(
             settings,
             AppSettings {
                 query: "".into(),
+                saved_search: None,
+                port: None,
                 viewport: Viewport::Inline(25),
                 filter_opions: IgnoreOptions {
                     ignore_threads: false,
                     ignore_other_users: false,
                     paths: vec![]
                 },
+                kill_options: KillOptions {
+                    grace_period: Duration::from_secs(3),
+                    history: KillHistory::new(state_dir().map(|dir| dir.join("history.log")))
+                },
+                kill_guard: KillGuard::default(),
+                privilege_helper: vec!["sudo".to_string()],
+                suspended_state_file: None,
+                search_history_file: state_dir().map(|dir| dir.join("search_history")),
+                saved_searches: vec![],
+                copy_templates: [
+                    ("command", "{cmd_path} {args}"),
+                    ("kill", "kill -9 {pid}"),
+                    ("ports", "{ports}"),
+                ]
+                .into_iter()
+                .map(|(name, template)| NamedCopyTemplate {
+                    name: name.to_string(),
+                    template: CopyTemplate::parse(template).unwrap(),
+                })
+                .collect(),
                 ui_config: default_config().unwrap().ui,
                 key_mappings: default_config().unwrap().key_mappings,
             }
@@ -145,10 +283,22 @@ mod tests {
             }),
             ..some_cli_args()
         };
-        let settings = AppSettings::from(config, cli_args);
+        let settings = AppSettings::from(config, cli_args).unwrap();
         assert_eq!(settings.viewport, Viewport::Fullscreen);
     }
 
+    #[test]
+    fn should_keep_search_history_only_for_session_when_disabled() {
+        let config = AppConfig {
+            search: SearchOptionsConfig { history: false },
+            ..Default::default()
+        };
+
+        let settings = AppSettings::from(config, some_cli_args()).unwrap();
+
+        assert_eq!(settings.search_history_file, None);
+    }
+
     #[test]
     fn should_prefer_config_ignore_threads_when_cli_args_is_none() {
         let config = AppConfig {
@@ -165,7 +315,7 @@ mod tests {
             },
             ..some_cli_args()
         };
-        let settings = AppSettings::from(config, cli_args);
+        let settings = AppSettings::from(config, cli_args).unwrap();
         assert!(settings.filter_opions.ignore_threads);
     }
 
@@ -185,7 +335,7 @@ mod tests {
             },
             ..some_cli_args()
         };
-        let settings = AppSettings::from(config, cli_args);
+        let settings = AppSettings::from(config, cli_args).unwrap();
         assert!(settings.filter_opions.ignore_threads);
     }
 
@@ -205,7 +355,7 @@ mod tests {
             },
             ..some_cli_args()
         };
-        let settings = AppSettings::from(config, cli_args);
+        let settings = AppSettings::from(config, cli_args).unwrap();
         assert!(settings.filter_opions.ignore_other_users);
     }
 
@@ -225,7 +375,7 @@ mod tests {
             },
             ..some_cli_args()
         };
-        let settings = AppSettings::from(config, cli_args);
+        let settings = AppSettings::from(config, cli_args).unwrap();
         assert!(settings.filter_opions.ignore_other_users);
     }
 
@@ -245,7 +395,7 @@ mod tests {
             },
             ..some_cli_args()
         };
-        let settings = AppSettings::from(config, cli_args);
+        let settings = AppSettings::from(config, cli_args).unwrap();
         assert_eq!(settings.filter_opions.paths.len(), 1);
         assert_eq!(settings.filter_opions.paths[0].as_str(), "/user/*");
     }
@@ -266,17 +416,98 @@ mod tests {
             },
             ..some_cli_args()
         };
-        let settings = AppSettings::from(config, cli_args);
+        let settings = AppSettings::from(config, cli_args).unwrap();
         assert_eq!(settings.filter_opions.paths.len(), 1);
         assert_eq!(settings.filter_opions.paths[0].as_str(), "/*");
     }
 
+    #[test]
+    fn should_pass_port_to_check_from_cli_args() {
+        let cli_args = CliArgs {
+            port: Some(8080),
+            ..some_cli_args()
+        };
+        let settings = AppSettings::from(AppConfig::default(), cli_args).unwrap();
+        assert_eq!(settings.port, Some(8080));
+    }
+
+    #[test]
+    fn should_search_for_any_word_given_as_query() {
+        use clap::Parser;
+
+        for word in ["history", "help"] {
+            let cli_args = CliArgs::try_parse_from(["pik", word]).unwrap();
+            assert_eq!(cli_args.query, word);
+            assert!(!cli_args.history);
+        }
+        assert!(
+            CliArgs::try_parse_from(["pik", "--history"])
+                .unwrap()
+                .history
+        );
+    }
+
+    #[test]
+    fn should_start_with_saved_search() {
+        let config = parse_config(
+            r#"
+            [ignore]
+            paths = ["/usr/.*"]
+
+            [searches]
+            dev-servers = ":3000|:8080"
+            all-java = { query = "java", ignore = { other_users = false, paths = ["/opt/.*"] } }
+            "#,
+        )
+        .unwrap();
+        let cli_args = CliArgs {
+            search: Some("dev-servers".to_string()),
+            ..some_cli_args()
+        };
+
+        let settings = AppSettings::from(config, cli_args).unwrap();
+
+        assert_eq!(settings.query, ":3000|:8080");
+        assert_eq!(settings.saved_search.as_deref(), Some("dev-servers"));
+        let java = &settings.saved_searches[0];
+        assert_eq!(java.name, "all-java");
+        let ignore = java.ignore.as_ref().unwrap();
+        assert!(!ignore.ignore_other_users);
+        assert!(ignore.ignore_threads);
+        assert_eq!(
+            ignore.paths.iter().map(|r| r.as_str()).collect::<Vec<_>>(),
+            vec!["/usr/.*", "/opt/.*"]
+        );
+        assert_eq!(settings.saved_searches[1].ignore, None);
+    }
+
+    #[test]
+    fn should_fail_for_unknown_saved_search() {
+        let cli_args = CliArgs {
+            search: Some("missing".to_string()),
+            ..some_cli_args()
+        };
+
+        let err = AppSettings::from(AppConfig::default(), cli_args).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Search 'missing' is not defined in [searches] of config"
+        );
+    }
+
     fn some_cli_args() -> CliArgs {
         CliArgs {
             query: "".to_string(),
+            search: None,
             screen_size: None,
             ignore: Default::default(),
+            port: None,
+            kill_all: false,
+            dry_run: false,
+            yes: false,
             print_config: false,
+            history: false,
         }
     }
 }
@@ -6,27 +6,59 @@ use std::{
 
 use anyhow::{Context, Result, anyhow};
 use components::{
-    Component, ComponentEvent, KeyAction, debug::DebugComponent,
-    general_input_handler::GeneralInputHandlerComponent, help_footer::HelpFooterComponent,
-    help_popup::HelpPopupComponent, notifications::NotificationsComponent,
-    processes_view::ProcessesViewComponent,
+    Component, ComponentEvent, KeyAction, Notification, batch_kill_popup::BatchKillPopupComponent,
+    command_palette_popup::CommandPalettePopupComponent, copy_field_popup::CopyFieldPopupComponent,
+    debug::DebugComponent, general_input_handler::GeneralInputHandlerComponent,
+    help_footer::HelpFooterComponent, help_popup::HelpPopupComponent,
+    history_popup::HistoryPopupComponent, kill_confirm_popup::KillConfirmPopupComponent,
+    notifications::NotificationsComponent, port_check_popup::PortCheckPopupComponent,
+    privileged_kill_popup::PrivilegedKillPopupComponent, processes_view::ProcessesViewComponent,
+    saved_searches_popup::SavedSearchesPopupComponent, scheduling_popup::SchedulingPopupComponent,
+    search_history_popup::SearchHistoryPopupComponent, suspended_popup::SuspendedPopupComponent,
 };
 use ratatui::crossterm::{
-    event::{self, Event, KeyEventKind},
+    event::{
+        self, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers, MouseEvent,
+    },
     execute,
-    terminal::EnterAlternateScreen,
+    terminal::{EnterAlternateScreen, enable_raw_mode},
 };
 use ratatui::{TerminalOptions, prelude::*};
 
+mod clipboard;
 pub mod components;
 mod highlight;
+mod panes;
+mod search_history;
 
-use crate::{config::keymappings::KeyMappings, settings::AppSettings};
+pub use panes::{LayoutChange, PanesLayout};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::LayoutKind,
+    },
+    processes::{KillHistory, KilledProcess, PrivilegedKill},
+    settings::AppSettings,
+};
 
 struct App {
     components: Vec<Box<dyn Component>>,
     component_events: VecDeque<ComponentEvent>,
     key_mappings: KeyMappings,
+    fullscreen: bool,
+    privilege_helper: Vec<String>,
+    // kill waiting to be run with privilege helper, terminal has to be released for it
+    privileged_kill: Option<PrivilegedKill>,
+    history: KillHistory,
+    mouse: bool,
+    panes: PanesLayout,
+    // layout of last render, mouse events are matched against it
+    layout: LayoutRects,
+    // terminal was resized, whole screen is redrawn then
+    resized: bool,
 }
 
 // NOTE: Simple FPS limiter based on input read delay (it's not stable, typing really fast will increase FPS for short time)
@@ -36,14 +68,23 @@ const KEY_READ_DELAY: u64 = 1000 / FPS;
 
 impl App {
     fn new(app_settings: AppSettings) -> Result<App> {
-        let component_events = VecDeque::new();
+        let mut component_events = VecDeque::new();
+        if let Some(port) = app_settings.port {
+            component_events.push_back(ComponentEvent::OpenPortCheck(Some(port)));
+        }
 
+        let history = app_settings.kill_options.history.clone();
+        let mouse = app_settings.ui_config.mouse;
         Ok(App {
             //Order matters!
             //Input handling is done in this order
             //Rendering is done in reverse
             //It allows for popups to be rendered on top but they handle input first
             components: vec![
+                Box::new(CommandPalettePopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                )),
                 Box::new(HelpPopupComponent::new(
                     &app_settings.ui_config,
                     &app_settings.key_mappings,
@@ -51,17 +92,75 @@ impl App {
                 Box::new(NotificationsComponent::new(
                     &app_settings.ui_config.notifications,
                 )),
+                Box::new(KillConfirmPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                )),
+                Box::new(PortCheckPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                )),
+                Box::new(PrivilegedKillPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                    app_settings.privilege_helper.clone(),
+                )),
+                Box::new(BatchKillPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                )),
+                Box::new(SchedulingPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                )),
+                Box::new(SuspendedPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                )),
+                Box::new(HistoryPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                    app_settings.kill_options.history.clone(),
+                )),
+                Box::new(SearchHistoryPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                )),
+                Box::new(SavedSearchesPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                    &app_settings.saved_searches,
+                )),
+                Box::new(CopyFieldPopupComponent::new(
+                    &app_settings.ui_config,
+                    &app_settings.key_mappings,
+                    &app_settings.copy_templates,
+                )),
                 Box::new(GeneralInputHandlerComponent),
                 Box::new(HelpFooterComponent::new(&app_settings.key_mappings)),
                 Box::new(DebugComponent::new()),
                 Box::new(ProcessesViewComponent::new(
                     &app_settings.ui_config,
                     app_settings.filter_opions,
+                    app_settings.kill_options,
+                    app_settings.kill_guard,
+                    app_settings.suspended_state_file,
+                    app_settings.search_history_file,
+                    app_settings.saved_searches,
                     app_settings.query,
+                    app_settings.saved_search,
                 )?),
             ],
             component_events,
             key_mappings: app_settings.key_mappings,
+            fullscreen: matches!(app_settings.viewport, ratatui::Viewport::Fullscreen),
+            privilege_helper: app_settings.privilege_helper,
+            privileged_kill: None,
+            history,
+            mouse,
+            panes: PanesLayout::new(&app_settings.ui_config),
+            layout: LayoutRects::default(),
+            resized: false,
         })
     }
 
@@ -72,34 +171,80 @@ impl App {
             if self.handle_events()? {
                 return Ok(());
             }
+            if let Some(kill) = self.privileged_kill.take() {
+                self.run_privileged_kill(terminal, kill)?;
+            }
+            if self.resized {
+                self.resized = false;
+                terminal
+                    .autoresize()
+                    .and_then(|_| terminal.clear())
+                    .map_err(|e| anyhow!("Failed to resize terminal {e}"))?;
+            }
 
             self.render(terminal)?;
         }
     }
 
     fn handle_input(&mut self) -> Result<()> {
-        if event::poll(Duration::from_millis(KEY_READ_DELAY))?
-            && let Event::Key(key) = event::read()?
-            && key.kind == KeyEventKind::Press
-        {
-            let action = self.key_mappings.resolve(key);
-            for component in self.components.iter_mut() {
-                let action = component.handle_input(key, action);
-                match action {
-                    KeyAction::Unhandled => continue,
-                    KeyAction::Consumed => {
-                        break;
-                    }
-                    KeyAction::Event(act) => {
-                        self.component_events.push_back(act);
-                        break;
-                    }
-                }
+        if !event::poll(Duration::from_millis(KEY_READ_DELAY))? {
+            return Ok(());
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let action = self.key_mappings.resolve(key);
+                self.handle_key(key, action);
             }
-        };
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
+            Event::Key(_) => (),
+            event => self.handle_terminal_event(event),
+        }
         Ok(())
     }
 
+    fn handle_key(&mut self, key: KeyEvent, action: AppAction) {
+        for component in self.components.iter_mut() {
+            let action = component.handle_input(key, action);
+            if dispatch(&mut self.component_events, action) {
+                break;
+            }
+        }
+    }
+
+    //NOTE: action from command palette gets its first key, some components look at key code,
+    // actions without key get null key
+    fn run_action(&mut self, action: AppAction) {
+        let key = self
+            .key_mappings
+            .bindings
+            .get(&action)
+            .and_then(|bindings| bindings.first())
+            .map(|binding| KeyEvent::new(binding.key, binding.modifier))
+            .unwrap_or_else(|| KeyEvent::new(KeyCode::Null, KeyModifiers::NONE));
+        self.handle_key(key, action);
+    }
+
+    //NOTE: paste goes to one component like key does, resize and focus are for every component
+    fn handle_terminal_event(&mut self, event: Event) {
+        let broadcast = !matches!(event, Event::Paste(_));
+        self.resized |= matches!(event, Event::Resize(..));
+        for component in self.components.iter_mut() {
+            let action = component.handle_terminal_event(&event);
+            if dispatch(&mut self.component_events, action) && !broadcast {
+                break;
+            }
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        for component in self.components.iter_mut() {
+            let action = component.handle_mouse(mouse, &self.layout);
+            if dispatch(&mut self.component_events, action) {
+                break;
+            }
+        }
+    }
+
     fn update_state(&mut self) {
         for component in self.components.iter_mut() {
             if let Some(event) = component.update_state() {
@@ -110,8 +255,19 @@ impl App {
 
     fn handle_events(&mut self) -> Result<bool> {
         while let Some(event) = self.component_events.pop_front() {
-            if let ComponentEvent::QuitRequested = event {
-                return Ok(true);
+            match event {
+                ComponentEvent::QuitRequested => return Ok(true),
+                ComponentEvent::RunPrivilegedKill(kill) => {
+                    self.privileged_kill = Some(kill);
+                    continue;
+                }
+                ComponentEvent::RunAction(action) => {
+                    self.run_action(action);
+                    continue;
+                }
+                //NOTE: components still get the change, i.e. details scroll is reset
+                ComponentEvent::ChangeLayout(change) => self.panes.change(change),
+                _ => (),
             }
             for component in self.components.iter_mut() {
                 let new_event = component.handle_event(&event);
@@ -123,19 +279,91 @@ impl App {
         Ok(false)
     }
 
+    // NOTE: helper like sudo may ask for password, so terminal is restored while it runs
+    fn run_privileged_kill<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        kill: PrivilegedKill,
+    ) -> Result<()> {
+        let Some(mut command) = kill.command(&self.privilege_helper) else {
+            return Ok(());
+        };
+        if !kill.is_same_process() {
+            let error = "process has exited or its PID was reused, it was not signaled".to_string();
+            self.record_privileged_kill(&kill, Some(&error));
+            self.component_events
+                .push_back(ComponentEvent::PrivilegedKillFinished {
+                    kill,
+                    error: Some(error),
+                });
+            return Ok(());
+        }
+        disable_terminal_events(self.mouse)?;
+        ratatui::restore();
+        let error = match command.status() {
+            Ok(status) if status.success() => None,
+            Ok(status) => Some(format!("helper exited with {status}")),
+            Err(err) => Some(format!("failed to run helper: {err}")),
+        };
+        self.record_privileged_kill(&kill, error.as_deref());
+        enable_raw_mode()?;
+        if self.fullscreen {
+            execute!(stdout(), EnterAlternateScreen)?;
+        }
+        enable_terminal_events(self.mouse)?;
+        terminal
+            .clear()
+            .map_err(|e| anyhow!("Failed to clear terminal {e}"))?;
+        self.component_events
+            .push_back(ComponentEvent::PrivilegedKillFinished { kill, error });
+        Ok(())
+    }
+
+    //NOTE: daemon is not involved in privileged kill, so it is recorded here
+    fn record_privileged_kill(&mut self, kill: &PrivilegedKill, error: Option<&str>) {
+        let process = KilledProcess {
+            pid: kill.pid,
+            name: kill.name.clone(),
+            args: kill.args.clone(),
+        };
+        let result = match error {
+            Some(err) => format!("failed: {err}"),
+            None => format!("delivered with {}", self.privilege_helper.join(" ")),
+        };
+        if let Err(err) = self.history.record(&process, kill.signal, &result) {
+            self.component_events
+                .push_back(ComponentEvent::ShowNotification(Notification::error(
+                    format!("{err:#}"),
+                )));
+        }
+    }
+
     fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         terminal
             .draw(|frame| {
-                let layout = LayoutRects::new(frame);
+                let layout = LayoutRects::new(frame.area(), &self.panes);
                 for component in self.components.iter_mut().rev() {
                     component.render(frame, &layout);
                 }
+                self.layout = layout;
             })
             .map_err(|e| anyhow!("Failed to render on terminal {e}"))?;
         Ok(())
     }
 }
 
+// returns true when action was handled and no other component should get it
+fn dispatch(component_events: &mut VecDeque<ComponentEvent>, action: KeyAction) -> bool {
+    match action {
+        KeyAction::Unhandled => false,
+        KeyAction::Consumed => true,
+        KeyAction::Event(event) => {
+            component_events.push_back(event);
+            true
+        }
+    }
+}
+
 pub enum ProcessRelatedSearch {
     Family,   // process + process children
     Siblings, // process parent + all his children
@@ -152,6 +380,8 @@ pub fn start_app(app_settings: AppSettings) -> Result<()> {
     set_cursor_color(app_settings.ui_config.search_bar.cursor_style.bg)?;
 
     let mut terminal = ratatui::init_with_options(TerminalOptions { viewport });
+    let mouse = app_settings.ui_config.mouse;
+    enable_terminal_events(mouse)?;
 
     // create app and run it
     let app = App::new(app_settings)?;
@@ -159,6 +389,7 @@ pub fn start_app(app_settings: AppSettings) -> Result<()> {
 
     // restore terminal
     terminal.clear()?;
+    disable_terminal_events(mouse)?;
     ratatui::restore();
     reset_cursor_color()?;
 
@@ -172,6 +403,23 @@ pub fn start_app(app_settings: AppSettings) -> Result<()> {
 
 use std::io::Write;
 
+//NOTE: terminal sends paste and focus events only when asked for them
+fn enable_terminal_events(mouse: bool) -> Result<()> {
+    execute!(stdout(), EnableBracketedPaste, EnableFocusChange)?;
+    if mouse {
+        execute!(stdout(), EnableMouseCapture)?;
+    }
+    Ok(())
+}
+
+fn disable_terminal_events(mouse: bool) -> Result<()> {
+    execute!(stdout(), DisableBracketedPaste, DisableFocusChange)?;
+    if mouse {
+        execute!(stdout(), DisableMouseCapture)?;
+    }
+    Ok(())
+}
+
 fn set_cursor_color(color: Option<Color>) -> Result<()> {
     if let Some(color) = color {
         // OSC 12 ; color BEL
@@ -187,6 +435,7 @@ fn reset_cursor_color() -> Result<()> {
     io::stdout().flush().context("failed to reset cursor color")
 }
 
+#[derive(Debug, Default, Clone, Copy)]
 pub struct LayoutRects {
     pub top_bar: Rect,
     pub process_table: Rect,
@@ -196,22 +445,40 @@ pub struct LayoutRects {
 }
 
 impl LayoutRects {
-    pub fn new(frame: &Frame) -> Self {
-        let rects = Layout::vertical([
+    pub fn new(area: Rect, panes: &PanesLayout) -> Self {
+        let [top_bar, main, footer] = Layout::vertical([
             Constraint::Length(1),
             Constraint::Min(10),
-            Constraint::Max(7),
             Constraint::Length(1),
         ])
-        .split(frame.area());
-        let footer = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(rects[3]);
+        .areas(area);
+        let [debug, help_text] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(footer);
+        //NOTE: hidden details get empty area, mouse never hits it
+        let (process_table, process_details) = match panes.kind {
+            LayoutKind::Vertical => {
+                let [table, details] =
+                    Layout::vertical([Constraint::Min(10), Constraint::Max(panes.details_height)])
+                        .areas(main);
+                (table, details)
+            }
+            LayoutKind::Horizontal => {
+                let [table, details] = Layout::horizontal([
+                    Constraint::Percentage(100 - panes.details_width),
+                    Constraint::Percentage(panes.details_width),
+                ])
+                .areas(main);
+                (table, details)
+            }
+            LayoutKind::DetailsHidden => (main, Rect::default()),
+        };
         Self {
-            top_bar: rects[0],
-            process_table: rects[1],
-            process_details: rects[2],
-            debug: footer[0],
-            help_text: footer[1],
+            top_bar,
+            process_table,
+            process_details,
+            debug,
+            help_text,
         }
     }
 }
@@ -0,0 +1,81 @@
+use std::{
+    io::Write,
+    sync::{LazyLock, Mutex},
+};
+
+use arboard::Clipboard;
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+// NOTE: clipboard access is initialized lazily because some systems do not provide a clipboard
+// backend at all. Surface those failures to the user instead of crashing the TUI.
+static CLIPBOARD: LazyLock<Result<Mutex<Clipboard>, String>> = LazyLock::new(|| {
+    Clipboard::new()
+        .map(Mutex::new)
+        .map_err(|err| format!("Clipboard is unavailable: {err}"))
+});
+
+/// How text was copied, terminal does not confirm whether it supports OSC 52
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Copied {
+    Clipboard,
+    Terminal,
+}
+
+/// Copies text to system clipboard, error is ready to be shown to user
+//NOTE: without clipboard backend (i.e. over SSH) terminal is asked to copy text with OSC 52
+pub fn copy_text(text: &str) -> Result<Copied, String> {
+    let clipboard = match CLIPBOARD.as_ref() {
+        Ok(clipboard) => clipboard,
+        Err(err) => {
+            return copy_with_terminal(text)
+                .map(|_| Copied::Terminal)
+                .map_err(|osc_err| format!("{err}, {osc_err}"));
+        }
+    };
+    let mut clipboard = clipboard
+        .lock()
+        .map_err(|_| "Clipboard is currently unavailable".to_string())?;
+    clipboard
+        .set_text(text)
+        .map(|_| Copied::Clipboard)
+        .map_err(|err| format!("Failed to copy to clipboard: {err}"))
+}
+
+fn copy_with_terminal(text: &str) -> Result<(), String> {
+    let sequence = osc52_sequence(text, std::env::var_os("TMUX").is_some());
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(sequence.as_bytes())
+        .and_then(|_| stdout.flush())
+        .map_err(|err| format!("failed to copy with terminal: {err}"))
+}
+
+// tmux passes escape sequence to outer terminal only when it is wrapped in DCS
+fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let osc52 = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    match tmux {
+        true => format!("\x1bPtmux;\x1b{osc52}\x1b\\"),
+        false => osc52,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_encode_text_in_osc52_sequence() {
+        assert_eq!(
+            osc52_sequence("kill -9 42", false),
+            "\x1b]52;c;a2lsbCAtOSA0Mg==\x07"
+        );
+    }
+
+    #[test]
+    fn should_wrap_osc52_sequence_for_tmux() {
+        assert_eq!(
+            osc52_sequence("42", true),
+            "\x1bPtmux;\x1b\x1b]52;c;NDI=\x07\x1b\\"
+        );
+    }
+}
@@ -1,19 +1,40 @@
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 use ratatui::Frame;
-use ratatui::crossterm::event::KeyEvent;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, HighlightSpacing, List, ListItem, ListState, Padding};
+
+use crate::config::ui::PopupsTheme;
 
 use crate::config::keymappings::AppAction;
+use crate::processes::{
+    BatchKill, MatchType, NamedCopyTemplate, PortCheck, PrivilegedKill, Process, Scheduling,
+    SchedulingChange, SchedulingKind, SuspendedProcess,
+};
 
-use super::LayoutRects;
+use super::{LayoutChange, LayoutRects};
 
+pub mod batch_kill_popup;
+pub mod command_palette_popup;
+pub mod copy_field_popup;
 pub mod debug;
 pub mod general_input_handler;
 pub mod help_footer;
 pub mod help_popup;
+pub mod history_popup;
+pub mod kill_confirm_popup;
 pub mod notifications;
+pub mod port_check_popup;
+pub mod privileged_kill_popup;
 pub mod process_details;
 pub mod process_table;
 pub mod processes_view;
+pub mod saved_searches_popup;
+pub mod scheduling_popup;
 pub mod search_bar;
+pub mod search_history_popup;
+pub mod suspended_popup;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NotificationSeverity {
@@ -70,6 +91,16 @@ pub trait Component {
         None
     }
 
+    // layout is the one used for last render, so mouse position can be matched with areas
+    fn handle_mouse(&mut self, _mouse: MouseEvent, _layout: &LayoutRects) -> KeyAction {
+        KeyAction::Unhandled
+    }
+
+    // terminal events other than keys and mouse: resize, focus and paste
+    fn handle_terminal_event(&mut self, _event: &Event) -> KeyAction {
+        KeyAction::Unhandled
+    }
+
     fn update_state(&mut self) -> Option<ComponentEvent> {
         None
     }
@@ -89,4 +120,222 @@ pub enum KeyAction {
 pub enum ComponentEvent {
     QuitRequested,
     ShowNotification(Notification),
+    // opens port check popup, port is checked right away when known
+    OpenPortCheck(Option<u16>),
+    CheckPort(u16),
+    PortChecked(PortCheck),
+    // kill requested by user, it may still need confirmation
+    KillProcess(Box<KillRequest>),
+    ConfirmKill {
+        request: Box<KillRequest>,
+        // reason why process is protected, user has to type process name then
+        protection: Option<String>,
+    },
+    KillConfirmed(Box<KillRequest>),
+    // every search result is killed, preview is shown first
+    ConfirmBatchKill(Box<BatchKill>),
+    BatchKillConfirmed(Box<BatchKill>),
+    // kill failed with EPERM, user may retry it with privilege helper
+    KillPermissionDenied(PrivilegedKill),
+    RunPrivilegedKill(PrivilegedKill),
+    PrivilegedKillFinished {
+        kill: PrivilegedKill,
+        error: Option<String>,
+    },
+    OpenSuspended(Vec<SuspendedProcess>),
+    ResumeProcess(SuspendedProcess),
+    // opens input for nice or CPU affinity of marked processes or selected one,
+    // input starts with scheduling of the first of them
+    OpenScheduling {
+        processes: Vec<Process>,
+        scheduling: Scheduling,
+        kind: SchedulingKind,
+    },
+    ChangeScheduling {
+        processes: Vec<Process>,
+        change: SchedulingChange,
+    },
+    // newest query first
+    OpenSearchHistory(Vec<String>),
+    // query picked from search history replaces current one
+    ApplySearch {
+        query: String,
+        // name of saved search, its ignore options are used then
+        saved_search: Option<String>,
+    },
+    ChangeLayout(LayoutChange),
+    // opens popup with details of process to pick one to copy
+    OpenCopyField {
+        process: Box<Process>,
+        scheduling: Scheduling,
+    },
+    // template bound to key is copied with values of selected process
+    CopyTemplate(Box<NamedCopyTemplate>),
+    // action picked from command palette, it is handled like its key was pressed
+    RunAction(AppAction),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KillRequest {
+    pub process: Process,
+    pub graceful: bool,
+    // port checked again after kill, i.e. when owner was killed from port check
+    pub recheck_port: Option<u16>,
+    pub kind: KillKind,
+}
+
+/// What is done with process once protection allows it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillKind {
+    Kill,
+    // process is started again with the same command line after it exits
+    Restart,
+    // process is stopped with SIGSTOP, it freezes pik when it is pik or its shell
+    Suspend,
+}
+
+impl KillKind {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            KillKind::Kill => "kill",
+            KillKind::Restart => "restart",
+            KillKind::Suspend => "suspend",
+        }
+    }
+}
+
+pub(super) fn popup_area(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
+    let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
+    let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
+
+// bordered list with selectable rows, shared by popups listing keybindings or queries
+pub(super) fn popup_list<'a>(
+    items: impl IntoIterator<Item = impl Into<ListItem<'a>>>,
+    title: String,
+    footer: String,
+    theme: &PopupsTheme,
+) -> List<'a> {
+    List::new(items)
+        .block(
+            Block::bordered()
+                .title_top(Line::from(title).centered())
+                .title_bottom(Line::from(footer).centered())
+                .padding(Padding::horizontal(1))
+                .border_style(theme.border.style)
+                .border_type(theme.border._type),
+        )
+        .highlight_style(theme.selected_row)
+        .highlight_spacing(HighlightSpacing::Always)
+}
+
+/// Popup list filtered by fuzzy matching as user types, best match first.
+/// Entries are kept by popup, list refers to them by index
+pub(super) struct FuzzyList {
+    filter: String,
+    // index of entry with its match, best match first
+    matches: Vec<(usize, MatchType)>,
+    matcher: SkimMatcherV2,
+    list_state: ListState,
+}
+
+impl FuzzyList {
+    pub fn new() -> Self {
+        Self {
+            filter: String::new(),
+            matches: vec![],
+            matcher: SkimMatcherV2::default(),
+            list_state: ListState::default(),
+        }
+    }
+
+    pub fn matches(&self) -> &[(usize, MatchType)] {
+        &self.matches
+    }
+
+    /// Index of selected entry
+    pub fn selected(&self) -> Option<usize> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.matches.get(index))
+            .map(|(entry, _)| *entry)
+    }
+
+    /// Title with filter typed so far
+    pub fn title(&self, name: &str) -> String {
+        match self.filter.is_empty() {
+            true => format!(" {name} "),
+            false => format!(" {name}: {} ", self.filter),
+        }
+    }
+
+    pub fn reset<'a>(&mut self, entries: impl IntoIterator<Item = &'a str>) {
+        self.filter.clear();
+        self.filter_entries(entries);
+    }
+
+    /// Moves selection and edits filter, keys list does not use are left to popup
+    pub fn handle_input<'a>(
+        &mut self,
+        key: KeyEvent,
+        action: AppAction,
+        entries: impl IntoIterator<Item = &'a str>,
+    ) {
+        match action {
+            AppAction::NextItem => self.list_state.select_next(),
+            AppAction::PreviousItem => self.list_state.select_previous(),
+            AppAction::GoToFirstItem => self.list_state.select_first(),
+            AppAction::GoToLastItem => self.list_state.select_last(),
+            AppAction::DeleteChar => {
+                self.filter.pop();
+                self.filter_entries(entries);
+            }
+            AppAction::Unmapped => {
+                if let KeyCode::Char(c) = key.code {
+                    self.filter.push(c);
+                    self.filter_entries(entries);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn paste<'a>(&mut self, text: &str, entries: impl IntoIterator<Item = &'a str>) {
+        self.filter.extend(text.chars().filter(|c| !c.is_control()));
+        self.filter_entries(entries);
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, list: List) {
+        frame.render_widget(Clear, area); //this clears out the background
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    //NOTE: sort is stable, so equally good matches keep order of entries
+    fn filter_entries<'a>(&mut self, entries: impl IntoIterator<Item = &'a str>) {
+        self.matches = entries
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, entry)| match self.filter.is_empty() {
+                true => Some((index, MatchType::Exists)),
+                false => self
+                    .matcher
+                    .fuzzy_indices(entry, &self.filter)
+                    .map(|(score, positions)| (index, MatchType::Fuzzy { score, positions })),
+            })
+            .collect();
+        self.matches.sort_by(|(_, a), (_, b)| a.cmp(b));
+        self.list_state
+            .select((!self.matches.is_empty()).then_some(0));
+    }
+}
+
+//NOTE: open popup swallows mouse and paste events, so view under it does not change
+pub(super) fn popup_action(is_open: bool) -> KeyAction {
+    match is_open {
+        true => KeyAction::Consumed,
+        false => KeyAction::Unhandled,
+    }
 }
@@ -0,0 +1,212 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem, ListState, Padding},
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::BatchKill,
+    tui::LayoutRects,
+};
+
+use super::{Component, ComponentEvent, KeyAction, Notification, popup_action, popup_area};
+
+pub struct BatchKillPopupComponent {
+    plan: Option<BatchKill>,
+    list_state: ListState,
+    theme: PopupsTheme,
+    close_bindings: String,
+}
+
+impl BatchKillPopupComponent {
+    pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings) -> Self {
+        Self {
+            plan: None,
+            list_state: ListState::default(),
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    fn confirm(&mut self) -> KeyAction {
+        match self.plan.take() {
+            Some(plan) if plan.targets.is_empty() => {
+                KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                    "No process to kill, all matching processes are protected",
+                )))
+            }
+            Some(plan) => KeyAction::Event(ComponentEvent::BatchKillConfirmed(Box::new(plan))),
+            None => KeyAction::Consumed,
+        }
+    }
+
+    fn items(&self, plan: &BatchKill) -> Vec<ListItem<'static>> {
+        let targets = plan.targets.iter().map(|prc| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:>7} ", prc.pid), self.theme.primary),
+                Span::styled(
+                    format!("{} ({})", prc.cmd, prc.user_name),
+                    self.theme.secondary,
+                ),
+            ]))
+        });
+        let skipped = plan.skipped.iter().map(|(prc, reason)| {
+            ListItem::new(Line::styled(
+                format!("{:>7} {} skipped, {reason}", prc.pid, prc.cmd),
+                self.theme.secondary,
+            ))
+        });
+        targets.chain(skipped).collect()
+    }
+}
+
+impl Component for BatchKillPopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        if self.plan.is_none() {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close => {
+                self.plan = None;
+            }
+            AppAction::NextItem => {
+                self.list_state.scroll_down_by(1);
+            }
+            AppAction::PreviousItem => {
+                self.list_state.scroll_up_by(1);
+            }
+            AppAction::Unmapped if matches!(key.code, KeyCode::Enter | KeyCode::Char('y')) => {
+                return self.confirm();
+            }
+            _ => (),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_event(&mut self, event: &ComponentEvent) -> Option<ComponentEvent> {
+        if let ComponentEvent::ConfirmBatchKill(plan) = event {
+            self.plan = Some(*plan.clone());
+            self.list_state = ListState::default();
+        }
+        None
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.plan.is_some())
+    }
+
+    fn handle_terminal_event(&mut self, _: &Event) -> KeyAction {
+        popup_action(self.plan.is_some())
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        let Some(plan) = &self.plan else {
+            return;
+        };
+        let area = popup_area(frame.area(), 60, 60);
+        let block = Block::bordered()
+            .title_top(
+                Line::from(format!(
+                    " Kill {} processes matching '{}'? ",
+                    plan.targets.len(),
+                    plan.query
+                ))
+                .centered(),
+            )
+            .title_bottom(
+                Line::from(format!(
+                    " y/enter confirm | {} cancel ",
+                    self.close_bindings
+                ))
+                .centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .border_style(self.theme.border.style)
+            .border_type(self.theme.border._type);
+
+        frame.render_widget(Clear, area); //this clears out the background
+        let list = List::new(self.items(plan)).block(block);
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::config::default_config;
+    use crate::processes::Process;
+
+    use super::*;
+
+    #[test]
+    fn should_confirm_batch_kill() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::ConfirmBatchKill(Box::new(some_plan(
+            vec![Process::test(42), Process::test(43)],
+        ))));
+
+        let action = popup.handle_input(key(KeyCode::Char('y')), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::BatchKillConfirmed(plan)) if plan.targets.len() == 2
+        ));
+        assert!(popup.plan.is_none());
+    }
+
+    #[test]
+    fn should_not_confirm_when_every_process_is_protected() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::ConfirmBatchKill(Box::new(some_plan(
+            vec![],
+        ))));
+
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ShowNotification(_))
+        ));
+    }
+
+    #[test]
+    fn should_cancel_batch_kill() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::ConfirmBatchKill(Box::new(some_plan(
+            vec![Process::test(42)],
+        ))));
+
+        popup.handle_input(key(KeyCode::Esc), AppAction::Close);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    fn create_popup() -> BatchKillPopupComponent {
+        let config = default_config().unwrap();
+        BatchKillPopupComponent::new(&config.ui, &config.key_mappings)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn some_plan(targets: Vec<Process>) -> BatchKill {
+        BatchKill {
+            query: "node".to_string(),
+            targets,
+            skipped: vec![(
+                Process::test(1),
+                "PID 1 keeps the whole system running".into(),
+            )],
+        }
+    }
+}
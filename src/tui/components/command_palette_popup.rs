@@ -0,0 +1,260 @@
+use itertools::Itertools;
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    text::{Line, Span},
+    widgets::ListItem,
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::MatchType,
+    tui::{LayoutRects, highlight::highlight_text},
+};
+
+use super::{
+    Component, ComponentEvent, FuzzyList, KeyAction, popup_action, popup_area, popup_list,
+};
+
+struct PaletteEntry {
+    action: AppAction,
+    // action name with spaces, i.e. 'select process siblings'
+    name: String,
+    bindings: String,
+}
+
+pub struct CommandPalettePopupComponent {
+    is_open: bool,
+    // in order of AppAction
+    entries: Vec<PaletteEntry>,
+    list: FuzzyList,
+    theme: PopupsTheme,
+    close_bindings: String,
+}
+
+impl CommandPalettePopupComponent {
+    //NOTE: actions without key are listed too, palette is the only way to run them
+    pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings) -> Self {
+        let entries = AppAction::ALL
+            .iter()
+            .copied()
+            .filter(|action| !action.is_special() && *action != AppAction::ShowCommandPalette)
+            .map(|action| PaletteEntry {
+                action,
+                name: action.to_string().replace('_', " "),
+                bindings: key_mappings
+                    .bindings
+                    .get(&action)
+                    .map(|bindings| bindings.iter().join(", "))
+                    .unwrap_or_default(),
+            })
+            .collect();
+        Self {
+            is_open: false,
+            entries,
+            list: FuzzyList::new(),
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    fn open(&mut self) {
+        self.is_open = true;
+        self.list.reset(names(&self.entries));
+    }
+
+    fn run_selected(&mut self) -> KeyAction {
+        match self.list.selected() {
+            Some(entry) => {
+                self.is_open = false;
+                KeyAction::Event(ComponentEvent::RunAction(self.entries[entry].action))
+            }
+            None => KeyAction::Consumed,
+        }
+    }
+}
+
+impl Component for CommandPalettePopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        if matches!(action, AppAction::ShowCommandPalette) {
+            if self.is_open {
+                self.is_open = false;
+            } else {
+                self.open();
+            }
+            return KeyAction::Consumed;
+        }
+        if !self.is_open {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close => {
+                self.is_open = false;
+            }
+            AppAction::Unmapped if key.code == KeyCode::Enter => return self.run_selected(),
+            _ => self.list.handle_input(key, action, names(&self.entries)),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn handle_terminal_event(&mut self, event: &Event) -> KeyAction {
+        if let (Event::Paste(text), true) = (event, self.is_open) {
+            self.list.paste(text, names(&self.entries));
+        }
+        popup_action(self.is_open)
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        if !self.is_open {
+            return;
+        }
+        let area = popup_area(frame.area(), 50, 60);
+        let footer = format!(
+            " type to filter | enter run | {} close ",
+            self.close_bindings
+        );
+        let list = popup_list(
+            items(&self.entries, self.list.matches(), &self.theme),
+            self.list.title("Command palette"),
+            footer,
+            &self.theme,
+        );
+        self.list.render(frame, area, list);
+    }
+}
+
+fn names(entries: &[PaletteEntry]) -> impl Iterator<Item = &str> {
+    entries.iter().map(|entry| entry.name.as_str())
+}
+
+//NOTE: items borrow only entries, list state is borrowed mutably while rendering
+fn items<'a>(
+    entries: &'a [PaletteEntry],
+    matches: &[(usize, MatchType)],
+    theme: &PopupsTheme,
+) -> Vec<ListItem<'a>> {
+    if matches.is_empty() {
+        return vec![ListItem::new(Line::styled(
+            "No action matches filter",
+            theme.secondary,
+        ))];
+    }
+    let name_width = entries
+        .iter()
+        .map(|entry| entry.name.len())
+        .max()
+        .unwrap_or_default();
+    matches
+        .iter()
+        .map(|(entry, match_type)| {
+            let entry = &entries[*entry];
+            let mut line = highlight_text(
+                &entry.name,
+                match_type,
+                theme.primary,
+                theme.secondary,
+                name_width,
+            );
+            line.spans.push(Span::raw(
+                " ".repeat(name_width.saturating_sub(entry.name.len()) + 1),
+            ));
+            line.spans
+                .push(Span::styled(entry.bindings.clone(), theme.secondary));
+            ListItem::new(line)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::config::default_config;
+
+    use super::*;
+
+    #[test]
+    fn should_run_best_matching_action() {
+        let mut popup = create_popup();
+        popup.handle_input(
+            KeyEvent::new(KeyCode::Char('e'), KeyModifiers::ALT),
+            AppAction::ShowCommandPalette,
+        );
+
+        for c in "sibl".chars() {
+            popup.handle_input(key(KeyCode::Char(c)), AppAction::Unmapped);
+        }
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::RunAction(AppAction::SelectProcessSiblings))
+        ));
+        assert!(!popup.is_open);
+    }
+
+    #[test]
+    fn should_list_every_action_except_palette_itself_and_special_ones() {
+        let mut popup = create_popup();
+        popup.open();
+
+        assert_eq!(popup.list.matches().len(), AppAction::ALL.len() - 4);
+        assert!(popup.entries.iter().all(|entry| {
+            entry.action != AppAction::ShowCommandPalette && !entry.action.is_special()
+        }));
+        //NOTE: actions without key can be run only from palette
+        let delete_to_end = popup
+            .entries
+            .iter()
+            .find(|entry| entry.action == AppAction::DeleteToEnd)
+            .unwrap();
+        assert_eq!(delete_to_end.bindings, "");
+        let siblings = popup
+            .entries
+            .iter()
+            .find(|entry| entry.action == AppAction::SelectProcessSiblings)
+            .unwrap();
+        assert_eq!(siblings.name, "select process siblings");
+        assert_eq!(siblings.bindings, "alt+s");
+    }
+
+    #[test]
+    fn should_keep_open_when_nothing_matches() {
+        let mut popup = create_popup();
+        popup.open();
+
+        popup.handle_input(key(KeyCode::Char('#')), AppAction::Unmapped);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(popup.list.matches().is_empty());
+        assert!(matches!(action, KeyAction::Consumed));
+        assert!(popup.is_open);
+    }
+
+    #[test]
+    fn should_not_handle_input_when_closed() {
+        let mut popup = create_popup();
+
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    fn create_popup() -> CommandPalettePopupComponent {
+        let config = default_config().unwrap();
+        CommandPalettePopupComponent::new(&config.ui, &config.key_mappings)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+}
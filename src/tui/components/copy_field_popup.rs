@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    text::{Line, Span},
+    widgets::{Clear, ListItem, ListState},
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyBinding, KeyMappings, resolve_named},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::{NamedCopyTemplate, Process, Scheduling},
+    tui::{
+        LayoutRects,
+        clipboard::{self, Copied},
+    },
+};
+
+use super::{
+    Component, ComponentEvent, KeyAction, Notification, popup_action, popup_area, popup_list,
+    process_details::details_sections,
+};
+
+pub struct CopyFieldPopupComponent {
+    is_open: bool,
+    templates: Vec<NamedCopyTemplate>,
+    template_bindings: BTreeMap<String, Vec<KeyBinding>>,
+    // templates rendered for process followed by its details, in order they are shown in details
+    entries: Vec<CopyEntry>,
+    list_state: ListState,
+    theme: PopupsTheme,
+    close_bindings: String,
+}
+
+struct CopyEntry {
+    label: String,
+    value: String,
+    // section of details or key template is bound to
+    hint: String,
+}
+
+impl CopyFieldPopupComponent {
+    pub fn new(
+        ui_config: &UIConfig,
+        key_mappings: &KeyMappings,
+        templates: &[NamedCopyTemplate],
+    ) -> Self {
+        Self {
+            is_open: false,
+            templates: templates.to_vec(),
+            template_bindings: key_mappings.copy_templates.clone(),
+            entries: vec![],
+            list_state: ListState::default(),
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    fn open(&mut self, process: &Process, scheduling: &Scheduling) {
+        let templates = self.templates.iter().map(|named| CopyEntry {
+            label: named.name.clone(),
+            value: named.template.render(process),
+            hint: self
+                .template_bindings
+                .get(&named.name)
+                .map(|bindings| bindings.iter().join(", "))
+                .unwrap_or_else(|| "template".to_string()),
+        });
+        let fields = details_sections(process, scheduling)
+            .into_iter()
+            .flat_map(|section| {
+                section.fields.into_iter().map(move |field| CopyEntry {
+                    label: field.label.to_string(),
+                    value: field.value,
+                    hint: section.title.to_string(),
+                })
+            });
+        self.entries = templates.chain(fields).collect();
+        self.list_state.select(Some(0));
+        self.is_open = true;
+    }
+
+    fn run_template(&self, key: KeyEvent) -> KeyAction {
+        let template = resolve_named(&self.template_bindings, key)
+            .and_then(|name| self.templates.iter().find(|named| named.name == name));
+        match template {
+            Some(template) => {
+                KeyAction::Event(ComponentEvent::CopyTemplate(Box::new(template.clone())))
+            }
+            None => KeyAction::Unhandled,
+        }
+    }
+
+    fn copy_selected(&mut self) -> KeyAction {
+        let Some(entry) = self
+            .list_state
+            .selected()
+            .and_then(|index| self.entries.get(index))
+        else {
+            return KeyAction::Consumed;
+        };
+        self.is_open = false;
+        KeyAction::Event(ComponentEvent::ShowNotification(copy_notification(
+            &entry.label,
+            &entry.value,
+        )))
+    }
+
+    fn items(&self) -> Vec<ListItem<'static>> {
+        let label_width = self
+            .entries
+            .iter()
+            .map(|entry| entry.label.chars().count())
+            .max()
+            .unwrap_or_default();
+        self.entries
+            .iter()
+            .map(|entry| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<label_width$} ", entry.label),
+                        self.theme.primary,
+                    ),
+                    Span::styled(entry.value.clone(), self.theme.secondary),
+                    Span::styled(format!(" ({})", entry.hint), self.theme.secondary),
+                ]))
+            })
+            .collect()
+    }
+}
+
+/// Copies text and tells user how it went
+pub(super) fn copy_notification(label: &str, text: &str) -> Notification {
+    match clipboard::copy_text(text) {
+        Ok(Copied::Clipboard) => Notification::success(format!("Copied {label} to clipboard")),
+        Ok(Copied::Terminal) => {
+            Notification::success(format!("Asked terminal to copy {label} to clipboard"))
+        }
+        Err(err) => Notification::error(err),
+    }
+}
+
+impl Component for CopyFieldPopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        if matches!(action, AppAction::RunCopyTemplate) {
+            self.is_open = false;
+            return self.run_template(key);
+        }
+        if !self.is_open {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close | AppAction::CopyProcessField => {
+                self.is_open = false;
+            }
+            AppAction::NextItem => {
+                self.list_state.select_next();
+            }
+            AppAction::PreviousItem => {
+                self.list_state.select_previous();
+            }
+            AppAction::GoToFirstItem => {
+                self.list_state.select_first();
+            }
+            AppAction::GoToLastItem => {
+                self.list_state.select_last();
+            }
+            AppAction::Unmapped if key.code == KeyCode::Enter => {
+                return self.copy_selected();
+            }
+            _ => (),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_event(&mut self, event: &ComponentEvent) -> Option<ComponentEvent> {
+        if let ComponentEvent::OpenCopyField {
+            process,
+            scheduling,
+        } = event
+        {
+            self.open(process, scheduling);
+        }
+        None
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn handle_terminal_event(&mut self, _: &Event) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        if !self.is_open {
+            return;
+        }
+        let area = popup_area(frame.area(), 60, 60);
+        let list = popup_list(
+            self.items(),
+            " Copy to clipboard ".to_string(),
+            format!(" enter copy | {} close ", self.close_bindings),
+            &self.theme,
+        );
+
+        frame.render_widget(Clear, area); //this clears out the background
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::{config::parse_config, processes::Process};
+
+    use super::*;
+
+    #[test]
+    fn should_list_rendered_templates_and_fields_of_every_section() {
+        let mut popup = create_popup();
+
+        popup.handle_event(&ComponentEvent::OpenCopyField {
+            process: Box::new(some_process()),
+            scheduling: Scheduling::default(),
+        });
+
+        assert!(popup.is_open);
+        assert_eq!(popup.list_state.selected(), Some(0));
+        assert_eq!(
+            popup
+                .entries
+                .iter()
+                .take(3)
+                .map(|entry| format!("{}={} ({})", entry.label, entry.value, entry.hint))
+                .collect::<Vec<_>>(),
+            vec![
+                "command=node server.js (template)",
+                "kill=kill -9 42 (alt+9)",
+                "ports=8080 (template)",
+            ]
+        );
+        assert_eq!(
+            popup
+                .entries
+                .iter()
+                .skip(3)
+                .map(|entry| format!("{}/{}", entry.hint, entry.label))
+                .collect::<Vec<_>>(),
+            vec![
+                "Identity/USER",
+                "Identity/PID",
+                "Identity/STATE",
+                "Resources/START TIME",
+                "Resources/RUN TIME",
+                "Resources/MEMORY",
+                "Resources/CPU",
+                "Network/PORTS",
+                "Command/CMD",
+                "Command/ARGS",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_close_on_toggle_action() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenCopyField {
+            process: Box::new(some_process()),
+            scheduling: Scheduling::default(),
+        });
+
+        let action = popup.handle_input(
+            KeyEvent::new(KeyCode::Char('y'), KeyModifiers::ALT),
+            AppAction::CopyProcessField,
+        );
+
+        assert!(matches!(action, KeyAction::Consumed));
+        assert!(!popup.is_open);
+    }
+
+    #[test]
+    fn should_copy_template_bound_to_key() {
+        let mut popup = create_popup();
+
+        let action = popup.handle_input(
+            KeyEvent::new(KeyCode::Char('9'), KeyModifiers::ALT),
+            AppAction::RunCopyTemplate,
+        );
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::CopyTemplate(named)) if named.name == "kill"
+        ));
+    }
+
+    #[test]
+    fn should_not_handle_input_when_closed() {
+        let mut popup = create_popup();
+
+        let action = popup.handle_input(
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            AppAction::Unmapped,
+        );
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    fn create_popup() -> CopyFieldPopupComponent {
+        let config = parse_config(
+            r#"
+            [key_mappings.copy_templates]
+            kill = ["alt+9"]
+            "#,
+        )
+        .unwrap();
+        let templates = config
+            .copy_templates
+            .into_iter()
+            .map(|(name, template)| NamedCopyTemplate { name, template })
+            .collect::<Vec<_>>();
+        CopyFieldPopupComponent::new(&config.ui, &config.key_mappings, &templates)
+    }
+
+    fn some_process() -> Process {
+        Process {
+            args: "server.js".to_string(),
+            ports: Some("8080".to_string()),
+            port_bindings: Some("0.0.0.0:8080".to_string()),
+            state: "Run".to_string(),
+            ..Process::test(42)
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use ratatui::crossterm::event::KeyEvent;
 
-use crate::config::keymappings::AppAction;
+use crate::{config::keymappings::AppAction, tui::LayoutChange};
 
 use super::{Component, ComponentEvent, KeyAction};
 
@@ -10,6 +10,15 @@ impl Component for GeneralInputHandlerComponent {
     fn handle_input(&mut self, _: KeyEvent, action: AppAction) -> KeyAction {
         match action {
             AppAction::Close | AppAction::Quit => KeyAction::Event(ComponentEvent::QuitRequested),
+            AppAction::ToggleLayout => {
+                KeyAction::Event(ComponentEvent::ChangeLayout(LayoutChange::Toggle))
+            }
+            AppAction::GrowDetails => {
+                KeyAction::Event(ComponentEvent::ChangeLayout(LayoutChange::GrowDetails))
+            }
+            AppAction::ShrinkDetails => {
+                KeyAction::Event(ComponentEvent::ChangeLayout(LayoutChange::ShrinkDetails))
+            }
             _ => KeyAction::Unhandled,
         }
     }
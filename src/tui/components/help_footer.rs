@@ -17,8 +17,9 @@ impl HelpFooterComponent {
         let close = keymappings.get_joined(AppAction::Close, "/");
         let kill_process = keymappings.get_joined(AppAction::KillProcess, "/");
         let help_toggle = keymappings.get_joined(AppAction::ToggleHelp, "/");
+        let palette = keymappings.get_joined(AppAction::ShowCommandPalette, "/");
         let help_bar = Paragraph::new(Line::from(format!(
-            "{quit}/{close} quit | {kill_process} kill process | {help_toggle} toggle help"
+            "{quit}/{close} quit | {kill_process} kill process | {palette} commands | {help_toggle} toggle help"
         )))
         .centered();
         Self { help_bar }
@@ -1,115 +1,331 @@
 use itertools::Itertools;
 use ratatui::{
-    crossterm::event::KeyEvent,
-    layout::{Constraint, Flex, Layout, Rect},
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    style::Modifier,
     text::{Line, Span},
-    widgets::{Block, Clear, HighlightSpacing, List, ListState, Padding},
+    widgets::ListItem,
 };
 
-use crate::config::{
-    keymappings::{AppAction, KeyMappings},
-    ui::UIConfig,
+use crate::{
+    config::{
+        keymappings::{ActionCategory, AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::MatchType,
+    tui::highlight::highlight_text,
 };
 
-use super::{Component, KeyAction};
+use super::{
+    Component, ComponentEvent, FuzzyList, KeyAction, popup_action, popup_area, popup_list,
+};
 
-//longest key binding
-const KEY_PADDING: usize = 28;
+struct HelpEntry {
+    action: AppAction,
+    category: ActionCategory,
+    // description followed by key bindings, filter is matched against it
+    text: String,
+}
 
-#[derive(Default)]
 pub struct HelpPopupComponent {
     is_open: bool,
-    list_state: ListState,
-    popup_content: List<'static>,
+    // grouped by category, in order of AppAction within category
+    entries: Vec<HelpEntry>,
+    list: FuzzyList,
+    theme: PopupsTheme,
+    close_bindings: String,
 }
 
 impl HelpPopupComponent {
     pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings) -> Self {
-        let theme = &ui_config.popups;
-        let esc_bindings = key_mappings.get_joined(AppAction::Close, "/");
-        let popup_content = key_mappings
+        let description_width = key_mappings
             .sorted()
-            .map(|(key, bindings)| {
-                Line::from(vec![
-                    Span::styled(format!("{key:>KEY_PADDING$}: "), theme.primary),
-                    Span::styled(
-                        bindings.iter().map(|b| b.to_string()).join(", "),
-                        theme.secondary,
-                    ),
-                ])
-                .left_aligned()
+            .map(|(action, _)| action.description().len())
+            .max()
+            .unwrap_or_default();
+        let entries = key_mappings
+            .sorted()
+            .map(|(action, bindings)| HelpEntry {
+                action: *action,
+                category: action.category(),
+                text: format!(
+                    "{:<description_width$} {}",
+                    action.description(),
+                    bindings.iter().join(", ")
+                ),
             })
-            .collect::<List>()
-            .block(
-                Block::bordered()
-                    .title_top(Line::from(" Keybindings ").centered())
-                    .title_bottom(Line::from(format!(" Press {esc_bindings} to close ")).centered())
-                    .padding(Padding {
-                        left: 1,
-                        right: 1,
-                        top: 0,
-                        bottom: 0,
-                    })
-                    .border_style(theme.border.style)
-                    .border_type(theme.border._type),
-            )
-            .highlight_style(theme.selected_row)
-            .highlight_spacing(HighlightSpacing::Always);
-
+            .sorted_by_key(|entry| entry.category)
+            .collect();
         Self {
             is_open: false,
-            list_state: ListState::default().with_selected(Some(0)),
-            popup_content,
+            entries,
+            list: FuzzyList::new(),
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    fn open(&mut self) {
+        self.is_open = true;
+        self.list.reset(texts(&self.entries));
+    }
+
+    //NOTE: help is closed anyway, so toggling it or closing is not run again
+    fn run_selected(&mut self) -> KeyAction {
+        let Some(entry) = self.list.selected() else {
+            return KeyAction::Consumed;
+        };
+        self.is_open = false;
+        match self.entries[entry].action {
+            AppAction::ToggleHelp | AppAction::Close => KeyAction::Consumed,
+            action => KeyAction::Event(ComponentEvent::RunAction(action)),
         }
     }
 }
 
 impl Component for HelpPopupComponent {
-    fn handle_input(&mut self, _: KeyEvent, action: AppAction) -> KeyAction {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
         if matches!(action, AppAction::ToggleHelp) {
-            self.is_open = !self.is_open;
+            if self.is_open {
+                self.is_open = false;
+            } else {
+                self.open();
+            }
             return KeyAction::Consumed;
         }
         if !self.is_open {
             return KeyAction::Unhandled;
         }
         match action {
-            AppAction::GoToFirstItem => {
-                self.list_state.select_first();
-            }
-            AppAction::GoToLastItem => {
-                self.list_state.select_last();
-            }
-            AppAction::NextItem => {
-                self.list_state.select_next();
-            }
-            AppAction::PreviousItem => {
-                self.list_state.select_previous();
-            }
             AppAction::Close => {
                 self.is_open = false;
             }
-            _ => (),
-        };
+            AppAction::Unmapped if key.code == KeyCode::Enter => return self.run_selected(),
+            _ => self.list.handle_input(key, action, texts(&self.entries)),
+        }
 
         //consume all keys if popup is open
         KeyAction::Consumed
     }
 
+    fn handle_mouse(&mut self, _: MouseEvent, _: &crate::tui::LayoutRects) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn handle_terminal_event(&mut self, event: &Event) -> KeyAction {
+        if let (Event::Paste(text), true) = (event, self.is_open) {
+            self.list.paste(text, texts(&self.entries));
+        }
+        popup_action(self.is_open)
+    }
+
     fn render(&mut self, frame: &mut ratatui::Frame, _layout: &crate::tui::LayoutRects) {
         if !self.is_open {
             return;
         }
-        let area = popup_area(frame.area(), 35, 80);
-        frame.render_widget(Clear, area); //this clears out the background
-        frame.render_stateful_widget(&self.popup_content, area, &mut self.list_state);
+        let area = popup_area(frame.area(), 60, 80);
+        let footer = format!(
+            " type to filter | enter run | {} close ",
+            self.close_bindings
+        );
+        let list = popup_list(
+            items(&self.entries, self.list.matches(), &self.theme),
+            self.list.title("Keybindings"),
+            footer,
+            &self.theme,
+        );
+        self.list.render(frame, area, list);
     }
 }
 
-fn popup_area(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
-    let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
-    let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
-    let [area] = vertical.areas(area);
-    let [area] = horizontal.areas(area);
-    area
+fn texts(entries: &[HelpEntry]) -> impl Iterator<Item = &str> {
+    entries.iter().map(|entry| entry.text.as_str())
+}
+
+// One row per match so rows follow selection of list, best match first.
+// Category is shown in first column of the first row of every run of its actions
+fn items<'a>(
+    entries: &'a [HelpEntry],
+    matches: &[(usize, MatchType)],
+    theme: &PopupsTheme,
+) -> Vec<ListItem<'a>> {
+    if matches.is_empty() {
+        return vec![ListItem::new(Line::styled(
+            "No action matches filter",
+            theme.secondary,
+        ))];
+    }
+    let category_width = entries
+        .iter()
+        .map(|entry| entry.category.to_string().len())
+        .max()
+        .unwrap_or_default();
+    let mut previous_category = None;
+    matches
+        .iter()
+        .map(|(entry, match_type)| {
+            let entry = &entries[*entry];
+            let category = match previous_category.replace(entry.category) {
+                Some(previous) if previous == entry.category => String::new(),
+                _ => entry.category.to_string(),
+            };
+            let mut line = highlight_text(
+                &entry.text,
+                match_type,
+                theme.primary,
+                theme.secondary,
+                entry.text.len(),
+            );
+            line.spans.insert(
+                0,
+                Span::styled(
+                    format!("{category:<category_width$}  "),
+                    theme.primary.add_modifier(Modifier::BOLD),
+                ),
+            );
+            ListItem::new(line)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::config::default_config;
+
+    use super::*;
+
+    #[test]
+    fn should_group_actions_by_category() {
+        let popup = create_popup();
+
+        let categories = popup
+            .entries
+            .iter()
+            .map(|entry| entry.category)
+            .dedup()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            categories,
+            vec![
+                ActionCategory::Navigation,
+                ActionCategory::ProcessActions,
+                ActionCategory::SearchEditing,
+                ActionCategory::General,
+            ]
+        );
+        assert!(
+            popup.entries[0]
+                .text
+                .starts_with(AppAction::NextItem.description())
+        );
+    }
+
+    #[test]
+    fn should_fuzzy_filter_by_description_and_binding() {
+        let mut popup = create_popup();
+        popup.handle_input(
+            KeyEvent::new(KeyCode::Char('h'), KeyModifiers::CONTROL),
+            AppAction::ToggleHelp,
+        );
+
+        for c in "SIGSTOP".chars() {
+            popup.handle_input(key(KeyCode::Char(c)), AppAction::Unmapped);
+        }
+        let suspend = matched(&popup);
+        assert_eq!(suspend.len(), 1);
+        assert!(
+            suspend[0]
+                .text
+                .starts_with(AppAction::SuspendProcess.description())
+        );
+
+        popup.open();
+        popup.handle_terminal_event(&Event::Paste("alt+s".to_string()));
+        assert!(
+            matched(&popup)[0]
+                .text
+                .starts_with(AppAction::SelectProcessSiblings.description())
+        );
+    }
+
+    #[test]
+    fn should_list_one_row_per_match_in_match_order() {
+        let mut popup = create_popup();
+        popup.open();
+        popup.handle_terminal_event(&Event::Paste("delete".to_string()));
+
+        let rows = items(&popup.entries, popup.list.matches(), &popup.theme);
+        let scores = popup
+            .list
+            .matches()
+            .iter()
+            .map(|(_, match_type)| match match_type {
+                MatchType::Fuzzy { score, .. } => *score,
+                _ => 0,
+            })
+            .collect::<Vec<_>>();
+
+        assert!(popup.list.matches().len() > 1);
+        assert_eq!(rows.len(), popup.list.matches().len());
+        assert!(scores.is_sorted_by(|a, b| a >= b));
+    }
+
+    #[test]
+    fn should_run_action_of_selected_row() {
+        let mut popup = create_popup();
+        popup.open();
+        popup.handle_terminal_event(&Event::Paste("delete".to_string()));
+        let third_match = matched(&popup)[2].action;
+
+        popup.handle_input(key(KeyCode::Down), AppAction::NextItem);
+        popup.handle_input(key(KeyCode::Down), AppAction::NextItem);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::RunAction(run)) if run == third_match
+        ));
+        assert!(!popup.is_open);
+
+        popup.open();
+        popup.handle_terminal_event(&Event::Paste("SIGSTOP".to_string()));
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::RunAction(AppAction::SuspendProcess))
+        ));
+    }
+
+    #[test]
+    fn should_clear_filter_when_reopened() {
+        let mut popup = create_popup();
+        popup.open();
+        popup.handle_input(key(KeyCode::Char('#')), AppAction::Unmapped);
+        assert!(popup.list.matches().is_empty());
+        popup.handle_input(key(KeyCode::Esc), AppAction::Close);
+
+        popup.open();
+
+        assert_eq!(popup.list.matches().len(), popup.entries.len());
+    }
+
+    fn matched(popup: &HelpPopupComponent) -> Vec<&HelpEntry> {
+        popup
+            .list
+            .matches()
+            .iter()
+            .map(|(entry, _)| &popup.entries[*entry])
+            .collect()
+    }
+
+    fn create_popup() -> HelpPopupComponent {
+        let config = default_config().unwrap();
+        HelpPopupComponent::new(&config.ui, &config.key_mappings)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
 }
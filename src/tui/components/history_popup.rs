@@ -0,0 +1,226 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyEvent, MouseEvent},
+    text::{Line, Span},
+    widgets::{Block, Clear, HighlightSpacing, List, ListItem, ListState, Padding},
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::{HistoryEntry, KillHistory},
+    tui::LayoutRects,
+};
+
+use super::{Component, KeyAction, popup_action, popup_area};
+
+pub struct HistoryPopupComponent {
+    is_open: bool,
+    history: KillHistory,
+    // newest entry first
+    entries: Vec<HistoryEntry>,
+    error: Option<String>,
+    list_state: ListState,
+    theme: PopupsTheme,
+    close_bindings: String,
+}
+
+impl HistoryPopupComponent {
+    pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings, history: KillHistory) -> Self {
+        Self {
+            is_open: false,
+            history,
+            entries: vec![],
+            error: None,
+            list_state: ListState::default(),
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    //NOTE: history is read on every open, it is appended by daemon in background
+    fn open(&mut self) {
+        self.is_open = true;
+        match self.history.entries() {
+            Ok(entries) => {
+                self.entries = entries.into_iter().rev().collect();
+                self.error = None;
+            }
+            Err(err) => {
+                self.entries = vec![];
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+        self.list_state
+            .select((!self.entries.is_empty()).then_some(0));
+    }
+
+    fn items(&self) -> Vec<ListItem<'static>> {
+        if let Some(err) = &self.error {
+            return vec![ListItem::new(Line::styled(
+                err.clone(),
+                self.theme.secondary,
+            ))];
+        }
+        if self.entries.is_empty() {
+            return vec![ListItem::new(Line::styled(
+                "No signals sent by pik yet",
+                self.theme.secondary,
+            ))];
+        }
+        self.entries
+            .iter()
+            .map(|entry| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} {:<8} {:>7} ", entry.timestamp, entry.signal, entry.pid),
+                        self.theme.primary,
+                    ),
+                    Span::styled(
+                        format!(
+                            "{} {} ({}) {}",
+                            entry.cmd,
+                            entry.args,
+                            entry.actor(),
+                            entry.result
+                        ),
+                        self.theme.secondary,
+                    ),
+                ]))
+            })
+            .collect()
+    }
+}
+
+impl Component for HistoryPopupComponent {
+    fn handle_input(&mut self, _: KeyEvent, action: AppAction) -> KeyAction {
+        if matches!(action, AppAction::ShowHistory) {
+            if self.is_open {
+                self.is_open = false;
+            } else {
+                self.open();
+            }
+            return KeyAction::Consumed;
+        }
+        if !self.is_open {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close => {
+                self.is_open = false;
+            }
+            AppAction::NextItem => {
+                self.list_state.select_next();
+            }
+            AppAction::PreviousItem => {
+                self.list_state.select_previous();
+            }
+            AppAction::GoToFirstItem => {
+                self.list_state.select_first();
+            }
+            AppAction::GoToLastItem => {
+                self.list_state.select_last();
+            }
+            _ => (),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn handle_terminal_event(&mut self, _: &Event) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        if !self.is_open {
+            return;
+        }
+        let area = popup_area(frame.area(), 80, 60);
+        let block = Block::bordered()
+            .title_top(Line::from(" Signals sent by pik ").centered())
+            .title_bottom(Line::from(format!(" {} close ", self.close_bindings)).centered())
+            .padding(Padding::horizontal(1))
+            .border_style(self.theme.border.style)
+            .border_type(self.theme.border._type);
+
+        frame.render_widget(Clear, area); //this clears out the background
+        let list = List::new(self.items())
+            .block(block)
+            .highlight_style(self.theme.selected_row)
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+    use sysinfo::Signal;
+
+    use crate::config::default_config;
+    use crate::processes::KilledProcess;
+
+    use super::*;
+
+    #[test]
+    fn should_show_newest_entry_first() {
+        let history_file = std::env::temp_dir()
+            .join(format!("pik-history-popup-{}", std::process::id()))
+            .join("history.log");
+        let history = KillHistory::new(Some(history_file.clone()));
+        history
+            .record(&some_process(42), Signal::Term, "delivered")
+            .unwrap();
+        history
+            .record(&some_process(43), Signal::Kill, "delivered")
+            .unwrap();
+        let mut popup = create_popup(history);
+
+        let action = popup.handle_input(key(KeyCode::Char('h')), AppAction::ShowHistory);
+
+        assert!(matches!(action, KeyAction::Consumed));
+        assert_eq!(
+            popup.entries.iter().map(|e| e.pid).collect::<Vec<_>>(),
+            vec![43, 42]
+        );
+        assert_eq!(popup.list_state.selected(), Some(0));
+        std::fs::remove_dir_all(history_file.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn should_toggle_popup() {
+        let mut popup = create_popup(KillHistory::default());
+
+        popup.handle_input(key(KeyCode::Char('h')), AppAction::ShowHistory);
+        assert!(popup.is_open);
+        popup.handle_input(key(KeyCode::Char('h')), AppAction::ShowHistory);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(!popup.is_open);
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    fn create_popup(history: KillHistory) -> HistoryPopupComponent {
+        let config = default_config().unwrap();
+        HistoryPopupComponent::new(&config.ui, &config.key_mappings, history)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn some_process(pid: u32) -> KilledProcess {
+        KilledProcess {
+            pid,
+            name: "node".to_string(),
+            args: String::new(),
+        }
+    }
+}
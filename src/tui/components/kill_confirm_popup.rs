@@ -0,0 +1,286 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    layout::{Constraint, Layout},
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    tui::LayoutRects,
+};
+
+use super::{
+    Component, ComponentEvent, KeyAction, KillKind, KillRequest, Notification, popup_action,
+    popup_area,
+};
+
+pub struct KillConfirmPopupComponent {
+    request: Option<KillRequest>,
+    protection: Option<String>,
+    typed_name: String,
+    theme: PopupsTheme,
+    close_bindings: String,
+}
+
+impl KillConfirmPopupComponent {
+    pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings) -> Self {
+        Self {
+            request: None,
+            protection: None,
+            typed_name: String::new(),
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    fn confirm(&mut self) -> KeyAction {
+        let Some(request) = self
+            .request
+            .take_if(|request| self.protection.is_none() || self.typed_name == request.process.cmd)
+        else {
+            return KeyAction::Event(ComponentEvent::ShowNotification(Notification::error(
+                "Typed name does not match process name",
+            )));
+        };
+        KeyAction::Event(ComponentEvent::KillConfirmed(Box::new(request)))
+    }
+
+    fn content(&self, request: &KillRequest) -> Vec<Line<'static>> {
+        let prc = &request.process;
+        let field = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{name:>9}: "), self.theme.primary),
+                Span::styled(value, self.theme.secondary),
+            ])
+        };
+        let signal = match (request.kind, request.graceful) {
+            (KillKind::Suspend, _) => "SIGSTOP",
+            (_, true) => "SIGTERM",
+            (_, false) => "SIGKILL",
+        };
+        let mut lines = vec![
+            field("Process", prc.cmd.clone()),
+            field("PID", prc.pid.to_string()),
+            field("User", prc.user_name.clone()),
+            field("Children", prc.children.to_string()),
+            field("Ports", prc.ports.clone().unwrap_or("-".to_string())),
+            field("Signal", signal.to_string()),
+        ];
+        if let Some(protection) = &self.protection {
+            lines.push(Line::default());
+            lines.push(Line::styled(
+                format!("Process is protected, {protection}."),
+                self.theme.primary,
+            ));
+            lines.push(Line::styled(
+                format!("Type '{}' to confirm:", prc.cmd),
+                self.theme.secondary,
+            ));
+        }
+        lines
+    }
+}
+
+impl Component for KillConfirmPopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        if self.request.is_none() {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close => {
+                self.request = None;
+            }
+            AppAction::DeleteChar => {
+                self.typed_name.pop();
+            }
+            AppAction::Unmapped => match key.code {
+                KeyCode::Enter => return self.confirm(),
+                KeyCode::Char(c) if self.protection.is_some() => self.typed_name.push(c),
+                KeyCode::Char('y') => return self.confirm(),
+                _ => (),
+            },
+            _ => (),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_event(&mut self, event: &ComponentEvent) -> Option<ComponentEvent> {
+        if let ComponentEvent::ConfirmKill {
+            request,
+            protection,
+        } = event
+        {
+            self.request = Some(*request.clone());
+            self.protection = protection.clone();
+            self.typed_name.clear();
+        }
+        None
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.request.is_some())
+    }
+
+    //NOTE: name of protected process must be typed, pasting it would defeat the point
+    fn handle_terminal_event(&mut self, _event: &Event) -> KeyAction {
+        popup_action(self.request.is_some())
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        let Some(request) = &self.request else {
+            return;
+        };
+        let confirm_hint = if self.protection.is_some() {
+            "enter"
+        } else {
+            "y/enter"
+        };
+        let area = popup_area(frame.area(), 50, 50);
+        let block = Block::bordered()
+            .title_top(
+                Line::from(match request.kind {
+                    KillKind::Kill => " Kill process? ",
+                    KillKind::Restart => " Restart process? ",
+                    KillKind::Suspend => " Suspend process? ",
+                })
+                .centered(),
+            )
+            .title_bottom(
+                Line::from(format!(
+                    " {confirm_hint} confirm | {} cancel ",
+                    self.close_bindings
+                ))
+                .centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .border_style(self.theme.border.style)
+            .border_type(self.theme.border._type);
+        let inner = block.inner(area);
+        let [content_area, input_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+
+        frame.render_widget(Clear, area); //this clears out the background
+        frame.render_widget(block, area);
+        frame.render_widget(
+            Paragraph::new(self.content(request)).wrap(Wrap { trim: false }),
+            content_area,
+        );
+        if self.protection.is_some() {
+            frame.render_widget(
+                Paragraph::new(Span::styled(self.typed_name.as_str(), self.theme.secondary)),
+                input_area,
+            );
+            let position_x = input_area.x + self.typed_name.chars().count() as u16;
+            frame.set_cursor_position((position_x, input_area.y));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::config::default_config;
+    use crate::processes::Process;
+
+    use super::*;
+
+    #[test]
+    fn should_confirm_kill_with_y() {
+        let mut popup = create_popup();
+        popup.handle_event(&confirm_kill(None));
+
+        let action = popup.handle_input(key(KeyCode::Char('y')), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::KillConfirmed(request)) if request.process.pid == 4242
+        ));
+        assert!(popup.request.is_none());
+    }
+
+    #[test]
+    fn should_cancel_kill() {
+        let mut popup = create_popup();
+        popup.handle_event(&confirm_kill(None));
+
+        popup.handle_input(key(KeyCode::Esc), AppAction::Close);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    #[test]
+    fn should_require_typed_name_for_protected_process() {
+        let mut popup = create_popup();
+        popup.handle_event(&confirm_kill(Some("it is owned by root".to_string())));
+
+        popup.handle_input(key(KeyCode::Char('y')), AppAction::Unmapped);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ShowNotification(_))
+        ));
+
+        popup.handle_input(key(KeyCode::Backspace), AppAction::DeleteChar);
+        for c in "sshd".chars() {
+            popup.handle_input(key(KeyCode::Char(c)), AppAction::Unmapped);
+        }
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::KillConfirmed(_))
+        ));
+    }
+
+    #[test]
+    fn should_ignore_pasted_name_of_protected_process() {
+        let mut popup = create_popup();
+        popup.handle_event(&confirm_kill(Some("it is owned by root".to_string())));
+
+        let action = popup.handle_terminal_event(&Event::Paste("sshd".to_string()));
+        assert!(matches!(action, KeyAction::Consumed));
+
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ShowNotification(_))
+        ));
+    }
+
+    fn create_popup() -> KillConfirmPopupComponent {
+        let config = default_config().unwrap();
+        KillConfirmPopupComponent::new(&config.ui, &config.key_mappings)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn confirm_kill(protection: Option<String>) -> ComponentEvent {
+        ComponentEvent::ConfirmKill {
+            request: Box::new(KillRequest {
+                process: Process {
+                    parent_pid: Some(1),
+                    children: 2,
+                    user_name: "root".to_string(),
+                    cmd: "sshd".to_string(),
+                    cmd_path: Some("/usr/bin/sshd".to_string()),
+                    ports: Some("22".to_string()),
+                    ..Process::test(4242)
+                },
+                graceful: true,
+                recheck_port: None,
+                kind: KillKind::Kill,
+            }),
+            protection,
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    layout::{Constraint, Layout},
+    text::{Line, Span},
+    widgets::{Block, Clear, HighlightSpacing, List, ListItem, ListState, Padding, Paragraph},
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::{PortCheck, PortOwner, ProcessType},
+    tui::LayoutRects,
+};
+
+use super::{
+    Component, ComponentEvent, KeyAction, KillKind, KillRequest, Notification, popup_action,
+    popup_area,
+};
+
+pub struct PortCheckPopupComponent {
+    is_open: bool,
+    port_input: String,
+    check: Option<PortCheck>,
+    list_state: ListState,
+    theme: PopupsTheme,
+    help_text: String,
+}
+
+impl PortCheckPopupComponent {
+    pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings) -> Self {
+        let kill = key_mappings.get_joined(AppAction::KillProcess, "/");
+        let force_kill = key_mappings.get_joined(AppAction::ForceKillProcess, "/");
+        let close = key_mappings.get_joined(AppAction::Close, "/");
+        Self {
+            is_open: false,
+            port_input: String::new(),
+            check: None,
+            list_state: ListState::default(),
+            theme: ui_config.popups.clone(),
+            help_text: format!(" enter check | {kill}/{force_kill} kill owner | {close} close "),
+        }
+    }
+
+    fn open(&mut self, port: Option<u16>) -> Option<ComponentEvent> {
+        self.is_open = true;
+        self.check = None;
+        self.list_state.select(None);
+        self.port_input = port.map(|p| p.to_string()).unwrap_or_default();
+        port.map(ComponentEvent::CheckPort)
+    }
+
+    fn check_port(&self) -> KeyAction {
+        match self.port_input.parse::<u16>() {
+            Ok(port) if port > 0 => KeyAction::Event(ComponentEvent::CheckPort(port)),
+            _ => KeyAction::Event(ComponentEvent::ShowNotification(Notification::error(
+                format!("Invalid port '{}'", self.port_input),
+            ))),
+        }
+    }
+
+    fn selected_owner(&self) -> Option<&PortOwner> {
+        let check = self.check.as_ref()?;
+        check.owners.get(self.list_state.selected()?)
+    }
+
+    fn kill_selected_owner(&self, graceful: bool) -> KeyAction {
+        let Some(check) = &self.check else {
+            return KeyAction::Consumed;
+        };
+        match self.selected_owner() {
+            Some(owner) => KeyAction::Event(ComponentEvent::KillProcess(Box::new(KillRequest {
+                process: owner.process.clone(),
+                graceful,
+                recheck_port: Some(check.port),
+                kind: KillKind::Kill,
+            }))),
+            None if check.owners.is_empty() => KeyAction::Event(ComponentEvent::ShowNotification(
+                Notification::info(format!("Port {} has no owner to kill", check.port)),
+            )),
+            None => KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                "Socket has no owner, kernel will release it shortly",
+            ))),
+        }
+    }
+
+    fn result_items(&self) -> Vec<ListItem<'static>> {
+        let Some(check) = &self.check else {
+            return vec![ListItem::new(Line::styled(
+                "Type port and press enter",
+                self.theme.secondary,
+            ))];
+        };
+        if check.is_free() {
+            return vec![ListItem::new(Line::styled(
+                format!("Port {} is free", check.port),
+                self.theme.primary,
+            ))];
+        }
+        let owners = check.owners.iter().map(|owner| {
+            let prc = &owner.process;
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:>7} ", prc.pid), self.theme.primary),
+                Span::styled(
+                    format!("{}{} ", prc.cmd, owner_kind(&prc.process_type)),
+                    self.theme.primary,
+                ),
+                Span::styled(
+                    format!("{} {} ({})", owner.binding, owner.state, prc.user_name),
+                    self.theme.secondary,
+                ),
+            ]))
+        });
+        let unowned = check.unowned.iter().map(|socket| {
+            ListItem::new(Line::styled(
+                format!("{:>7} no owner {} {}", "-", socket.binding, socket.state),
+                self.theme.secondary,
+            ))
+        });
+        owners.chain(unowned).collect()
+    }
+}
+
+fn owner_kind(process_type: &ProcessType) -> String {
+    match process_type {
+        ProcessType::Native => String::new(),
+        ProcessType::Container { container_id } => {
+            format!(
+                " [container {}]",
+                &container_id[..container_id.len().min(12)]
+            )
+        }
+        ProcessType::Pod(pod) => format!(" [pod {}]", pod.display_name()),
+    }
+}
+
+impl Component for PortCheckPopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        if !self.is_open {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close => {
+                self.is_open = false;
+            }
+            AppAction::NextItem => {
+                self.list_state.select_next();
+            }
+            AppAction::PreviousItem => {
+                self.list_state.select_previous();
+            }
+            AppAction::KillProcess => {
+                return self.kill_selected_owner(true);
+            }
+            AppAction::ForceKillProcess => {
+                return self.kill_selected_owner(false);
+            }
+            AppAction::DeleteChar => {
+                self.port_input.pop();
+            }
+            AppAction::Unmapped => match key.code {
+                KeyCode::Enter => return self.check_port(),
+                KeyCode::Char(c) if c.is_ascii_digit() && self.port_input.len() < 5 => {
+                    self.port_input.push(c);
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_event(&mut self, event: &ComponentEvent) -> Option<ComponentEvent> {
+        match event {
+            ComponentEvent::OpenPortCheck(port) => self.open(*port),
+            ComponentEvent::PortChecked(check) if self.is_open => {
+                let owners = check.owners.len();
+                self.check = Some(check.clone());
+                self.list_state.select((owners > 0).then_some(0));
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn handle_terminal_event(&mut self, event: &Event) -> KeyAction {
+        if let Event::Paste(text) = event
+            && self.is_open
+        {
+            self.port_input = text
+                .trim()
+                .trim_start_matches(':')
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .take(5)
+                .collect();
+        }
+        popup_action(self.is_open)
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        if !self.is_open {
+            return;
+        }
+        let area = popup_area(frame.area(), 60, 50);
+        let block = Block::bordered()
+            .title_top(Line::from(" Port check ").centered())
+            .title_bottom(Line::from(self.help_text.as_str()).centered())
+            .padding(Padding::horizontal(1))
+            .border_style(self.theme.border.style)
+            .border_type(self.theme.border._type);
+        let inner = block.inner(area);
+        let [input_area, results_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).areas(inner);
+
+        frame.render_widget(Clear, area); //this clears out the background
+        frame.render_widget(block, area);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("Port: ", self.theme.primary),
+                Span::styled(self.port_input.as_str(), self.theme.secondary),
+            ])),
+            input_area,
+        );
+        let position_x = input_area.x + "Port: ".len() as u16 + self.port_input.len() as u16;
+        frame.set_cursor_position((position_x, input_area.y));
+
+        let results = List::new(self.result_items())
+            .highlight_style(self.theme.selected_row)
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(results, results_area, &mut self.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::config::default_config;
+    use crate::processes::{Process, UnownedSocket};
+
+    use super::*;
+
+    #[test]
+    fn should_check_port_given_on_open() {
+        let mut popup = create_popup();
+
+        let event = popup.handle_event(&ComponentEvent::OpenPortCheck(Some(8080)));
+
+        assert!(popup.is_open);
+        assert_eq!(popup.port_input, "8080");
+        assert!(matches!(event, Some(ComponentEvent::CheckPort(8080))));
+    }
+
+    #[test]
+    fn should_check_typed_port_on_enter() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenPortCheck(None));
+
+        for c in ['3', 'x', '0', '0', '0'] {
+            popup.handle_input(key(KeyCode::Char(c)), AppAction::Unmapped);
+        }
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert_eq!(popup.port_input, "3000");
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::CheckPort(3000))
+        ));
+    }
+
+    #[test]
+    fn should_reject_invalid_port() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenPortCheck(None));
+        for c in "99999".chars() {
+            popup.handle_input(key(KeyCode::Char(c)), AppAction::Unmapped);
+        }
+
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ShowNotification(Notification { .. }))
+        ));
+    }
+
+    #[test]
+    fn should_request_kill_of_selected_owner() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenPortCheck(Some(8080)));
+        popup.handle_event(&ComponentEvent::PortChecked(PortCheck {
+            port: 8080,
+            owners: vec![PortOwner {
+                process: some_process(4242),
+                binding: "0.0.0.0:8080/tcp".to_string(),
+                state: "LISTEN".to_string(),
+            }],
+            unowned: vec![UnownedSocket {
+                binding: "127.0.0.1:8080/tcp".to_string(),
+                state: "TIME_WAIT".to_string(),
+            }],
+        }));
+
+        let action = popup.handle_input(key(KeyCode::Char('x')), AppAction::ForceKillProcess);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::KillProcess(request))
+                if request.process.pid == 4242 && !request.graceful && request.recheck_port == Some(8080)
+        ));
+    }
+
+    #[test]
+    fn should_not_handle_input_when_closed() {
+        let mut popup = create_popup();
+
+        let action = popup.handle_input(key(KeyCode::Char('1')), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    fn create_popup() -> PortCheckPopupComponent {
+        let config = default_config().unwrap();
+        PortCheckPopupComponent::new(&config.ui, &config.key_mappings)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn some_process(pid: u32) -> Process {
+        Process {
+            ports: Some("8080".to_string()),
+            port_bindings: Some("0.0.0.0:8080/tcp".to_string()),
+            ..Process::test(pid)
+        }
+    }
+}
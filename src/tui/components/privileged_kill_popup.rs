@@ -0,0 +1,200 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::PrivilegedKill,
+    tui::LayoutRects,
+};
+
+use super::{Component, ComponentEvent, KeyAction, Notification, popup_action, popup_area};
+
+pub struct PrivilegedKillPopupComponent {
+    kill: Option<PrivilegedKill>,
+    privilege_helper: Vec<String>,
+    theme: PopupsTheme,
+    close_bindings: String,
+}
+
+impl PrivilegedKillPopupComponent {
+    pub fn new(
+        ui_config: &UIConfig,
+        key_mappings: &KeyMappings,
+        privilege_helper: Vec<String>,
+    ) -> Self {
+        Self {
+            kill: None,
+            privilege_helper,
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    fn permission_denied(&mut self, kill: &PrivilegedKill) -> Option<ComponentEvent> {
+        //NOTE: without helper (or signal helper can send) there is nothing to retry, so only failure is reported
+        if kill.command_line(&self.privilege_helper).is_some() {
+            self.kill = Some(kill.clone());
+        }
+        Some(ComponentEvent::ShowNotification(Notification::error(
+            format!("Permission denied - {} : PID {}", kill.name, kill.pid),
+        )))
+    }
+
+    fn content(&self, kill: &PrivilegedKill) -> Vec<Line<'static>> {
+        vec![
+            Line::styled(
+                format!(
+                    "Permission denied to signal {} : PID {}.",
+                    kill.name, kill.pid
+                ),
+                self.theme.primary,
+            ),
+            Line::default(),
+            Line::from(vec![
+                Span::styled("Retry with ", self.theme.primary),
+                Span::styled(
+                    kill.command_line(&self.privilege_helper)
+                        .unwrap_or_default(),
+                    self.theme.secondary,
+                ),
+                Span::styled(" ?", self.theme.primary),
+            ]),
+        ]
+    }
+}
+
+impl Component for PrivilegedKillPopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        if self.kill.is_none() {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close => {
+                self.kill = None;
+            }
+            AppAction::Unmapped if matches!(key.code, KeyCode::Enter | KeyCode::Char('y')) => {
+                if let Some(kill) = self.kill.take() {
+                    return KeyAction::Event(ComponentEvent::RunPrivilegedKill(kill));
+                }
+            }
+            _ => (),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_event(&mut self, event: &ComponentEvent) -> Option<ComponentEvent> {
+        match event {
+            ComponentEvent::KillPermissionDenied(kill) => self.permission_denied(kill),
+            _ => None,
+        }
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.kill.is_some())
+    }
+
+    fn handle_terminal_event(&mut self, _: &Event) -> KeyAction {
+        popup_action(self.kill.is_some())
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        let Some(kill) = &self.kill else {
+            return;
+        };
+        let area = popup_area(frame.area(), 50, 30);
+        let block = Block::bordered()
+            .title_top(Line::from(" Permission denied ").centered())
+            .title_bottom(
+                Line::from(format!(" y/enter retry | {} cancel ", self.close_bindings)).centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .border_style(self.theme.border.style)
+            .border_type(self.theme.border._type);
+
+        frame.render_widget(Clear, area); //this clears out the background
+        frame.render_widget(
+            Paragraph::new(self.content(kill))
+                .wrap(Wrap { trim: false })
+                .block(block),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+    use sysinfo::Signal;
+
+    use crate::config::default_config;
+
+    use super::*;
+
+    #[test]
+    fn should_offer_retry_when_permission_is_denied() {
+        let mut popup = create_popup(vec!["sudo".to_string()]);
+
+        let event = popup.handle_event(&ComponentEvent::KillPermissionDenied(some_kill()));
+        let action = popup.handle_input(key(KeyCode::Char('y')), AppAction::Unmapped);
+
+        assert!(matches!(
+            event,
+            Some(ComponentEvent::ShowNotification(Notification { message, .. }))
+                if message == "Permission denied - nginx : PID 4242"
+        ));
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::RunPrivilegedKill(kill)) if kill == some_kill()
+        ));
+        assert!(popup.kill.is_none());
+    }
+
+    #[test]
+    fn should_cancel_retry() {
+        let mut popup = create_popup(vec!["sudo".to_string()]);
+        popup.handle_event(&ComponentEvent::KillPermissionDenied(some_kill()));
+
+        popup.handle_input(key(KeyCode::Esc), AppAction::Close);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    #[test]
+    fn should_only_notify_when_no_helper_is_configured() {
+        let mut popup = create_popup(vec![]);
+
+        let event = popup.handle_event(&ComponentEvent::KillPermissionDenied(some_kill()));
+
+        assert!(matches!(event, Some(ComponentEvent::ShowNotification(_))));
+        assert!(popup.kill.is_none());
+    }
+
+    fn create_popup(privilege_helper: Vec<String>) -> PrivilegedKillPopupComponent {
+        let config = default_config().unwrap();
+        PrivilegedKillPopupComponent::new(&config.ui, &config.key_mappings, privilege_helper)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn some_kill() -> PrivilegedKill {
+        PrivilegedKill {
+            pid: 4242,
+            start_time: None,
+            name: "nginx".to_string(),
+            args: String::new(),
+            signal: Signal::Term,
+        }
+    }
+}
@@ -1,17 +1,23 @@
 use ratatui::{
-    prelude::Rect,
-    text::Line,
-    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::{config::ui::ProcessDetailsTheme, processes::Process, tui::LayoutRects};
+use crate::{
+    config::ui::ProcessDetailsTheme,
+    processes::{Process, ProcessType, Scheduling},
+    tui::LayoutRects,
+};
 
 pub struct ProcessDetailsComponent {
     process_details_scroll_state: ScrollbarState,
     process_details_scroll_offset: u16,
     process_details_number_of_lines: u16,
     area_content_height: u16,
+    // scheduling of selected process by its PID, it takes syscalls so it is not read on every render
+    scheduling: Option<(u32, Scheduling)>,
     theme: ProcessDetailsTheme,
 }
 
@@ -21,105 +27,73 @@ impl ProcessDetailsComponent {
         Self {
             process_details_scroll_offset: 0,
             process_details_number_of_lines: 0,
-            //NOTE: we don't update this, value 1 means that this should be rendered
             process_details_scroll_state: ScrollbarState::new(1),
             area_content_height: 0,
+            scheduling: None,
             theme,
         }
     }
 
     pub fn process_details_down(&mut self) {
-        let content_scrolled =
-            self.process_details_number_of_lines - self.process_details_scroll_offset;
+        let content_scrolled = self
+            .process_details_number_of_lines
+            .saturating_sub(self.process_details_scroll_offset);
 
         if content_scrolled > self.area_content_height {
             self.process_details_scroll_offset =
                 self.process_details_scroll_offset.saturating_add(1);
         }
+        self.update_scroll_state();
     }
 
     pub fn process_details_up(&mut self) {
         self.process_details_scroll_offset = self.process_details_scroll_offset.saturating_sub(1);
+        self.update_scroll_state();
     }
 
     pub fn reset_details_scroll_offset(&mut self) {
         self.process_details_scroll_offset = 0;
+        self.update_scroll_state();
     }
 
-    fn calculate_wrapped_lines(text: &str, width: u16) -> u16 {
-        if text.is_empty() {
-            return 1;
-        }
-        let width = width as usize;
-
-        // Simulate word wrapping similar to ratatui's Paragraph
-        let mut line_count = 0;
-        let mut current_line_width = 0;
-
-        for word in text.split_inclusive(' ') {
-            let word_width = word.width();
-
-            if current_line_width == 0 {
-                // First word on the line
-                current_line_width = word_width;
-                line_count += 1;
-            } else if current_line_width + word_width <= width {
-                // Word fits on current line
-                current_line_width += word_width;
-            } else {
-                // Word needs a new line
-                current_line_width = word_width;
-                line_count += 1;
-            }
+    /// Scheduling is read again on next render, i.e. when processes were refreshed
+    pub fn forget_scheduling(&mut self) {
+        self.scheduling = None;
+    }
+
+    fn scheduling(&mut self, pid: u32) -> &Scheduling {
+        if self
+            .scheduling
+            .as_ref()
+            .is_none_or(|(read_pid, _)| *read_pid != pid)
+        {
+            self.scheduling = Some((pid, Scheduling::read(pid)));
         }
+        self.scheduling
+            .as_ref()
+            .map(|(_, scheduling)| scheduling)
+            .expect("scheduling was just read")
+    }
 
-        line_count.max(1) as u16
+    //NOTE: lines are already wrapped, so scrolling stops at the last one
+    fn update_process_details_number_of_lines(&mut self, lines: &[Line]) {
+        self.process_details_number_of_lines = u16::try_from(lines.len()).unwrap_or(u16::MAX);
+        // details of newly selected process may be shorter than scrolled offset
+        self.process_details_scroll_offset =
+            self.process_details_scroll_offset.min(self.max_offset());
+        self.update_scroll_state();
     }
 
-    fn update_process_details_number_of_lines(
-        &mut self,
-        selected_process: Option<&Process>,
-        area: Rect,
-    ) {
-        let content_width = area.width - 2;
-
-        match selected_process.as_ref() {
-            Some(process) => {
-                // rebuild to-be-rendered lines to calculate their wrapped height
-                let ports = process
-                    .ports
-                    .as_deref()
-                    .map(|p| format!(" PORTS: {p}"))
-                    .unwrap_or("".to_string());
-                let parent = process
-                    .parent_pid
-                    .map(|p| format!(" PARENT: {p}"))
-                    .unwrap_or("".to_string());
-
-                let line1 = format!(
-                    "USER: {} PID: {}{} START TIME: {}, RUN TIME: {} MEMORY: {}MB{}",
-                    process.user_name,
-                    process.pid,
-                    parent,
-                    process.start_time,
-                    process.run_time,
-                    process.memory / 1024 / 1024,
-                    ports,
-                );
-                let line2 = format!("CMD: {}", process.exe());
-                let line3 = format!("ARGS: {}", process.args);
-
-                let line1_wrapped = Self::calculate_wrapped_lines(&line1, content_width);
-                let line2_wrapped = Self::calculate_wrapped_lines(&line2, content_width);
-                let line3_wrapped = Self::calculate_wrapped_lines(&line3, content_width);
-
-                self.process_details_number_of_lines =
-                    line1_wrapped + line2_wrapped + line3_wrapped;
-            }
-            None => {
-                self.process_details_number_of_lines = 1;
-            }
-        }
+    fn max_offset(&self) -> u16 {
+        self.process_details_number_of_lines
+            .saturating_sub(self.area_content_height)
+    }
+
+    //NOTE: each scroll offset is one position, so thumb reaches bottom when last line is shown
+    fn update_scroll_state(&mut self) {
+        self.process_details_scroll_state = ScrollbarState::new(self.max_offset() as usize + 1)
+            .viewport_content_length(self.area_content_height as usize)
+            .position(self.process_details_scroll_offset as usize);
     }
 
     pub fn render(
@@ -129,10 +103,22 @@ impl ProcessDetailsComponent {
         selected_process: Option<&Process>,
     ) {
         let area = layout.process_details;
-        self.area_content_height = area.height - 2;
-        let lines = process_details_lines(selected_process);
+        if area.is_empty() {
+            return;
+        }
+        self.area_content_height = area.height.saturating_sub(2);
+        let sections = match selected_process {
+            Some(prc) => details_sections(prc, self.scheduling(prc.pid)),
+            None => vec![],
+        };
+        //NOTE: details next to table are tall enough to show every field on its own line
+        let lines = match expanded_lines_count(&sections) <= self.area_content_height as usize {
+            true => expanded_lines(&sections),
+            false => compact_lines(&sections),
+        };
+        let lines = wrap_lines(lines, area.width.saturating_sub(2));
+        self.update_process_details_number_of_lines(&lines);
         let details = Paragraph::new(lines)
-            .wrap(Wrap { trim: false })
             .left_aligned()
             .block(
                 Block::default()
@@ -145,7 +131,6 @@ impl ProcessDetailsComponent {
             )
             .scroll((self.process_details_scroll_offset, 0));
         frame.render_widget(details, area);
-        self.update_process_details_number_of_lines(selected_process, area);
         frame.render_stateful_widget(
             Scrollbar::default()
                 .orientation(ScrollbarOrientation::VerticalRight)
@@ -160,33 +145,409 @@ impl ProcessDetailsComponent {
     }
 }
 
-fn process_details_lines<'a>(selected_process: Option<&'a Process>) -> Vec<Line<'a>> {
-    match selected_process {
-        Some(prc) => {
-            let ports = prc
-                .ports
-                .as_deref()
-                .map(|p| format!(" PORTS: {p}"))
-                .unwrap_or("".to_string());
-            let parent = prc
-                .parent_pid
-                .map(|p| format!(" PARENT: {p}"))
-                .unwrap_or("".to_string());
+/// Group of related process details, i.e. identity or resources
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetailsSection {
+    pub title: &'static str,
+    pub fields: Vec<DetailsField>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetailsField {
+    pub label: &'static str,
+    pub value: String,
+}
+
+impl DetailsSection {
+    fn new(title: &'static str, fields: Vec<Option<DetailsField>>) -> Option<Self> {
+        let fields = fields.into_iter().flatten().collect::<Vec<_>>();
+        (!fields.is_empty()).then_some(Self { title, fields })
+    }
+}
+
+fn field(label: &'static str, value: impl ToString) -> Option<DetailsField> {
+    Some(DetailsField {
+        label,
+        value: value.to_string(),
+    })
+}
+
+fn optional_field<T: ToString>(label: &'static str, value: Option<T>) -> Option<DetailsField> {
+    value.and_then(|value| field(label, value))
+}
+
+//NOTE: sections without any value (i.e. network of process without ports) are skipped
+pub fn details_sections(prc: &Process, scheduling: &Scheduling) -> Vec<DetailsSection> {
+    let (container, pod) = match &prc.process_type {
+        ProcessType::Native => (None, None),
+        ProcessType::Container { .. } => (prc.container(), None),
+        ProcessType::Pod(pod) => (
+            pod.container_id
+                .as_ref()
+                .map(|id| id.chars().take(12).collect::<String>()),
+            Some(pod.display_name()),
+        ),
+    };
+    [
+        DetailsSection::new(
+            "Identity",
+            vec![
+                field("USER", &prc.user_name),
+                field("PID", prc.pid),
+                optional_field("PARENT", prc.parent_pid),
+                field("STATE", &prc.state),
+            ],
+        ),
+        DetailsSection::new(
+            "Resources",
+            vec![
+                field("START TIME", prc.start_time_as_string()),
+                field("RUN TIME", prc.run_time_as_string()),
+                field("MEMORY", format!("{}MB", prc.memory / 1024 / 1024)),
+                field("CPU", format!("{:.1}%", prc.cpu_usage)),
+                optional_field("NICE", scheduling.nice),
+                optional_field("CPUS", scheduling.cpu_affinity.as_deref()),
+            ],
+        ),
+        DetailsSection::new(
+            "Network",
+            vec![optional_field("PORTS", prc.port_bindings.as_deref())],
+        ),
+        DetailsSection::new(
+            "Command",
+            vec![
+                field("CMD", prc.exe()),
+                optional_field("CWD", prc.cwd.as_deref()),
+                field("ARGS", &prc.args),
+            ],
+        ),
+        DetailsSection::new(
+            "Container",
+            vec![
+                optional_field("CONTAINER", container),
+                optional_field("POD", pod),
+            ],
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn no_process_lines() -> Vec<Line<'static>> {
+    vec![Line::from("No process selected")]
+}
+
+// every section takes one line
+fn compact_lines(sections: &[DetailsSection]) -> Vec<Line<'static>> {
+    if sections.is_empty() {
+        return no_process_lines();
+    }
+    let title_width = section_title_width(sections);
+    sections
+        .iter()
+        .map(|section| {
+            let mut spans = vec![section_title(section, title_width)];
+            spans.extend(
+                section
+                    .fields
+                    .iter()
+                    .map(|field| Span::from(format!("{}: {} ", field.label, field.value))),
+            );
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn expanded_lines_count(sections: &[DetailsSection]) -> usize {
+    sections
+        .iter()
+        .map(|section| section.fields.len() + 1)
+        .sum::<usize>()
+        .max(1)
+}
+
+// title of section followed by its fields with aligned values
+fn expanded_lines(sections: &[DetailsSection]) -> Vec<Line<'static>> {
+    if sections.is_empty() {
+        return no_process_lines();
+    }
+    let label_width = sections
+        .iter()
+        .flat_map(|section| &section.fields)
+        .map(|field| field.label.len())
+        .max()
+        .unwrap_or_default();
+    sections
+        .iter()
+        .flat_map(|section| {
+            std::iter::once(Line::from(section_title(section, 0))).chain(section.fields.iter().map(
+                move |field| Line::from(format!("  {:<label_width$} {}", field.label, field.value)),
+            ))
+        })
+        .collect()
+}
+
+/// Wraps lines at word boundaries to fit width, words wider than width are broken at its edge.
+/// Paragraph is not asked to wrap them, so number of lines to scroll is known up front
+fn wrap_lines(lines: Vec<Line<'static>>, width: u16) -> Vec<Line<'static>> {
+    let width = usize::from(width.max(1));
+    lines
+        .into_iter()
+        .flat_map(|line| wrap_line(line, width))
+        .collect()
+}
+
+fn wrap_line(line: Line<'static>, width: usize) -> Vec<Line<'static>> {
+    let mut wrapped = vec![];
+    let mut current = vec![];
+    let mut current_width = 0;
+    for span in line.spans {
+        for word in span.content.split_inclusive(' ') {
+            let word_width = word.trim_end_matches(' ').width();
+            // word that fits on its own line is moved to the next one as whole
+            if current_width > 0 && current_width + word_width > width && word_width <= width {
+                wrapped.push(Line::from(std::mem::take(&mut current)));
+                current_width = 0;
+            }
+            let mut rest = word;
+            while current_width + rest.trim_end_matches(' ').width() > width {
+                if current_width >= width {
+                    wrapped.push(Line::from(std::mem::take(&mut current)));
+                    current_width = 0;
+                    continue;
+                }
+                let (head, tail) = split_at_width(rest, width - current_width);
+                current.push(Span::styled(head.to_string(), span.style));
+                wrapped.push(Line::from(std::mem::take(&mut current)));
+                current_width = 0;
+                rest = tail;
+            }
+            if !rest.is_empty() {
+                current_width += rest.width();
+                current.push(Span::styled(rest.to_string(), span.style));
+            }
+        }
+    }
+    if !current.is_empty() || wrapped.is_empty() {
+        wrapped.push(Line::from(current));
+    }
+    wrapped
+}
+
+// at least one char is taken, so char wider than pane does not stop wrapping
+fn split_at_width(text: &str, width: usize) -> (&str, &str) {
+    let mut taken = 0;
+    let split = text
+        .char_indices()
+        .find(|(index, c)| {
+            taken += c.width().unwrap_or_default();
+            *index > 0 && taken > width
+        })
+        .map(|(index, _)| index)
+        .unwrap_or(text.len());
+    text.split_at(split)
+}
+
+fn section_title_width(sections: &[DetailsSection]) -> usize {
+    sections
+        .iter()
+        .map(|section| section.title.len())
+        .max()
+        .unwrap_or_default()
+}
+
+fn section_title(section: &DetailsSection, width: usize) -> Span<'static> {
+    Span::styled(
+        format!("{:<width$} ", section.title),
+        Style::default().add_modifier(Modifier::BOLD),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::processes::PodInfo;
+
+    use super::*;
+
+    #[test]
+    fn should_skip_sections_without_values() {
+        let sections = details_sections(&some_process(), &Scheduling::default());
+
+        assert_eq!(
+            sections.iter().map(|s| s.title).collect::<Vec<_>>(),
+            vec!["Identity", "Resources", "Command"]
+        );
+        assert_eq!(
+            sections[0].fields,
             vec![
-                Line::from(format!(
-                    "USER: {} PID: {}{} START TIME: {}, RUN TIME: {} MEMORY: {}MB{}",
-                    prc.user_name,
-                    prc.pid,
-                    parent,
-                    prc.start_time,
-                    prc.run_time,
-                    prc.memory / 1024 / 1024,
-                    ports,
-                )),
-                Line::from(format!("CMD: {}", prc.exe())),
-                Line::from(format!("ARGS: {}", prc.args)),
+                DetailsField {
+                    label: "USER",
+                    value: "user".to_string()
+                },
+                DetailsField {
+                    label: "PID",
+                    value: "42".to_string()
+                },
+                DetailsField {
+                    label: "STATE",
+                    value: "Run".to_string()
+                },
             ]
+        );
+    }
+
+    #[test]
+    fn should_show_network_and_container_sections() {
+        let prc = Process {
+            port_bindings: Some("0.0.0.0:8080".to_string()),
+            process_type: ProcessType::Pod(PodInfo {
+                uid: "uid".to_string(),
+                namespace: Some("default".to_string()),
+                name: Some("nginx".to_string()),
+                container_name: None,
+                container_id: Some("0123456789abcdef".to_string()),
+            }),
+            ..some_process()
+        };
+
+        let sections = details_sections(&prc, &Scheduling::default());
+
+        assert_eq!(sections[2].title, "Network");
+        assert_eq!(sections[2].fields[0].value, "0.0.0.0:8080");
+        let container = sections.last().unwrap();
+        assert_eq!(container.title, "Container");
+        assert_eq!(
+            container
+                .fields
+                .iter()
+                .map(|f| f.value.as_str())
+                .collect::<Vec<_>>(),
+            vec!["0123456789ab", "default/nginx"]
+        );
+    }
+
+    #[test]
+    fn should_show_scheduling_of_selected_process() {
+        let scheduling = Scheduling {
+            nice: Some(5),
+            cpu_affinity: Some("0-3".to_string()),
+        };
+
+        let sections = details_sections(&some_process(), &scheduling);
+
+        let resources = sections.iter().find(|s| s.title == "Resources").unwrap();
+        assert!(resources.fields.contains(&DetailsField {
+            label: "NICE",
+            value: "5".to_string()
+        }));
+        assert!(resources.fields.contains(&DetailsField {
+            label: "CPUS",
+            value: "0-3".to_string()
+        }));
+    }
+
+    #[test]
+    fn should_render_section_per_line_in_compact_mode() {
+        let sections = details_sections(&some_process(), &Scheduling::default());
+
+        let lines = compact_lines(&sections);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0].to_string(),
+            "Identity  USER: user PID: 42 STATE: Run "
+        );
+        assert_eq!(
+            expanded_lines(&sections).len(),
+            expanded_lines_count(&sections)
+        );
+        assert_eq!(
+            expanded_lines(&sections)[1].to_string(),
+            "  USER       user"
+        );
+    }
+
+    #[test]
+    fn should_keep_scroll_offset_within_lines() {
+        let mut details = ProcessDetailsComponent::new(ProcessDetailsTheme::default());
+        details.area_content_height = 2;
+        let lines = compact_lines(&details_sections(&some_process(), &Scheduling::default()));
+        details.update_process_details_number_of_lines(&wrap_lines(lines, 98));
+        assert_eq!(details.process_details_number_of_lines, 3);
+
+        details.process_details_down();
+        details.process_details_down();
+        assert_eq!(details.process_details_scroll_offset, 1);
+
+        details.update_process_details_number_of_lines(&no_process_lines());
+        assert_eq!(details.process_details_scroll_offset, 0);
+    }
+
+    #[test]
+    fn should_count_words_wider_than_pane_as_wrapped_lines() {
+        let mut details = ProcessDetailsComponent::new(ProcessDetailsTheme::default());
+        details.area_content_height = 2;
+        let lines = vec![Line::from("PATH: /very/long/path/without/spaces/to/binary")];
+        details.update_process_details_number_of_lines(&wrap_lines(lines, 10));
+        assert_eq!(details.process_details_number_of_lines, 5);
+
+        details.process_details_down();
+        details.process_details_down();
+        details.process_details_down();
+        assert_eq!(details.process_details_scroll_offset, 3);
+        assert_eq!(
+            details.process_details_scroll_state,
+            ScrollbarState::new(4)
+                .viewport_content_length(2)
+                .position(3)
+        );
+    }
+
+    #[test]
+    fn should_read_scheduling_again_only_for_other_process_or_after_refresh() {
+        let mut details = ProcessDetailsComponent::new(ProcessDetailsTheme::default());
+        let cached = Scheduling {
+            nice: Some(5),
+            cpu_affinity: Some("0-3".to_string()),
+        };
+        details.scheduling = Some((42, cached.clone()));
+
+        assert_eq!(details.scheduling(42), &cached);
+        details.scheduling(std::process::id());
+        assert_eq!(
+            details.scheduling.as_ref().map(|(pid, _)| *pid),
+            Some(std::process::id())
+        );
+        details.forget_scheduling();
+        assert_eq!(details.scheduling, None);
+    }
+
+    #[test]
+    fn should_wrap_at_words_and_keep_styles() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let line = Line::from(vec![
+            Span::styled("Command ", bold),
+            Span::from("CMD: /usr/bin/node ARGS: server.js"),
+        ]);
+
+        let lines = wrap_lines(vec![line], 14);
+
+        assert_eq!(
+            lines.iter().map(Line::to_string).collect::<Vec<_>>(),
+            vec!["Command CMD: ", "/usr/bin/node ", "ARGS: ", "server.js"]
+        );
+        assert_eq!(lines[0].spans[0].style, bold);
+        assert_eq!(lines[0].spans[1].style, Style::default());
+        assert_eq!(wrap_lines(vec![Line::from("")], 14).len(), 1);
+        assert_eq!(wrap_lines(vec![Line::from("日本語")], 1).len(), 3);
+    }
+
+    fn some_process() -> Process {
+        Process {
+            args: "server.js".to_string(),
+            state: "Run".to_string(),
+            ..Process::test(42)
         }
-        None => vec![Line::from("No process selected")],
     }
 }
@@ -1,5 +1,9 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use itertools::Itertools;
 use ratatui::{
-    layout::Constraint,
+    layout::{Constraint, Flex, Layout, Margin, Position, Rect},
     text::{Line, Span},
     widgets::{
         Block, Borders, HighlightSpacing, Row, Scrollbar, ScrollbarOrientation, ScrollbarState,
@@ -8,50 +12,56 @@ use ratatui::{
 };
 
 use crate::{
-    config::ui::{IconsStruct, TableTheme},
-    processes::{MatchedBy, ProcessSearchResults, ResultItem},
-    tui::{LayoutRects, highlight::highlight_text},
+    config::ui::{ColumnId, IconsStruct, TableTheme},
+    processes::{MatchedBy, Process, ProcessSearchResults, ResultItem, SuspendedProcesses},
+    tui::{
+        LayoutRects,
+        highlight::{TRUNCATED_STR, highlight_text},
+    },
 };
 
 pub struct ProcessTableComponent {
     headers: Vec<String>,
+    suspended_marker: String,
+    marked_marker: String,
     theme: TableTheme,
     process_table: TableState,
     process_table_scroll_state: ScrollbarState,
+    // column picked by header click, results are kept in match order when not set
+    sort: Option<ColumnSort>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnSort {
+    pub column: ColumnId,
+    pub descending: bool,
+}
+
+/// Part of table under mouse cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableClick {
+    Header(ColumnId),
+    // index of search result
+    Row(usize),
 }
 
-const MAX_CMD_LEN: usize = 20;
-const MAX_PATH_LEN: usize = 38;
-const MAX_ARGS_LEN: usize = 35;
-const MAX_PORTS_LEN: usize = 20;
-
-const TABLE_WIDTHS: [Constraint; 8] = [
-    Constraint::Percentage(5),
-    Constraint::Percentage(5),
-    Constraint::Percentage(5),
-    Constraint::Percentage(5),
-    Constraint::Percentage(10),
-    Constraint::Percentage(30),
-    Constraint::Percentage(25),
-    Constraint::Percentage(15),
-];
+// same as table default
+const COLUMN_SPACING: u16 = 1;
 
 impl ProcessTableComponent {
     pub fn new(icons: &IconsStruct, theme: TableTheme) -> Self {
         Self {
             process_table: TableState::default(),
             process_table_scroll_state: ScrollbarState::new(0),
+            sort: None,
+            suspended_marker: icons.suspended.clone(),
+            marked_marker: icons.marked.clone(),
+            headers: theme
+                .columns
+                .iter()
+                .map(|column| column.id.header(icons))
+                .collect(),
             theme,
-            headers: vec![
-                format!("USER {}", icons.user).trim().to_string(),
-                format!("PID {}", icons.pid).trim().to_string(),
-                format!("PARENT {}", icons.parent).trim().to_string(),
-                format!("TIME {}", icons.time).trim().to_string(),
-                format!("CMD {}", icons.cmd).trim().to_string(),
-                format!("PATH {}", icons.path).trim().to_string(),
-                format!("ARGS {}", icons.args).trim().to_string(),
-                format!("PORTS {}", icons.ports).trim().to_string(),
-            ],
         }
     }
 
@@ -125,52 +135,178 @@ impl ProcessTableComponent {
         }
     }
 
+    fn create_cell<'a>(
+        &self,
+        column: ColumnId,
+        item: &'a ResultItem,
+        suspended: &SuspendedProcesses,
+        marked: &[Process],
+        width: u16,
+    ) -> Line<'a> {
+        //NOTE: highlighted text is truncated by us, so it is cut with marker at the column edge
+        let max_len = (width as usize).saturating_sub(TRUNCATED_STR.len());
+        let data = &item.process;
+        let text = match column {
+            ColumnId::User => data.user_name.clone(),
+            ColumnId::Pid => {
+                let mut pid = data.pid.to_string();
+                if suspended.contains(data.pid) {
+                    pid = format!("{pid} {}", self.suspended_marker);
+                }
+                if marked.iter().any(|prc| prc.pid == data.pid) {
+                    pid = format!("{pid} {}", self.marked_marker);
+                }
+                pid
+            }
+            ColumnId::Ppid => data.parent_as_string(),
+            ColumnId::Time => data.run_time_as_string(),
+            ColumnId::Cmd => return self.create_line(item, &data.cmd, MatchedBy::Cmd, max_len),
+            ColumnId::Path => {
+                return self.create_line(
+                    item,
+                    data.cmd_path.as_deref().unwrap_or(""),
+                    MatchedBy::Path,
+                    max_len,
+                );
+            }
+            ColumnId::Args => {
+                return self.create_line(item, &data.args, MatchedBy::Args, max_len);
+            }
+            ColumnId::Ports => {
+                return self.create_line(
+                    item,
+                    data.ports.as_deref().unwrap_or(""),
+                    MatchedBy::Port,
+                    max_len,
+                );
+            }
+            ColumnId::Cpu => format!("{:.1}", data.cpu_usage),
+            ColumnId::Mem => format!("{}MB", data.memory / 1024 / 1024),
+            ColumnId::State => data.state.clone(),
+            ColumnId::Cwd => data.cwd.clone().unwrap_or_default(),
+            ColumnId::Container => data.container().unwrap_or_default(),
+        };
+        Line::from(Span::styled(text, self.theme.cell.normal))
+    }
+
+    fn constraints(&self) -> Vec<Constraint> {
+        self.theme
+            .columns
+            .iter()
+            .map(|column| column.constraint())
+            .collect()
+    }
+
+    //NOTE: columns are split the same way table does it, so cell width is known before rendering
+    fn column_areas(&self, area: Rect) -> Rc<[Rect]> {
+        let selection_width = Span::raw(self.theme.row.selected_symbol.as_str()).width() as u16;
+        let [_, columns_area] =
+            Layout::horizontal([Constraint::Length(selection_width), Constraint::Fill(0)])
+                .areas(area.inner(Margin::new(1, 1)));
+        Layout::horizontal(self.constraints())
+            .flex(Flex::Start)
+            .spacing(COLUMN_SPACING)
+            .split(columns_area)
+    }
+
+    /// Finds header or row under position, area is the one table was rendered in
+    pub fn clicked(&self, area: Rect, position: Position) -> Option<TableClick> {
+        let inner = area.inner(Margin::new(1, 1));
+        if !inner.contains(position) {
+            return None;
+        }
+        if position.y == inner.y {
+            return self
+                .theme
+                .columns
+                .iter()
+                .zip(self.column_areas(area).iter())
+                .find(|(_, column_area)| column_area.contains(position))
+                .map(|(column, _)| TableClick::Header(column.id));
+        }
+        let row = (position.y - inner.y - 1) as usize;
+        Some(TableClick::Row(self.process_table.offset() + row))
+    }
+
+    /// Sorts ascending, then descending and returns to match order on third click
+    pub fn toggle_sort(&mut self, column: ColumnId) -> Option<ColumnSort> {
+        self.sort = match self.sort {
+            Some(sort) if sort.column == column && !sort.descending => Some(ColumnSort {
+                column,
+                descending: true,
+            }),
+            Some(sort) if sort.column == column => None,
+            _ => Some(ColumnSort {
+                column,
+                descending: false,
+            }),
+        };
+        self.sort
+    }
+
+    pub fn sort_results(&self, results: &mut ProcessSearchResults) {
+        let Some(sort) = self.sort else {
+            return;
+        };
+        //NOTE: sort is stable, so processes with equal values stay in match order
+        results.items.sort_by(|a, b| {
+            let ordering = compare_column(sort.column, &a.process, &b.process);
+            match sort.descending {
+                true => ordering.reverse(),
+                false => ordering,
+            }
+        });
+    }
+
+    fn header(&self, index: usize, column: ColumnId) -> String {
+        let header = &self.headers[index];
+        match self.sort {
+            Some(sort) if sort.column == column && sort.descending => format!("{header} ↓"),
+            Some(sort) if sort.column == column => format!("{header} ↑"),
+            _ => header.clone(),
+        }
+    }
+
     pub fn render(
         &mut self,
         f: &mut ratatui::Frame,
         layout: &LayoutRects,
         search_results: &ProcessSearchResults,
+        suspended: &SuspendedProcesses,
+        marked: &[Process],
     ) {
         let area = layout.process_table;
+        let widths = self
+            .column_areas(area)
+            .iter()
+            .map(|column_area| column_area.width)
+            .collect_vec();
         let rows = search_results.iter().enumerate().map(|(i, item)| {
             let row_style = match i % 2 {
                 0 => self.theme.row.even,
                 _ => self.theme.row.odd,
             };
-            let data = &item.process;
-            Row::new(vec![
-                Line::from(Span::styled(
-                    data.user_name.as_str(),
-                    self.theme.cell.normal,
-                )),
-                Line::from(Span::styled(
-                    format!("{}", data.pid),
-                    self.theme.cell.normal,
-                )),
-                Line::from(Span::styled(
-                    data.parent_as_string(),
-                    self.theme.cell.normal,
-                )),
-                Line::from(Span::styled(&data.run_time, self.theme.cell.normal)),
-                self.create_line(item, &data.cmd, MatchedBy::Cmd, MAX_CMD_LEN),
-                self.create_line(
-                    item,
-                    data.cmd_path.as_deref().unwrap_or(""),
-                    MatchedBy::Path,
-                    MAX_PATH_LEN,
-                ),
-                self.create_line(item, &data.args, MatchedBy::Args, MAX_ARGS_LEN),
-                self.create_line(
-                    item,
-                    data.ports.as_deref().unwrap_or(""),
-                    MatchedBy::Port,
-                    MAX_PORTS_LEN,
-                ),
-            ])
+            Row::new(
+                self.theme
+                    .columns
+                    .iter()
+                    .zip(widths.iter())
+                    .map(|(column, width)| {
+                        self.create_cell(column.id, item, suspended, marked, *width)
+                            .alignment(column.alignment)
+                    })
+                    .collect_vec(),
+            )
             .style(row_style)
         });
-        let table = Table::new(rows, TABLE_WIDTHS)
-            .header(Row::new(self.headers.iter().map(|r| r.as_str())))
+        let header =
+            self.theme.columns.iter().enumerate().map(|(i, column)| {
+                Line::from(self.header(i, column.id)).alignment(column.alignment)
+            });
+        let table = Table::new(rows, self.constraints())
+            .column_spacing(COLUMN_SPACING)
+            .flex(Flex::Start)
+            .header(Row::new(header))
             .block(
                 Block::default()
                     .title_position(self.theme.title.position)
@@ -202,3 +338,121 @@ impl ProcessTableComponent {
         );
     }
 }
+
+fn compare_column(column: ColumnId, a: &Process, b: &Process) -> Ordering {
+    match column {
+        ColumnId::User => a.user_name.cmp(&b.user_name),
+        ColumnId::Pid => a.pid.cmp(&b.pid),
+        ColumnId::Ppid => a.parent_pid.cmp(&b.parent_pid),
+        ColumnId::Time => a.run_time.cmp(&b.run_time),
+        ColumnId::Cmd => a.cmd.to_lowercase().cmp(&b.cmd.to_lowercase()),
+        ColumnId::Path => a.cmd_path.cmp(&b.cmd_path),
+        ColumnId::Args => a.args.cmp(&b.args),
+        ColumnId::Ports => a.ports.cmp(&b.ports),
+        ColumnId::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+        ColumnId::Mem => a.memory.cmp(&b.memory),
+        ColumnId::State => a.state.cmp(&b.state),
+        ColumnId::Cwd => a.cwd.cmp(&b.cwd),
+        ColumnId::Container => a.container().cmp(&b.container()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::ui::{ColumnConfig, ColumnWidth};
+    use crate::processes::{MatchData, MatchType};
+
+    use super::*;
+
+    #[test]
+    fn should_give_space_left_by_fixed_columns_to_fill_columns() {
+        let table = create_table();
+
+        // borders take 2 cells, selection symbol 1 and spacing between columns 2
+        let widths = table
+            .column_areas(Rect::new(0, 0, 55, 10))
+            .iter()
+            .map(|area| area.width)
+            .collect_vec();
+
+        assert_eq!(widths, vec![10, 10, 30]);
+    }
+
+    #[test]
+    fn should_find_clicked_header_and_row() {
+        let table = create_table();
+        let area = Rect::new(0, 5, 55, 10);
+
+        assert_eq!(
+            table.clicked(area, Position::new(2, 6)),
+            Some(TableClick::Header(ColumnId::Pid))
+        );
+        assert_eq!(
+            table.clicked(area, Position::new(20, 6)),
+            Some(TableClick::Header(ColumnId::Cmd))
+        );
+        assert_eq!(
+            table.clicked(area, Position::new(30, 9)),
+            Some(TableClick::Row(2))
+        );
+        assert_eq!(table.clicked(area, Position::new(30, 5)), None);
+        assert_eq!(table.clicked(area, Position::new(12, 6)), None);
+    }
+
+    #[test]
+    fn should_sort_ascending_descending_and_back_to_match_order() {
+        let mut table = create_table();
+        let mut results = ProcessSearchResults {
+            items: vec![some_item(2, 65), some_item(3, 3601), some_item(1, 50)],
+        };
+
+        table.toggle_sort(ColumnId::Time);
+        table.sort_results(&mut results);
+        assert_eq!(pids(&results), vec![1, 2, 3]);
+
+        table.toggle_sort(ColumnId::Time);
+        table.sort_results(&mut results);
+        assert_eq!(pids(&results), vec![3, 2, 1]);
+        assert_eq!(table.header(0, ColumnId::Pid), "PID");
+
+        assert_eq!(table.toggle_sort(ColumnId::Time), None);
+        assert_eq!(
+            table.toggle_sort(ColumnId::Pid),
+            Some(ColumnSort {
+                column: ColumnId::Pid,
+                descending: false
+            })
+        );
+        assert_eq!(table.header(0, ColumnId::Pid), "PID ↑");
+    }
+
+    fn create_table() -> ProcessTableComponent {
+        let column = |id, width| ColumnConfig {
+            width: Some(ColumnWidth(width)),
+            ..ColumnConfig::new(id)
+        };
+        let theme = TableTheme {
+            columns: vec![
+                column(ColumnId::Pid, Constraint::Length(10)),
+                column(ColumnId::Cmd, Constraint::Fill(1)),
+                column(ColumnId::Time, Constraint::Fill(3)),
+            ],
+            ..TableTheme::default()
+        };
+        ProcessTableComponent::new(&IconsStruct::ascii(), theme)
+    }
+
+    fn pids(results: &ProcessSearchResults) -> Vec<u32> {
+        results.iter().map(|item| item.process.pid).collect()
+    }
+
+    fn some_item(pid: u32, run_time: u64) -> ResultItem {
+        ResultItem::new(
+            MatchData::new(MatchedBy::Cmd, MatchType::Exact),
+            Process {
+                run_time,
+                ..Process::test(pid)
+            },
+        )
+    }
+}
@@ -1,29 +1,41 @@
-use std::sync::Mutex;
+use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender};
 
 use anyhow::Result;
-use arboard::Clipboard;
+use itertools::Itertools;
 use ratatui::Frame;
-use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use ratatui::crossterm::event::{
+    Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::Position;
+use sysinfo::Signal;
 use tui_input::InputRequest;
 
 use crate::config::keymappings::AppAction;
 use crate::processes::{
-    KilledProcess, OperationResult, Operations, ProcessManager, ProcssAsyncService,
+    BatchKill, KillCheck, KillError, KillGuard, KillOptions, KilledProcess, OperationResult,
+    Operations, PrivilegedKill, ProcessManager, ProcssAsyncService, SavedSearch, Scheduling,
+    SchedulingChange, SchedulingKind, SuspendedProcess, SuspendedProcesses,
+    selects_by_existence_only, signal_name,
 };
+use crate::tui::LayoutRects;
+use crate::tui::clipboard;
+use crate::tui::components::process_table::TableClick;
 use crate::tui::components::search_bar::CursorMove;
+use crate::tui::search_history::SearchHistory;
 use crate::{
-    config::ui::UIConfig,
-    processes::{IgnoreOptions, Process, ProcessSearchResults},
+    config::ui::{ColumnId, UIConfig},
+    processes::{IgnoreOptions, Process, ProcessSearchResults, ProcessType},
     tui::{
         ProcessRelatedSearch,
-        components::{KeyAction, Notification},
+        components::{KeyAction, KillKind, KillRequest, Notification},
     },
 };
 
 use super::{
-    Component, ComponentEvent, process_details::ProcessDetailsComponent,
-    process_table::ProcessTableComponent, search_bar::SearchBarComponent,
+    Component, ComponentEvent, copy_field_popup::copy_notification,
+    process_details::ProcessDetailsComponent, process_table::ProcessTableComponent,
+    search_bar::SearchBarComponent,
 };
 
 pub struct ProcessesViewComponent {
@@ -33,25 +45,44 @@ pub struct ProcessesViewComponent {
     process_table_component: ProcessTableComponent,
     process_details_component: ProcessDetailsComponent,
     search_bar: SearchBarComponent,
+    kill_guard: KillGuard,
+    suspended: SuspendedProcesses,
+    // processes renice and CPU affinity change is applied to instead of selected one
+    marked: Vec<Process>,
+    search_history: SearchHistory,
+    refresh_on_focus: bool,
+    // name of saved search with its query, it is forgotten once query is edited
+    saved_search: Option<(String, String)>,
 }
 
-// NOTE: clipboard access is initialized lazily because some systems do not provide a clipboard
-// backend at all. Surface those failures to the user instead of crashing the TUI.
-static CLIPBOARD: std::sync::LazyLock<Result<Mutex<Clipboard>, String>> =
-    std::sync::LazyLock::new(|| {
-        Clipboard::new()
-            .map(Mutex::new)
-            .map_err(|err| format!("Clipboard is unavailable: {err}"))
-    });
+// rows scrolled by one wheel step
+const MOUSE_SCROLL_STEP: usize = 3;
 
 impl ProcessesViewComponent {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         ui_config: &UIConfig,
         ignore_options: IgnoreOptions,
+        kill_options: KillOptions,
+        kill_guard: KillGuard,
+        suspended_state_file: Option<PathBuf>,
+        search_history_file: Option<PathBuf>,
+        saved_searches: Vec<SavedSearch>,
         initial_query: String,
+        saved_search: Option<String>,
     ) -> Result<Self> {
-        let mut process_service = ProcssAsyncService::new(ProcessManager::new()?, ignore_options);
-        let initial_results = process_service.find_processes(&initial_query);
+        let mut process_manager = ProcessManager::new()?;
+        let mut suspended = SuspendedProcesses::load(suspended_state_file);
+        //NOTE: persisted processes may be resumed or gone since last session
+        suspended.retain(|prc| process_manager.is_running(prc.pid, prc.start_time))?;
+        let mut process_service =
+            ProcssAsyncService::new(process_manager, ignore_options, kill_options)
+                .with_saved_searches(saved_searches);
+        let saved_search = saved_search.map(|name| (name, initial_query.clone()));
+        let initial_results = process_service.find_processes(
+            &initial_query,
+            saved_search.as_ref().map(|(name, _)| name.as_str()),
+        );
         let (ops_sender, results_receiver) = process_service.run_as_background_process();
         let mut component = Self {
             ops_sender,
@@ -70,6 +101,12 @@ impl ProcessesViewComponent {
                 &ui_config.search_bar,
                 ui_config.icons.get_icons().search_prompt.as_str(),
             ),
+            kill_guard,
+            suspended,
+            marked: vec![],
+            search_history: SearchHistory::load(search_history_file),
+            refresh_on_focus: ui_config.refresh_on_focus,
+            saved_search,
         };
         component.update_process_table_state();
         Ok(component)
@@ -110,9 +147,73 @@ impl ProcessesViewComponent {
             .update_process_table_state(number_of_items);
     }
 
+    fn show_results(&mut self, mut results: ProcessSearchResults) {
+        self.process_table_component.sort_results(&mut results);
+        self.search_results = results;
+        self.update_process_table_state();
+        self.process_details_component.forget_scheduling();
+    }
+
+    fn select_row(&mut self, index: usize) {
+        if index < self.search_results.len() {
+            self.process_table_component
+                .select_row_by_index(Some(index));
+            self.process_details_component.reset_details_scroll_offset();
+        }
+    }
+
+    //NOTE: unlike keys, wheel stops at the first and last row
+    fn scroll_rows(&mut self, down: bool) {
+        let Some(selected) = self.process_table_component.get_selected_process_index() else {
+            return;
+        };
+        let index = match down {
+            true => (selected + MOUSE_SCROLL_STEP).min(self.search_results.len().saturating_sub(1)),
+            false => selected.saturating_sub(MOUSE_SCROLL_STEP),
+        };
+        self.select_row(index);
+    }
+
+    fn sort_by_column(&mut self, column: ColumnId) -> KeyAction {
+        if self.process_table_component.toggle_sort(column).is_none() {
+            //NOTE: match order is only known to daemon, so search is run again
+            return match self.search_for_processess() {
+                Ok(()) => KeyAction::Consumed,
+                Err(notification) => {
+                    KeyAction::Event(ComponentEvent::ShowNotification(notification))
+                }
+            };
+        }
+        let selected_pid = self.get_selected_process().map(|prc| prc.pid);
+        self.process_table_component
+            .sort_results(&mut self.search_results);
+        //NOTE: selected process stays selected, it just moves to its new row
+        let index = selected_pid
+            .and_then(|pid| {
+                self.search_results
+                    .iter()
+                    .position(|item| item.process.pid == pid)
+            })
+            .unwrap_or_default();
+        self.select_row(index);
+        KeyAction::Consumed
+    }
+
     fn search_for_processess(&mut self) -> Result<(), Notification> {
-        let search_text = self.search_bar.get_search_text().to_string();
-        match self.ops_sender.send(Operations::Search(search_text)) {
+        let query = self.search_bar.get_search_text().to_string();
+        //NOTE: edited query is not the saved search anymore, even if it is edited back
+        if self
+            .saved_search
+            .as_ref()
+            .is_some_and(|(_, saved_query)| *saved_query != query)
+        {
+            self.saved_search = None;
+        }
+        let saved_search = self.saved_search.as_ref().map(|(name, _)| name.clone());
+        match self.ops_sender.send(Operations::Search {
+            query,
+            saved_search,
+        }) {
             Ok(_) => Ok(()),
             Err(_) => Err(Notification::error(
                 "Failed to send search request to process daemon",
@@ -121,25 +222,156 @@ impl ProcessesViewComponent {
     }
 
     fn kill_selected_process(&mut self, graceful: bool) -> KeyAction {
-        if let Some(prc) = self.get_selected_process() {
-            let pid = prc.pid;
-            let name = prc.cmd.clone();
-            let process_type = prc.process_type.clone();
-            return match self.ops_sender.send(Operations::KillProcess {
-                pid,
-                process_type,
-                graceful,
-                name,
-            }) {
-                Ok(_) => KeyAction::Consumed,
-                Err(_) => KeyAction::Event(ComponentEvent::ShowNotification(Notification::error(
-                    "Failed to send kill request to process daemon",
-                ))),
-            };
+        self.remember_query();
+        let Some(prc) = self.get_selected_process() else {
+            return KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                "No process selected",
+            )));
+        };
+        let request = KillRequest {
+            process: prc.clone(),
+            graceful,
+            recheck_port: None,
+            kind: KillKind::Kill,
+        };
+        match self.request_kill(request) {
+            Some(event) => KeyAction::Event(event),
+            None => KeyAction::Consumed,
+        }
+    }
+
+    //NOTE: containers and pods are restarted by their runtime, only native processes are started by pik
+    fn restart_selected_process(&mut self) -> KeyAction {
+        self.remember_query();
+        let Some(prc) = self.get_selected_process() else {
+            return KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                "No process selected",
+            )));
+        };
+        if prc.process_type != ProcessType::Native {
+            return KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                "Only native processes can be restarted, containers are restarted by their runtime",
+            )));
+        }
+        let request = KillRequest {
+            process: prc.clone(),
+            graceful: true,
+            recheck_port: None,
+            kind: KillKind::Restart,
+        };
+        match self.request_kill(request) {
+            Some(event) => KeyAction::Event(event),
+            None => KeyAction::Consumed,
+        }
+    }
+
+    fn request_kill(&self, request: KillRequest) -> Option<ComponentEvent> {
+        match self.kill_guard.check(&request.process) {
+            KillCheck::Allowed => self.send_kill(&request),
+            KillCheck::Confirm => Some(ComponentEvent::ConfirmKill {
+                request: Box::new(request),
+                protection: None,
+            }),
+            KillCheck::ConfirmTyped(reason) => Some(ComponentEvent::ConfirmKill {
+                request: Box::new(request),
+                protection: Some(reason),
+            }),
+            KillCheck::Refused(reason) => Some(ComponentEvent::ShowNotification(
+                Notification::error(format!(
+                    "Refusing to {} {} : PID {}, {reason}",
+                    request.kind.verb(),
+                    request.process.cmd,
+                    request.process.pid
+                )),
+            )),
+        }
+    }
+
+    fn send_kill(&self, request: &KillRequest) -> Option<ComponentEvent> {
+        let process = &request.process;
+        match request.kind {
+            KillKind::Kill => (),
+            KillKind::Restart => {
+                return self.send_operation(
+                    Operations::RestartProcess {
+                        pid: process.pid,
+                        name: process.cmd.clone(),
+                        args: process.args.clone(),
+                    },
+                    "Failed to send restart request to process daemon",
+                );
+            }
+            KillKind::Suspend => {
+                return self.send_operation(
+                    Operations::SignalProcess {
+                        pid: process.pid,
+                        name: process.cmd.clone(),
+                        args: process.args.clone(),
+                        signal: Signal::Stop,
+                    },
+                    "Failed to send signal request to process daemon",
+                );
+            }
+        }
+        let error = self.send_operation(
+            Operations::KillProcess {
+                pid: process.pid,
+                process_type: process.process_type.clone(),
+                graceful: request.graceful,
+                name: process.cmd.clone(),
+                args: process.args.clone(),
+            },
+            "Failed to send kill request to process daemon",
+        );
+        //NOTE: daemon handles operations in order so port is checked after kill
+        match request.recheck_port {
+            Some(port) if error.is_none() => self.send_operation(
+                Operations::CheckPort(port),
+                "Failed to send port check request to process daemon",
+            ),
+            _ => error,
+        }
+    }
+
+    fn plan_batch_kill(&mut self) -> KeyAction {
+        self.remember_query();
+        let query = self.search_bar.get_search_text();
+        //NOTE: query like '' or '/' matches every process, that is never what user wants to kill
+        if selects_by_existence_only(query) {
+            return KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                "Type query to select processes to kill",
+            )));
+        }
+        let plan = BatchKill::plan(query, &self.search_results, &self.kill_guard);
+        KeyAction::Event(ComponentEvent::ConfirmBatchKill(Box::new(plan)))
+    }
+
+    //NOTE: queries user acted on are the ones worth recalling later,
+    // history is convenience only, failing to save it should not interrupt user
+    fn remember_query(&mut self) {
+        let query = self.search_bar.get_search_text().to_string();
+        self.search_history.record(&query).ok();
+    }
+
+    fn recall_search(&mut self, older: bool) -> KeyAction {
+        let current = self.search_bar.get_search_text().to_string();
+        let recalled = match older {
+            true => self.search_history.previous(&current),
+            false => self.search_history.next(&current),
+        };
+        match recalled.map(str::to_string) {
+            Some(query) => self.apply_search(&query, None),
+            None => KeyAction::Consumed,
+        }
+    }
+
+    fn apply_search(&mut self, query: &str, saved_search: Option<&str>) -> KeyAction {
+        self.search_bar.set_search_text(query);
+        self.saved_search = saved_search.map(|name| (name.to_string(), query.to_string()));
+        match self.search_for_processess() {
+            Ok(()) => KeyAction::Consumed,
+            Err(notification) => KeyAction::Event(ComponentEvent::ShowNotification(notification)),
         }
-        KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
-            "No process selected",
-        )))
     }
 
     fn enforce_search_by(&mut self, search_by: ProcessRelatedSearch) -> KeyAction {
@@ -167,32 +399,277 @@ impl ProcessesViewComponent {
         }
     }
 
-    fn copy_pid_to_clipboard(&mut self) -> KeyAction {
-        if let Some(prc) = self.get_selected_process() {
-            let clipboard = match CLIPBOARD.as_ref() {
-                Ok(clipboard) => clipboard,
-                Err(err) => {
-                    return KeyAction::Event(ComponentEvent::ShowNotification(
-                        Notification::error(err.clone()),
-                    ));
-                }
-            };
-            let mut clipboard = match clipboard.lock() {
-                Ok(clipboard) => clipboard,
-                Err(_) => {
-                    return KeyAction::Event(ComponentEvent::ShowNotification(
-                        Notification::error("Clipboard is currently unavailable"),
-                    ));
-                }
-            };
-            if let Err(err) = clipboard.set_text(format!("{}", prc.pid)) {
-                return KeyAction::Event(ComponentEvent::ShowNotification(Notification::error(
-                    format!("Failed to copy PID to clipboard: {err}"),
-                )));
+    // port from ':<port>' search or first port of selected process
+    fn port_to_check(&self) -> Option<u16> {
+        if let Some(port) = self.search_bar.get_search_text().strip_prefix(':') {
+            return port.trim().parse().ok();
+        }
+        let ports = self.get_selected_process()?.ports.as_deref()?;
+        let port = ports
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect::<String>();
+        port.parse().ok()
+    }
+
+    fn send_operation(&self, operation: Operations, error_message: &str) -> Option<ComponentEvent> {
+        self.ops_sender
+            .send(operation)
+            .err()
+            .map(|_| ComponentEvent::ShowNotification(Notification::error(error_message)))
+    }
+
+    //NOTE: stopped pik, its shell or init freeze terminal just like killed ones would
+    fn suspend_selected_process(&mut self) -> KeyAction {
+        self.remember_query();
+        let Some(prc) = self.get_selected_process() else {
+            return KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                "No process selected",
+            )));
+        };
+        let request = KillRequest {
+            process: prc.clone(),
+            graceful: true,
+            recheck_port: None,
+            kind: KillKind::Suspend,
+        };
+        match self.request_kill(request) {
+            Some(event) => KeyAction::Event(event),
+            None => KeyAction::Consumed,
+        }
+    }
+
+    fn toggle_mark(&mut self) -> KeyAction {
+        let Some(prc) = self.get_selected_process() else {
+            return KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                "No process selected",
+            )));
+        };
+        //NOTE: start time tells apart new process that reused PID of marked one
+        let position = self
+            .marked
+            .iter()
+            .position(|marked| marked.pid == prc.pid && marked.start_time == prc.start_time);
+        match position {
+            Some(position) => {
+                self.marked.remove(position);
             }
+            None => self.marked.push(prc.clone()),
         }
         KeyAction::Consumed
     }
+
+    // marked processes when there are any, selected one otherwise
+    fn scheduling_targets(&self) -> Vec<Process> {
+        match self.marked.is_empty() {
+            true => self.get_selected_process().cloned().into_iter().collect(),
+            false => self.marked.clone(),
+        }
+    }
+
+    fn open_scheduling(&mut self, kind: SchedulingKind) -> KeyAction {
+        self.remember_query();
+        let processes = self.scheduling_targets();
+        match processes.first() {
+            Some(prc) => KeyAction::Event(ComponentEvent::OpenScheduling {
+                scheduling: Scheduling::read(prc.pid),
+                processes,
+                kind,
+            }),
+            None => KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                "No process selected",
+            ))),
+        }
+    }
+
+    fn change_scheduling(
+        &self,
+        processes: &[Process],
+        change: &SchedulingChange,
+    ) -> Option<ComponentEvent> {
+        let targets = processes
+            .iter()
+            .map(|prc| KilledProcess {
+                pid: prc.pid,
+                name: prc.cmd.clone(),
+                args: prc.args.clone(),
+            })
+            .collect();
+        let operation = match change {
+            SchedulingChange::Nice(nice) => Operations::Renice {
+                targets,
+                nice: *nice,
+            },
+            SchedulingChange::Affinity(cpus) => Operations::SetAffinity {
+                targets,
+                cpus: cpus.clone(),
+            },
+        };
+        self.send_operation(
+            operation,
+            "Failed to send scheduling request to process daemon",
+        )
+    }
+
+    fn resume_process(&self, prc: &SuspendedProcess) -> Option<ComponentEvent> {
+        self.send_operation(
+            Operations::SignalProcess {
+                pid: prc.pid,
+                name: prc.name.clone(),
+                args: prc.args.clone(),
+                signal: Signal::Continue,
+            },
+            "Failed to send signal request to process daemon",
+        )
+    }
+
+    fn process_signaled(
+        &mut self,
+        process: KilledProcess,
+        signal: Signal,
+        start_time: Option<u64>,
+    ) -> Notification {
+        let updated = match signal {
+            Signal::Stop => self.suspended.add(SuspendedProcess {
+                pid: process.pid,
+                name: process.name.clone(),
+                args: process.args.clone(),
+                start_time,
+            }),
+            Signal::Continue => self.suspended.remove(process.pid),
+            _ => Ok(()),
+        };
+        match (updated, signal) {
+            (Err(err), _) => Notification::error(format!("{err:#}")),
+            (Ok(()), Signal::Stop) => {
+                Notification::success(process_result_message("Process suspended", &process))
+            }
+            (Ok(()), Signal::Continue) => {
+                Notification::success(process_result_message("Process resumed", &process))
+            }
+            (Ok(()), _) => Notification::info(process_result_message(
+                &format!("Sent {}", signal_name(signal)),
+                &process,
+            )),
+        }
+    }
+
+    fn privileged_kill_finished(
+        &mut self,
+        kill: &PrivilegedKill,
+        error: Option<&str>,
+    ) -> Option<ComponentEvent> {
+        let notification = match (error, self.search_for_processess()) {
+            (Some(err), _) => Notification::error(format!(
+                "Privileged kill failed: {err} - {} : PID {}",
+                kill.name, kill.pid
+            )),
+            (None, Err(notification)) => notification,
+            (None, Ok(_)) if matches!(kill.signal, Signal::Stop | Signal::Continue) => {
+                //NOTE: start time is unknown here, entry is then not checked for PID reuse
+                let process = KilledProcess {
+                    pid: kill.pid,
+                    name: kill.name.clone(),
+                    args: kill.args.clone(),
+                };
+                self.process_signaled(process, kill.signal, None)
+            }
+            (None, Ok(_)) => Notification::success(format!(
+                "Sent {} with privilege helper - {} : PID {}",
+                signal_name(kill.signal),
+                kill.name,
+                kill.pid
+            )),
+        };
+        Some(ComponentEvent::ShowNotification(notification))
+    }
+
+    fn copy_pid_to_clipboard(&mut self) -> KeyAction {
+        self.remember_query();
+        let copied = self
+            .get_selected_process()
+            .map(|prc| clipboard::copy_text(&prc.pid.to_string()));
+        match copied {
+            Some(Err(err)) => {
+                KeyAction::Event(ComponentEvent::ShowNotification(Notification::error(err)))
+            }
+            _ => KeyAction::Consumed,
+        }
+    }
+
+    fn open_copy_field(&mut self) -> KeyAction {
+        self.remember_query();
+        match self.get_selected_process() {
+            Some(prc) => KeyAction::Event(ComponentEvent::OpenCopyField {
+                process: Box::new(prc.clone()),
+                scheduling: Scheduling::read(prc.pid),
+            }),
+            None => KeyAction::Event(ComponentEvent::ShowNotification(Notification::info(
+                "No process selected",
+            ))),
+        }
+    }
+}
+
+fn batch_kill_notification(
+    killed: &[KilledProcess],
+    still_alive: &[KilledProcess],
+    failed: &[(KilledProcess, KillError)],
+) -> Notification {
+    let total = killed.len() + still_alive.len() + failed.len();
+    if still_alive.is_empty() && failed.is_empty() {
+        return Notification::success(format!("Killed {total} processes"));
+    }
+    let problems = still_alive
+        .iter()
+        .map(|prc| format!("{} : PID {} is still alive", prc.name, prc.pid))
+        .chain(
+            failed
+                .iter()
+                .map(|(prc, err)| format!("{} : PID {} {err}", prc.name, prc.pid)),
+        )
+        .join(", ");
+    Notification::error(format!(
+        "Killed {} of {total} processes, {problems}",
+        killed.len()
+    ))
+}
+
+fn scheduling_notification(
+    change: &SchedulingChange,
+    changed: &[KilledProcess],
+    failed: &[(KilledProcess, String)],
+) -> Notification {
+    match (changed, failed) {
+        ([process], []) => Notification::success(process_result_message(
+            &format!("Changed {change}"),
+            process,
+        )),
+        ([], [(process, err)]) => Notification::error(process_result_message(
+            &format!("Failed to change {change}: {err}"),
+            process,
+        )),
+        (_, []) => {
+            Notification::success(format!("Changed {change} of {} processes", changed.len()))
+        }
+        _ => Notification::error(format!(
+            "Changed {change} of {} of {} processes, {}",
+            changed.len(),
+            changed.len() + failed.len(),
+            failed
+                .iter()
+                .map(|(prc, err)| format!("{} : PID {} {err}", prc.name, prc.pid))
+                .join(", ")
+        )),
+    }
+}
+
+fn signal_action(signal: Signal) -> &'static str {
+    match signal {
+        Signal::Stop => "suspend",
+        Signal::Continue => "resume",
+        _ => "kill",
+    }
 }
 
 fn process_result_message(prefix: &str, process: &KilledProcess) -> String {
@@ -210,21 +687,126 @@ impl Component for ProcessesViewComponent {
         if let Ok(ops_result) = self.results_receiver.try_recv() {
             match ops_result {
                 OperationResult::SearchCompleted(results) => {
-                    self.search_results = results;
-                    self.update_process_table_state();
+                    self.show_results(results);
+                }
+                OperationResult::KillSignalSent { process, signal } => {
+                    return Some(ComponentEvent::ShowNotification(Notification::info(
+                        process_result_message(&format!("Sent {}", signal_name(signal)), &process),
+                    )));
+                }
+                OperationResult::KillEscalated(process) => {
+                    return Some(ComponentEvent::ShowNotification(Notification::info(
+                        process_result_message(
+                            "Process did not exit in time, sent SIGKILL",
+                            &process,
+                        ),
+                    )));
+                }
+                OperationResult::ProcessKilled {
+                    results,
+                    process,
+                    exited_after,
+                } => {
+                    self.show_results(results);
+                    return Some(ComponentEvent::ShowNotification(Notification::success(
+                        process_result_message(
+                            &format!("Process exited after {} ms", exited_after.as_millis()),
+                            &process,
+                        ),
+                    )));
                 }
-                OperationResult::ProcessKilled { results, process } => {
-                    self.search_results = results;
-                    self.update_process_table_state();
+                OperationResult::ProcessRestarted {
+                    results,
+                    process,
+                    new_pid,
+                } => {
+                    self.show_results(results);
                     return Some(ComponentEvent::ShowNotification(Notification::success(
-                        process_result_message("Process killed", &process),
+                        process_result_message(
+                            &format!("Process restarted with PID {new_pid}"),
+                            &process,
+                        ),
+                    )));
+                }
+                OperationResult::ProcessRestartFailed {
+                    results,
+                    process,
+                    error,
+                } => {
+                    self.show_results(results);
+                    return Some(ComponentEvent::ShowNotification(Notification::error(
+                        process_result_message(
+                            &format!("Process killed but failed to restart: {error}"),
+                            &process,
+                        ),
+                    )));
+                }
+                OperationResult::SchedulingChanged {
+                    results,
+                    change,
+                    changed,
+                    failed,
+                } => {
+                    self.show_results(results);
+                    return Some(ComponentEvent::ShowNotification(scheduling_notification(
+                        &change, &changed, &failed,
+                    )));
+                }
+                OperationResult::ProcessStillAlive { process, signal } => {
+                    return Some(ComponentEvent::ShowNotification(Notification::error(
+                        process_result_message(
+                            &format!(
+                                "{} delivered but process is still alive",
+                                signal_name(signal)
+                            ),
+                            &process,
+                        ),
                     )));
                 }
-                OperationResult::ProcessKillFailed(process) => {
+                OperationResult::ProcessKillFailed {
+                    process,
+                    signal,
+                    error,
+                } => {
+                    //NOTE: process is gone, there is nothing to resume anymore
+                    if error == KillError::NoSuchProcess {
+                        self.suspended.remove(process.pid).ok();
+                    }
                     return Some(ComponentEvent::ShowNotification(Notification::error(
-                        process_result_message("Failed to kill process", &process),
+                        process_result_message(
+                            &format!("Failed to {} process: {error}", signal_action(signal)),
+                            &process,
+                        ),
+                    )));
+                }
+                OperationResult::ProcessesKilled {
+                    results,
+                    killed,
+                    still_alive,
+                    failed,
+                } => {
+                    self.show_results(results);
+                    return Some(ComponentEvent::ShowNotification(batch_kill_notification(
+                        &killed,
+                        &still_alive,
+                        &failed,
                     )));
                 }
+                OperationResult::ProcessSignaled {
+                    process,
+                    signal,
+                    start_time,
+                } => {
+                    return Some(ComponentEvent::ShowNotification(
+                        self.process_signaled(process, signal, start_time),
+                    ));
+                }
+                OperationResult::KillPermissionDenied(kill) => {
+                    return Some(ComponentEvent::KillPermissionDenied(kill));
+                }
+                OperationResult::PortChecked(check) => {
+                    return Some(ComponentEvent::PortChecked(check));
+                }
                 OperationResult::Error(err) => {
                     return Some(ComponentEvent::ShowNotification(Notification::error(err)));
                 }
@@ -233,6 +815,51 @@ impl Component for ProcessesViewComponent {
         None
     }
 
+    fn handle_event(&mut self, event: &ComponentEvent) -> Option<ComponentEvent> {
+        match event {
+            ComponentEvent::CheckPort(port) => self.send_operation(
+                Operations::CheckPort(*port),
+                "Failed to send port check request to process daemon",
+            ),
+            ComponentEvent::KillProcess(request) => self.request_kill(*request.clone()),
+            ComponentEvent::KillConfirmed(request) => self.send_kill(request),
+            ComponentEvent::BatchKillConfirmed(plan) => self.send_operation(
+                Operations::KillProcesses {
+                    targets: plan.kill_targets(),
+                    graceful: true,
+                },
+                "Failed to send kill request to process daemon",
+            ),
+            ComponentEvent::PrivilegedKillFinished { kill, error } => {
+                self.privileged_kill_finished(kill, error.as_deref())
+            }
+            ComponentEvent::ResumeProcess(prc) => self.resume_process(prc),
+            ComponentEvent::ChangeScheduling { processes, change } => {
+                self.change_scheduling(processes, change)
+            }
+            ComponentEvent::ChangeLayout(_) => {
+                self.process_details_component.reset_details_scroll_offset();
+                None
+            }
+            ComponentEvent::ApplySearch {
+                query,
+                saved_search,
+            } => match self.apply_search(query, saved_search.as_deref()) {
+                KeyAction::Event(event) => Some(event),
+                _ => None,
+            },
+            ComponentEvent::CopyTemplate(named) => {
+                self.remember_query();
+                let notification = match self.get_selected_process() {
+                    Some(prc) => copy_notification(&named.name, &named.template.render(prc)),
+                    None => Notification::info("No process selected"),
+                };
+                Some(ComponentEvent::ShowNotification(notification))
+            }
+            _ => None,
+        }
+    }
+
     fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
         use KeyCode::*;
         match action {
@@ -260,6 +887,21 @@ impl Component for ProcessesViewComponent {
             AppAction::ForceKillProcess => {
                 return self.kill_selected_process(false);
             }
+            AppAction::KillAllMatching => {
+                return self.plan_batch_kill();
+            }
+            AppAction::RestartProcess => {
+                return self.restart_selected_process();
+            }
+            AppAction::ReniceProcess => {
+                return self.open_scheduling(SchedulingKind::Nice);
+            }
+            AppAction::SetProcessAffinity => {
+                return self.open_scheduling(SchedulingKind::Affinity);
+            }
+            AppAction::ToggleMark => {
+                return self.toggle_mark();
+            }
             AppAction::RefreshProcessList => {
                 return match self.search_for_processess() {
                     Ok(()) => KeyAction::Consumed,
@@ -271,6 +913,20 @@ impl Component for ProcessesViewComponent {
             AppAction::CopyProcessPid => {
                 return self.copy_pid_to_clipboard();
             }
+            AppAction::CopyProcessField => {
+                return self.open_copy_field();
+            }
+            AppAction::CheckPort => {
+                return KeyAction::Event(ComponentEvent::OpenPortCheck(self.port_to_check()));
+            }
+            AppAction::SuspendProcess => {
+                return self.suspend_selected_process();
+            }
+            AppAction::ShowSuspended => {
+                return KeyAction::Event(ComponentEvent::OpenSuspended(
+                    self.suspended.entries().to_vec(),
+                ));
+            }
             AppAction::SelectProcessParent => {
                 return self.enforce_search_by(ProcessRelatedSearch::Parent);
             }
@@ -280,6 +936,22 @@ impl Component for ProcessesViewComponent {
             AppAction::SelectProcessSiblings => {
                 return self.enforce_search_by(ProcessRelatedSearch::Siblings);
             }
+            AppAction::PreviousSearch => {
+                return self.recall_search(true);
+            }
+            AppAction::NextSearch => {
+                return self.recall_search(false);
+            }
+            AppAction::ShowSearchHistory => {
+                return KeyAction::Event(ComponentEvent::OpenSearchHistory(
+                    self.search_history
+                        .entries()
+                        .iter()
+                        .rev()
+                        .cloned()
+                        .collect(),
+                ));
+            }
             AppAction::ScrollProcessDetailsUp => {
                 self.process_details_component.process_details_up();
             }
@@ -363,6 +1035,9 @@ impl Component for ProcessesViewComponent {
                 };
             }
             AppAction::Unmapped => {
+                if key.code == Enter {
+                    self.remember_query();
+                }
                 if let Char(c) = key.code {
                     self.search_bar.insert_char(c);
                     return match self.search_for_processess() {
@@ -380,13 +1055,63 @@ impl Component for ProcessesViewComponent {
         KeyAction::Consumed
     }
 
+    fn handle_mouse(&mut self, mouse: MouseEvent, layout: &LayoutRects) -> KeyAction {
+        let position = Position::new(mouse.column, mouse.row);
+        let over_table = layout.process_table.contains(position);
+        let over_details = layout.process_details.contains(position);
+        match mouse.kind {
+            MouseEventKind::ScrollDown if over_table => self.scroll_rows(true),
+            MouseEventKind::ScrollUp if over_table => self.scroll_rows(false),
+            MouseEventKind::ScrollDown if over_details => {
+                self.process_details_component.process_details_down();
+            }
+            MouseEventKind::ScrollUp if over_details => {
+                self.process_details_component.process_details_up();
+            }
+            MouseEventKind::Down(MouseButton::Left) if over_table => {
+                match self
+                    .process_table_component
+                    .clicked(layout.process_table, position)
+                {
+                    Some(TableClick::Header(column)) => return self.sort_by_column(column),
+                    Some(TableClick::Row(index)) => self.select_row(index),
+                    None => (),
+                }
+            }
+            _ => return KeyAction::Unhandled,
+        }
+        KeyAction::Consumed
+    }
+
+    fn handle_terminal_event(&mut self, event: &Event) -> KeyAction {
+        match event {
+            Event::Paste(text) => self.search_bar.insert_text(text),
+            Event::FocusGained if self.refresh_on_focus => (),
+            //NOTE: details area changes with terminal, scroll offset may point past content then
+            Event::Resize(..) => {
+                self.process_details_component.reset_details_scroll_offset();
+                return KeyAction::Consumed;
+            }
+            _ => return KeyAction::Unhandled,
+        }
+        match self.search_for_processess() {
+            Ok(()) => KeyAction::Consumed,
+            Err(notification) => KeyAction::Event(ComponentEvent::ShowNotification(notification)),
+        }
+    }
+
     fn render(&mut self, frame: &mut Frame, layout: &crate::tui::LayoutRects) {
         let selected_index = self.process_table_component.get_selected_process_index();
         let selected_process = self.search_results.nth(selected_index);
 
         self.search_bar.render(frame, layout);
-        self.process_table_component
-            .render(frame, layout, &self.search_results);
+        self.process_table_component.render(
+            frame,
+            layout,
+            &self.search_results,
+            &self.suspended,
+            &self.marked,
+        );
         self.process_details_component
             .render(frame, layout, selected_process);
     }
@@ -394,15 +1119,119 @@ impl Component for ProcessesViewComponent {
 
 impl Drop for ProcessesViewComponent {
     fn drop(&mut self) {
+        //NOTE: query pik is closed with was likely found useful
+        self.remember_query();
         self.ops_sender.send(Operations::Shutdown).ok();
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::processes::KilledProcess;
+    use std::sync::mpsc;
+
+    use crate::config::default_config;
+    use crate::processes::{
+        KillError, KilledProcess, MatchData, MatchType, MatchedBy, ProtectedAction, ResultItem,
+    };
+    use crate::tui::components::NotificationSeverity;
+
+    use super::*;
+
+    #[test]
+    fn should_refuse_to_suspend_pik_itself() {
+        let guard = KillGuard {
+            protected_action: ProtectedAction::Refuse,
+            ..KillGuard::default()
+        };
+        let (mut view, ops_receiver) = view_with(Process::test(std::process::id()), guard);
+
+        let action = view.handle_input(
+            KeyEvent::from(KeyCode::Char('z')),
+            AppAction::SuspendProcess,
+        );
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ShowNotification(notification))
+                if notification.message.starts_with("Refusing to suspend node")
+        ));
+        assert!(ops_receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn should_confirm_suspending_protected_process() {
+        let (mut view, ops_receiver) = view_with(Process::test(1), KillGuard::default());
+
+        let action = view.handle_input(
+            KeyEvent::from(KeyCode::Char('z')),
+            AppAction::SuspendProcess,
+        );
 
-    use super::process_result_message;
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ConfirmKill { request, protection: Some(_) })
+                if request.kind == KillKind::Suspend
+        ));
+        assert!(ops_receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn should_change_scheduling_of_marked_processes_instead_of_selected() {
+        let (mut view, ops_receiver) = view_with(Process::test(4242), KillGuard::default());
+        let open_scheduling = |view: &mut ProcessesViewComponent| match view
+            .handle_input(KeyEvent::from(KeyCode::Char('n')), AppAction::ReniceProcess)
+        {
+            KeyAction::Event(ComponentEvent::OpenScheduling { processes, .. }) => {
+                processes.iter().map(|prc| prc.pid).collect::<Vec<_>>()
+            }
+            _ => panic!("Expected scheduling to be opened"),
+        };
+
+        let unmarked_targets = open_scheduling(&mut view);
+        view.marked.push(Process::test(77));
+        view.handle_input(KeyEvent::from(KeyCode::Char('t')), AppAction::ToggleMark);
+        let marked_targets = open_scheduling(&mut view);
+        view.handle_event(&ComponentEvent::ChangeScheduling {
+            processes: view.marked.clone(),
+            change: SchedulingChange::Nice(10),
+        });
+        view.handle_input(KeyEvent::from(KeyCode::Char('t')), AppAction::ToggleMark);
+
+        assert_eq!(unmarked_targets, vec![4242]);
+        assert_eq!(marked_targets, vec![77, 4242]);
+        assert!(matches!(
+            ops_receiver.try_recv(),
+            Ok(Operations::Renice { targets, nice: 10 })
+                if targets.iter().map(|prc| prc.pid).collect::<Vec<_>>() == vec![77, 4242]
+        ));
+        assert_eq!(view.marked, vec![Process::test(77)]);
+    }
+
+    #[test]
+    fn builds_scheduling_change_summary() {
+        let process = |pid: u32| KilledProcess {
+            pid,
+            name: "node".to_string(),
+            args: String::new(),
+        };
+        let change = SchedulingChange::Nice(10);
+
+        let single = scheduling_notification(&change, &[process(1)], &[]);
+        let all = scheduling_notification(&change, &[process(1), process(2)], &[]);
+        let partial = scheduling_notification(
+            &change,
+            &[process(1)],
+            &[(process(2), "permission denied".to_string())],
+        );
+
+        assert_eq!(single.message, "Changed nice 10 - node : PID 1");
+        assert_eq!(all.message, "Changed nice 10 of 2 processes");
+        assert_eq!(partial.severity, NotificationSeverity::Error);
+        assert_eq!(
+            partial.message,
+            "Changed nice 10 of 1 of 2 processes, node : PID 2 permission denied"
+        );
+    }
 
     #[test]
     fn builds_success_message_with_name_and_pid() {
@@ -411,12 +1240,33 @@ mod tests {
             &KilledProcess {
                 pid: 4242,
                 name: "pik".to_string(),
+                args: String::new(),
             },
         );
 
         assert_eq!(message, "Process killed - pik : PID 4242");
     }
 
+    #[test]
+    fn builds_batch_kill_summary_with_failures() {
+        let process = |pid: u32| KilledProcess {
+            pid,
+            name: "node".to_string(),
+            args: String::new(),
+        };
+
+        let notification = batch_kill_notification(
+            &[process(1), process(2)],
+            &[process(3)],
+            &[(process(4), KillError::PermissionDenied)],
+        );
+
+        assert_eq!(
+            notification.message,
+            "Killed 2 of 4 processes, node : PID 3 is still alive, node : PID 4 permission denied, process has to be killed by its owner or root"
+        );
+    }
+
     #[test]
     fn builds_failure_message_with_name_and_pid() {
         let message = process_result_message(
@@ -424,6 +1274,7 @@ mod tests {
             &KilledProcess {
                 pid: 4242,
                 name: "pik".to_string(),
+                args: String::new(),
             },
         );
 
@@ -437,9 +1288,45 @@ mod tests {
             &KilledProcess {
                 pid: 4242,
                 name: String::new(),
+                args: String::new(),
             },
         );
 
         assert_eq!(message, "Process killed - unknown : PID 4242");
     }
+
+    fn view_with(
+        process: Process,
+        kill_guard: KillGuard,
+    ) -> (ProcessesViewComponent, Receiver<Operations>) {
+        let ui_config = default_config().unwrap().ui;
+        let (ops_sender, ops_receiver) = mpsc::channel();
+        let (_, results_receiver) = mpsc::channel();
+        let mut view = ProcessesViewComponent {
+            ops_sender,
+            results_receiver,
+            search_results: ProcessSearchResults {
+                items: vec![ResultItem::new(
+                    MatchData::new(MatchedBy::Cmd, MatchType::Exact),
+                    process,
+                )],
+            },
+            process_table_component: ProcessTableComponent::new(
+                ui_config.icons.get_icons(),
+                ui_config.process_table.clone(),
+            ),
+            process_details_component: ProcessDetailsComponent::new(
+                ui_config.process_details.clone(),
+            ),
+            search_bar: SearchBarComponent::new(String::new(), &ui_config.search_bar, ">"),
+            kill_guard,
+            suspended: SuspendedProcesses::load(None),
+            marked: vec![],
+            search_history: SearchHistory::load(None),
+            refresh_on_focus: false,
+            saved_search: None,
+        };
+        view.update_process_table_state();
+        (view, ops_receiver)
+    }
 }
@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    text::{Line, Span},
+    widgets::{Clear, ListItem, ListState},
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyBinding, KeyMappings, resolve_named},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::SavedSearch,
+    tui::LayoutRects,
+};
+
+use super::{Component, ComponentEvent, KeyAction, popup_action, popup_area, popup_list};
+
+pub struct SavedSearchesPopupComponent {
+    is_open: bool,
+    searches: Vec<SavedSearch>,
+    key_mappings: BTreeMap<String, Vec<KeyBinding>>,
+    list_state: ListState,
+    theme: PopupsTheme,
+    close_bindings: String,
+}
+
+impl SavedSearchesPopupComponent {
+    pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings, searches: &[SavedSearch]) -> Self {
+        Self {
+            is_open: false,
+            searches: searches.to_vec(),
+            key_mappings: key_mappings.searches.clone(),
+            list_state: ListState::default(),
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    fn open(&mut self) {
+        self.is_open = true;
+        self.list_state
+            .select((!self.searches.is_empty()).then_some(0));
+    }
+
+    fn run(&mut self, name: &str) -> KeyAction {
+        match self.searches.iter().find(|search| search.name == name) {
+            Some(search) => {
+                self.is_open = false;
+                KeyAction::Event(ComponentEvent::ApplySearch {
+                    query: search.query.clone(),
+                    saved_search: Some(search.name.clone()),
+                })
+            }
+            None => KeyAction::Consumed,
+        }
+    }
+
+    fn run_selected(&mut self) -> KeyAction {
+        let selected = self
+            .list_state
+            .selected()
+            .and_then(|index| self.searches.get(index))
+            .map(|search| search.name.clone());
+        match selected {
+            Some(name) => self.run(&name),
+            None => KeyAction::Consumed,
+        }
+    }
+
+    fn items(&self) -> Vec<ListItem<'static>> {
+        if self.searches.is_empty() {
+            return vec![ListItem::new(Line::styled(
+                "No searches saved in [searches] section of config",
+                self.theme.secondary,
+            ))];
+        }
+        let name_width = self
+            .searches
+            .iter()
+            .map(|search| search.name.chars().count())
+            .max()
+            .unwrap_or_default();
+        self.searches
+            .iter()
+            .map(|search| {
+                let keys = self
+                    .key_mappings
+                    .get(&search.name)
+                    .map(|bindings| format!(" ({})", bindings.iter().join(", ")))
+                    .unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<name_width$} ", search.name), self.theme.primary),
+                    Span::styled(format!("{}{keys}", search.query), self.theme.secondary),
+                ]))
+            })
+            .collect()
+    }
+}
+
+impl Component for SavedSearchesPopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        if matches!(action, AppAction::RunSearch) {
+            let name = resolve_named(&self.key_mappings, key).map(str::to_string);
+            return match name {
+                Some(name) => self.run(&name),
+                None => KeyAction::Unhandled,
+            };
+        }
+        if matches!(action, AppAction::ShowSavedSearches) {
+            if self.is_open {
+                self.is_open = false;
+            } else {
+                self.open();
+            }
+            return KeyAction::Consumed;
+        }
+        if !self.is_open {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close => {
+                self.is_open = false;
+            }
+            AppAction::NextItem => {
+                self.list_state.select_next();
+            }
+            AppAction::PreviousItem => {
+                self.list_state.select_previous();
+            }
+            AppAction::GoToFirstItem => {
+                self.list_state.select_first();
+            }
+            AppAction::GoToLastItem => {
+                self.list_state.select_last();
+            }
+            AppAction::Unmapped if key.code == KeyCode::Enter => {
+                return self.run_selected();
+            }
+            _ => (),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn handle_terminal_event(&mut self, _: &Event) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        if !self.is_open {
+            return;
+        }
+        let area = popup_area(frame.area(), 60, 50);
+        let list = popup_list(
+            self.items(),
+            " Saved searches ".to_string(),
+            format!(" enter search | {} close ", self.close_bindings),
+            &self.theme,
+        );
+
+        frame.render_widget(Clear, area); //this clears out the background
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::config::parse_config;
+
+    use super::*;
+
+    #[test]
+    fn should_apply_selected_search() {
+        let mut popup = create_popup();
+
+        popup.handle_input(key(KeyCode::Char('m')), AppAction::ShowSavedSearches);
+        popup.handle_input(key(KeyCode::Down), AppAction::NextItem);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ApplySearch { query, saved_search })
+                if query == ":3000|:8080" && saved_search.as_deref() == Some("dev-servers")
+        ));
+        assert!(!popup.is_open);
+    }
+
+    #[test]
+    fn should_run_search_bound_to_key() {
+        let mut popup = create_popup();
+
+        let action = popup.handle_input(
+            KeyEvent::new(KeyCode::Char('1'), KeyModifiers::ALT),
+            AppAction::RunSearch,
+        );
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ApplySearch { query, saved_search })
+                if query == "java" && saved_search.as_deref() == Some("all-java")
+        ));
+    }
+
+    #[test]
+    fn should_not_handle_input_when_closed() {
+        let mut popup = create_popup();
+
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    fn create_popup() -> SavedSearchesPopupComponent {
+        let config = parse_config(
+            r#"
+            [searches]
+            all-java = "java"
+            dev-servers = ":3000|:8080"
+
+            [key_mappings.searches]
+            all-java = ["alt+1"]
+            "#,
+        )
+        .unwrap();
+        let searches = config
+            .searches
+            .iter()
+            .map(|(name, search)| SavedSearch {
+                name: name.clone(),
+                query: search.query().to_string(),
+                ignore: None,
+            })
+            .collect::<Vec<_>>();
+        SavedSearchesPopupComponent::new(&config.ui, &config.key_mappings, &searches)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+}
@@ -0,0 +1,262 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    layout::{Constraint, Layout},
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph},
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::{Process, Scheduling, SchedulingKind},
+    tui::LayoutRects,
+};
+
+use super::{Component, ComponentEvent, KeyAction, Notification, popup_action, popup_area};
+
+// longest valid input is CPU list, it is rarely longer than this
+const MAX_INPUT_LEN: usize = 64;
+
+pub struct SchedulingPopupComponent {
+    target: Option<(Vec<Process>, SchedulingKind)>,
+    input: String,
+    theme: PopupsTheme,
+    close_bindings: String,
+}
+
+impl SchedulingPopupComponent {
+    pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings) -> Self {
+        Self {
+            target: None,
+            input: String::new(),
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    //NOTE: input starts with current value so it can be adjusted
+    fn open(&mut self, processes: &[Process], scheduling: &Scheduling, kind: SchedulingKind) {
+        self.input = match kind {
+            SchedulingKind::Nice => scheduling.nice.map(|n| n.to_string()),
+            SchedulingKind::Affinity => scheduling.cpu_affinity.clone(),
+        }
+        .unwrap_or_default();
+        self.target = Some((processes.to_vec(), kind));
+    }
+
+    fn apply(&mut self) -> KeyAction {
+        let Some((_, kind)) = &self.target else {
+            return KeyAction::Consumed;
+        };
+        match kind.parse(&self.input) {
+            Ok(change) => {
+                let processes = self.target.take().map(|(processes, _)| processes);
+                KeyAction::Event(ComponentEvent::ChangeScheduling {
+                    processes: processes.unwrap_or_default(),
+                    change,
+                })
+            }
+            Err(err) => {
+                KeyAction::Event(ComponentEvent::ShowNotification(Notification::error(err)))
+            }
+        }
+    }
+}
+
+impl Component for SchedulingPopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        let Some((_, kind)) = &self.target else {
+            return KeyAction::Unhandled;
+        };
+        match action {
+            AppAction::Close => {
+                self.target = None;
+            }
+            AppAction::DeleteChar => {
+                self.input.pop();
+            }
+            AppAction::Unmapped => match key.code {
+                KeyCode::Enter => return self.apply(),
+                KeyCode::Char(c) if kind.accepts(c) && self.input.len() < MAX_INPUT_LEN => {
+                    self.input.push(c);
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_event(&mut self, event: &ComponentEvent) -> Option<ComponentEvent> {
+        if let ComponentEvent::OpenScheduling {
+            processes,
+            scheduling,
+            kind,
+        } = event
+        {
+            self.open(processes, scheduling, *kind);
+        }
+        None
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.target.is_some())
+    }
+
+    fn handle_terminal_event(&mut self, event: &Event) -> KeyAction {
+        if let (Event::Paste(text), Some((_, kind))) = (event, &self.target) {
+            let room = MAX_INPUT_LEN.saturating_sub(self.input.len());
+            let pasted: String = text
+                .chars()
+                .filter(|c| kind.accepts(*c))
+                .take(room)
+                .collect();
+            self.input.push_str(&pasted);
+        }
+        popup_action(self.target.is_some())
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        let Some((processes, kind)) = &self.target else {
+            return;
+        };
+        let area = popup_area(frame.area(), 50, 30);
+        let title = match processes.as_slice() {
+            [process] => format!(
+                " {} of {} : PID {} ",
+                kind.title(),
+                process.cmd,
+                process.pid
+            ),
+            _ => format!(" {} of {} marked processes ", kind.title(), processes.len()),
+        };
+        let block = Block::bordered()
+            .title_top(Line::from(title).centered())
+            .title_bottom(
+                Line::from(format!(" enter apply | {} cancel ", self.close_bindings)).centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .border_style(self.theme.border.style)
+            .border_type(self.theme.border._type);
+        let inner = block.inner(area);
+        let [input_area, hint_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).areas(inner);
+
+        frame.render_widget(Clear, area); //this clears out the background
+        frame.render_widget(block, area);
+        let prompt = format!("{}: ", kind.title());
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(prompt.as_str(), self.theme.primary),
+                Span::styled(self.input.as_str(), self.theme.secondary),
+            ])),
+            input_area,
+        );
+        frame.render_widget(
+            Paragraph::new(Line::styled(kind.hint(), self.theme.secondary)),
+            hint_area,
+        );
+        let position_x = input_area.x + prompt.len() as u16 + self.input.len() as u16;
+        frame.set_cursor_position((position_x, input_area.y));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::config::default_config;
+    use crate::processes::SchedulingChange;
+
+    use super::*;
+
+    #[test]
+    fn should_start_with_current_value_and_apply_change() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenScheduling {
+            processes: vec![Process::test(4242)],
+            scheduling: some_scheduling(),
+            kind: SchedulingKind::Nice,
+        });
+        assert_eq!(popup.input, "0");
+
+        popup.handle_input(key(KeyCode::Backspace), AppAction::DeleteChar);
+        popup.handle_input(key(KeyCode::Char('x')), AppAction::Unmapped);
+        popup.handle_input(key(KeyCode::Char('1')), AppAction::Unmapped);
+        popup.handle_input(key(KeyCode::Char('0')), AppAction::Unmapped);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ChangeScheduling {
+                processes,
+                change: SchedulingChange::Nice(10),
+            }) if processes == vec![Process::test(4242)]
+        ));
+        assert!(popup.target.is_none());
+    }
+
+    #[test]
+    fn should_keep_popup_open_when_input_is_invalid() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenScheduling {
+            processes: vec![Process::test(4242)],
+            scheduling: some_scheduling(),
+            kind: SchedulingKind::Affinity,
+        });
+
+        popup.handle_input(key(KeyCode::Char('-')), AppAction::Unmapped);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ShowNotification(_))
+        ));
+        assert!(popup.target.is_some());
+    }
+
+    #[test]
+    fn should_keep_only_accepted_chars_from_pasted_text() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenScheduling {
+            processes: vec![Process::test(4242)],
+            scheduling: some_scheduling(),
+            kind: SchedulingKind::Affinity,
+        });
+
+        let action = popup.handle_terminal_event(&Event::Paste(",6 cpus\n".to_string()));
+
+        assert!(matches!(action, KeyAction::Consumed));
+        assert_eq!(popup.input, "0-3,6");
+    }
+
+    #[test]
+    fn should_not_handle_input_when_closed() {
+        let mut popup = create_popup();
+
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    fn create_popup() -> SchedulingPopupComponent {
+        let config = default_config().unwrap();
+        SchedulingPopupComponent::new(&config.ui, &config.key_mappings)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn some_scheduling() -> Scheduling {
+        Scheduling {
+            nice: Some(0),
+            cpu_affinity: Some("0-3".to_string()),
+        }
+    }
+}
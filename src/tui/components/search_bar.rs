@@ -1,3 +1,4 @@
+use itertools::Itertools;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout},
@@ -56,6 +57,18 @@ impl SearchBarComponent {
         self.search_area.handle(InputRequest::InsertChar(c));
     }
 
+    //NOTE: pasted text often ends with new line or spans more lines, query is single line
+    pub fn insert_text(&mut self, text: &str) {
+        let text = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .join(" ");
+        for c in text.chars() {
+            self.insert_char(c);
+        }
+    }
+
     pub fn delete_char(&mut self) {
         self.search_area.handle(InputRequest::DeletePrevChar);
     }
@@ -99,3 +112,19 @@ impl SearchBarComponent {
         f.render_widget(input, rects[1]);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_insert_pasted_text_as_single_line_at_cursor() {
+        let mut search_bar =
+            SearchBarComponent::new("node".to_string(), &SearchBarTheme::default(), ">");
+        search_bar.move_cursor(CursorMove::Head);
+
+        search_bar.insert_text("  -port\r\n3000\n");
+
+        assert_eq!(search_bar.get_search_text(), "-port 3000node");
+    }
+}
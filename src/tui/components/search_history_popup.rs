@@ -0,0 +1,214 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    text::Line,
+    widgets::ListItem,
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::MatchType,
+    tui::{LayoutRects, highlight::highlight_text},
+};
+
+use super::{
+    Component, ComponentEvent, FuzzyList, KeyAction, popup_action, popup_area, popup_list,
+};
+
+pub struct SearchHistoryPopupComponent {
+    is_open: bool,
+    // newest query first
+    entries: Vec<String>,
+    list: FuzzyList,
+    theme: PopupsTheme,
+    close_bindings: String,
+}
+
+impl SearchHistoryPopupComponent {
+    pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings) -> Self {
+        Self {
+            is_open: false,
+            entries: vec![],
+            list: FuzzyList::new(),
+            theme: ui_config.popups.clone(),
+            close_bindings: key_mappings.get_joined(AppAction::Close, "/"),
+        }
+    }
+
+    fn open(&mut self, entries: &[String]) {
+        self.is_open = true;
+        self.entries = entries.to_vec();
+        self.list.reset(self.entries.iter().map(String::as_str));
+    }
+
+    fn apply(&mut self) -> KeyAction {
+        match self.list.selected() {
+            Some(entry) => {
+                self.is_open = false;
+                KeyAction::Event(ComponentEvent::ApplySearch {
+                    query: self.entries[entry].clone(),
+                    saved_search: None,
+                })
+            }
+            None => KeyAction::Consumed,
+        }
+    }
+}
+
+impl Component for SearchHistoryPopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        if !self.is_open {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close | AppAction::ShowSearchHistory => {
+                self.is_open = false;
+            }
+            AppAction::Unmapped if key.code == KeyCode::Enter => return self.apply(),
+            _ => self
+                .list
+                .handle_input(key, action, self.entries.iter().map(String::as_str)),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_event(&mut self, event: &ComponentEvent) -> Option<ComponentEvent> {
+        if let ComponentEvent::OpenSearchHistory(entries) = event {
+            self.open(entries);
+        }
+        None
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn handle_terminal_event(&mut self, event: &Event) -> KeyAction {
+        if let (Event::Paste(text), true) = (event, self.is_open) {
+            self.list
+                .paste(text, self.entries.iter().map(String::as_str));
+        }
+        popup_action(self.is_open)
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        if !self.is_open {
+            return;
+        }
+        let area = popup_area(frame.area(), 50, 60);
+        let footer = format!(
+            " type to filter | enter search | {} close ",
+            self.close_bindings
+        );
+        //NOTE: borders, padding and highlight symbol take some of the width
+        let max_len = area.width.saturating_sub(6) as usize;
+        let list = popup_list(
+            items(&self.entries, self.list.matches(), &self.theme, max_len),
+            self.list.title("Search history"),
+            footer,
+            &self.theme,
+        );
+        self.list.render(frame, area, list);
+    }
+}
+
+//NOTE: items borrow only entries, list state is borrowed mutably while rendering
+fn items<'a>(
+    entries: &'a [String],
+    matches: &[(usize, MatchType)],
+    theme: &PopupsTheme,
+    max_len: usize,
+) -> Vec<ListItem<'a>> {
+    if matches.is_empty() {
+        let message = match entries.is_empty() {
+            true => "No queries in history yet",
+            false => "No query matches filter",
+        };
+        return vec![ListItem::new(Line::styled(message, theme.secondary))];
+    }
+    matches
+        .iter()
+        .map(|(entry, match_type)| {
+            ListItem::new(highlight_text(
+                &entries[*entry],
+                match_type,
+                theme.primary,
+                theme.secondary,
+                max_len,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::config::default_config;
+
+    use super::*;
+
+    #[test]
+    fn should_filter_entries_and_apply_best_match() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenSearchHistory(
+            [":8080", "-gradle", "/node_modules"]
+                .map(String::from)
+                .to_vec(),
+        ));
+        assert_eq!(popup.list.matches().len(), 3);
+
+        popup.handle_input(key(KeyCode::Char('g')), AppAction::Unmapped);
+        popup.handle_input(key(KeyCode::Char('r')), AppAction::Unmapped);
+        assert_eq!(popup.list.matches().len(), 1);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ApplySearch { query, .. }) if query == "-gradle"
+        ));
+        assert!(!popup.is_open);
+    }
+
+    #[test]
+    fn should_keep_newest_first_without_filter() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenSearchHistory(
+            [":8080", "-gradle"].map(String::from).to_vec(),
+        ));
+
+        popup.handle_input(key(KeyCode::Char('x')), AppAction::Unmapped);
+        assert!(popup.list.matches().is_empty());
+        popup.handle_input(key(KeyCode::Backspace), AppAction::DeleteChar);
+        popup.handle_input(key(KeyCode::Down), AppAction::NextItem);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ApplySearch { query, .. }) if query == "-gradle"
+        ));
+    }
+
+    #[test]
+    fn should_not_handle_input_when_closed() {
+        let mut popup = create_popup();
+
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    fn create_popup() -> SearchHistoryPopupComponent {
+        let config = default_config().unwrap();
+        SearchHistoryPopupComponent::new(&config.ui, &config.key_mappings)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+}
@@ -0,0 +1,212 @@
+use ratatui::{
+    Frame,
+    crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent},
+    text::{Line, Span},
+    widgets::{Block, Clear, HighlightSpacing, List, ListItem, ListState, Padding},
+};
+
+use crate::{
+    config::{
+        keymappings::{AppAction, KeyMappings},
+        ui::{PopupsTheme, UIConfig},
+    },
+    processes::SuspendedProcess,
+    tui::LayoutRects,
+};
+
+use super::{Component, ComponentEvent, KeyAction, popup_action, popup_area};
+
+pub struct SuspendedPopupComponent {
+    is_open: bool,
+    suspended: Vec<SuspendedProcess>,
+    list_state: ListState,
+    theme: PopupsTheme,
+    help_text: String,
+}
+
+impl SuspendedPopupComponent {
+    pub fn new(ui_config: &UIConfig, key_mappings: &KeyMappings) -> Self {
+        let close = key_mappings.get_joined(AppAction::Close, "/");
+        Self {
+            is_open: false,
+            suspended: vec![],
+            list_state: ListState::default(),
+            theme: ui_config.popups.clone(),
+            help_text: format!(" enter resume | {close} close "),
+        }
+    }
+
+    fn open(&mut self, suspended: &[SuspendedProcess]) {
+        self.is_open = true;
+        self.suspended = suspended.to_vec();
+        self.list_state
+            .select((!self.suspended.is_empty()).then_some(0));
+    }
+
+    //NOTE: entry is dropped right away, failed resume is reported by notification
+    fn resume_selected(&mut self) -> KeyAction {
+        let Some(index) = self
+            .list_state
+            .selected()
+            .filter(|i| *i < self.suspended.len())
+        else {
+            return KeyAction::Consumed;
+        };
+        let prc = self.suspended.remove(index);
+        if self.suspended.is_empty() {
+            self.list_state.select(None);
+        } else {
+            self.list_state
+                .select(Some(index.min(self.suspended.len() - 1)));
+        }
+        KeyAction::Event(ComponentEvent::ResumeProcess(prc))
+    }
+
+    fn items(&self) -> Vec<ListItem<'static>> {
+        if self.suspended.is_empty() {
+            return vec![ListItem::new(Line::styled(
+                "No processes suspended by pik",
+                self.theme.secondary,
+            ))];
+        }
+        self.suspended
+            .iter()
+            .map(|prc| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:>7} ", prc.pid), self.theme.primary),
+                    Span::styled(prc.name.clone(), self.theme.secondary),
+                ]))
+            })
+            .collect()
+    }
+}
+
+impl Component for SuspendedPopupComponent {
+    fn handle_input(&mut self, key: KeyEvent, action: AppAction) -> KeyAction {
+        if !self.is_open {
+            return KeyAction::Unhandled;
+        }
+        match action {
+            AppAction::Close | AppAction::ShowSuspended => {
+                self.is_open = false;
+            }
+            AppAction::NextItem => {
+                self.list_state.select_next();
+            }
+            AppAction::PreviousItem => {
+                self.list_state.select_previous();
+            }
+            AppAction::Unmapped if key.code == KeyCode::Enter => {
+                return self.resume_selected();
+            }
+            _ => (),
+        }
+
+        //consume all keys if popup is open
+        KeyAction::Consumed
+    }
+
+    fn handle_event(&mut self, event: &ComponentEvent) -> Option<ComponentEvent> {
+        if let ComponentEvent::OpenSuspended(suspended) = event {
+            self.open(suspended);
+        }
+        None
+    }
+
+    fn handle_mouse(&mut self, _: MouseEvent, _: &LayoutRects) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn handle_terminal_event(&mut self, _: &Event) -> KeyAction {
+        popup_action(self.is_open)
+    }
+
+    fn render(&mut self, frame: &mut Frame, _layout: &LayoutRects) {
+        if !self.is_open {
+            return;
+        }
+        let area = popup_area(frame.area(), 50, 40);
+        let block = Block::bordered()
+            .title_top(Line::from(" Suspended by pik ").centered())
+            .title_bottom(Line::from(self.help_text.as_str()).centered())
+            .padding(Padding::horizontal(1))
+            .border_style(self.theme.border.style)
+            .border_type(self.theme.border._type);
+
+        frame.render_widget(Clear, area); //this clears out the background
+        let list = List::new(self.items())
+            .block(block)
+            .highlight_style(self.theme.selected_row)
+            .highlight_spacing(HighlightSpacing::Always);
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use crate::config::default_config;
+
+    use super::*;
+
+    #[test]
+    fn should_resume_selected_process() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenSuspended(vec![
+            some_suspended(42, "node"),
+            some_suspended(7, "java"),
+        ]));
+
+        popup.handle_input(key(KeyCode::Down), AppAction::NextItem);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(
+            action,
+            KeyAction::Event(ComponentEvent::ResumeProcess(prc)) if prc.pid == 7
+        ));
+        assert_eq!(popup.suspended, vec![some_suspended(42, "node")]);
+        assert_eq!(popup.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn should_ignore_enter_when_nothing_is_suspended() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenSuspended(vec![]));
+
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Consumed));
+    }
+
+    #[test]
+    fn should_not_handle_input_when_closed() {
+        let mut popup = create_popup();
+        popup.handle_event(&ComponentEvent::OpenSuspended(vec![some_suspended(
+            42, "node",
+        )]));
+
+        popup.handle_input(key(KeyCode::Esc), AppAction::Close);
+        let action = popup.handle_input(key(KeyCode::Enter), AppAction::Unmapped);
+
+        assert!(matches!(action, KeyAction::Unhandled));
+    }
+
+    fn create_popup() -> SuspendedPopupComponent {
+        let config = default_config().unwrap();
+        SuspendedPopupComponent::new(&config.ui, &config.key_mappings)
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn some_suspended(pid: u32, name: &str) -> SuspendedProcess {
+        SuspendedProcess {
+            pid,
+            name: name.to_string(),
+            args: String::new(),
+            start_time: None,
+        }
+    }
+}
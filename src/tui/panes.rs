@@ -0,0 +1,146 @@
+use crate::config::ui::{LayoutKind, UIConfig};
+
+const MIN_DETAILS_HEIGHT: u16 = 3;
+const MAX_DETAILS_HEIGHT: u16 = 40;
+const MIN_DETAILS_WIDTH: u16 = 20;
+const MAX_DETAILS_WIDTH: u16 = 80;
+// percent of width details pane changes by
+const DETAILS_WIDTH_STEP: u16 = 5;
+
+/// Change of panes requested by user while pik runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutChange {
+    Toggle,
+    GrowDetails,
+    ShrinkDetails,
+}
+
+/// Arrangement of process table and details, it starts from config and is changed at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanesLayout {
+    pub kind: LayoutKind,
+    // rows, used by vertical layout
+    pub details_height: u16,
+    // percent of width, used by horizontal layout
+    pub details_width: u16,
+}
+
+impl PanesLayout {
+    pub fn new(ui_config: &UIConfig) -> Self {
+        Self {
+            kind: ui_config.layout,
+            details_height: ui_config
+                .details_height
+                .clamp(MIN_DETAILS_HEIGHT, MAX_DETAILS_HEIGHT),
+            details_width: ui_config
+                .details_width
+                .clamp(MIN_DETAILS_WIDTH, MAX_DETAILS_WIDTH),
+        }
+    }
+
+    //NOTE: resizing changes only size used by current layout, hidden details can't be resized
+    pub fn change(&mut self, change: LayoutChange) {
+        match (change, self.kind) {
+            (LayoutChange::Toggle, kind) => self.kind = kind.next(),
+            (LayoutChange::GrowDetails, LayoutKind::Vertical) => {
+                self.details_height = (self.details_height + 1).min(MAX_DETAILS_HEIGHT);
+            }
+            (LayoutChange::ShrinkDetails, LayoutKind::Vertical) => {
+                self.details_height = (self.details_height - 1).max(MIN_DETAILS_HEIGHT);
+            }
+            (LayoutChange::GrowDetails, LayoutKind::Horizontal) => {
+                self.details_width =
+                    (self.details_width + DETAILS_WIDTH_STEP).min(MAX_DETAILS_WIDTH);
+            }
+            (LayoutChange::ShrinkDetails, LayoutKind::Horizontal) => {
+                self.details_width =
+                    (self.details_width - DETAILS_WIDTH_STEP).max(MIN_DETAILS_WIDTH);
+            }
+            (_, LayoutKind::DetailsHidden) => (),
+        }
+    }
+}
+
+impl Default for PanesLayout {
+    fn default() -> Self {
+        Self {
+            kind: LayoutKind::Vertical,
+            details_height: 7,
+            details_width: 40,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+
+    use crate::tui::LayoutRects;
+
+    use super::*;
+
+    #[test]
+    fn should_cycle_layouts() {
+        let mut panes = PanesLayout::default();
+
+        panes.change(LayoutChange::Toggle);
+        assert_eq!(panes.kind, LayoutKind::Horizontal);
+        panes.change(LayoutChange::Toggle);
+        assert_eq!(panes.kind, LayoutKind::DetailsHidden);
+        panes.change(LayoutChange::Toggle);
+        assert_eq!(panes.kind, LayoutKind::Vertical);
+    }
+
+    #[test]
+    fn should_resize_details_of_current_layout_within_limits() {
+        let mut panes = PanesLayout::default();
+
+        panes.change(LayoutChange::GrowDetails);
+        assert_eq!(panes.details_height, 8);
+        for _ in 0..10 {
+            panes.change(LayoutChange::ShrinkDetails);
+        }
+        assert_eq!(panes.details_height, MIN_DETAILS_HEIGHT);
+        assert_eq!(panes.details_width, 40);
+
+        panes.change(LayoutChange::Toggle);
+        panes.change(LayoutChange::GrowDetails);
+        assert_eq!(panes.details_width, 45);
+        assert_eq!(panes.details_height, MIN_DETAILS_HEIGHT);
+    }
+
+    #[test]
+    fn should_place_details_next_to_table_in_horizontal_layout() {
+        let panes = PanesLayout {
+            kind: LayoutKind::Horizontal,
+            ..Default::default()
+        };
+
+        let layout = LayoutRects::new(Rect::new(0, 0, 100, 30), &panes);
+
+        assert_eq!(layout.process_table, Rect::new(0, 1, 60, 28));
+        assert_eq!(layout.process_details, Rect::new(60, 1, 40, 28));
+    }
+
+    #[test]
+    fn should_give_whole_height_to_table_when_details_are_hidden() {
+        let panes = PanesLayout {
+            kind: LayoutKind::DetailsHidden,
+            ..Default::default()
+        };
+
+        let layout = LayoutRects::new(Rect::new(0, 0, 100, 30), &panes);
+
+        assert_eq!(layout.process_table, Rect::new(0, 1, 100, 28));
+        assert!(layout.process_details.is_empty());
+    }
+
+    #[test]
+    fn should_stack_details_below_table_in_vertical_layout() {
+        let layout = LayoutRects::new(Rect::new(0, 0, 100, 30), &PanesLayout::default());
+
+        assert_eq!(layout.process_table, Rect::new(0, 1, 100, 21));
+        assert_eq!(layout.process_details, Rect::new(0, 22, 100, 7));
+        assert_eq!(layout.help_text.y, 29);
+    }
+}
@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+// older queries are dropped, history is meant for queries typed every day
+const MAX_ENTRIES: usize = 100;
+
+/// Queries user acted on, recalled like shell history and optionally persisted to state file
+#[derive(Debug, Default)]
+pub struct SearchHistory {
+    history_file: Option<PathBuf>,
+    // oldest first, without duplicates
+    entries: Vec<String>,
+    // entry shown in search bar while browsing history
+    recalled: Option<usize>,
+    // query typed before browsing started, it is restored after the newest entry
+    draft: String,
+}
+
+impl SearchHistory {
+    //NOTE: missing history file is not an error, history just starts empty
+    pub fn load(history_file: Option<PathBuf>) -> Self {
+        let entries = history_file
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|content| {
+                content
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            history_file,
+            entries,
+            recalled: None,
+            draft: String::new(),
+        }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Moves query to the end of history, empty queries are skipped
+    pub fn record(&mut self, query: &str) -> Result<()> {
+        let query = query.trim();
+        self.recalled = None;
+        if query.is_empty() || self.entries.last().is_some_and(|last| last == query) {
+            return Ok(());
+        }
+        self.entries.retain(|entry| entry != query);
+        self.entries.push(query.to_string());
+        let overflow = self.entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries.drain(..overflow);
+        self.persist()
+    }
+
+    //NOTE: when current query differs from recalled entry user edited it, browsing starts over then
+    pub fn previous(&mut self, current: &str) -> Option<&str> {
+        let index = match self.recalled {
+            Some(index) if self.is_recalled(index, current) => index.checked_sub(1)?,
+            _ => {
+                let index = self.entries.len().checked_sub(1)?;
+                self.draft = current.to_string();
+                index
+            }
+        };
+        self.recalled = Some(index);
+        Some(&self.entries[index])
+    }
+
+    pub fn next(&mut self, current: &str) -> Option<&str> {
+        let index = self
+            .recalled
+            .filter(|index| self.is_recalled(*index, current))?;
+        if index + 1 < self.entries.len() {
+            self.recalled = Some(index + 1);
+            return Some(&self.entries[index + 1]);
+        }
+        self.recalled = None;
+        Some(&self.draft)
+    }
+
+    fn is_recalled(&self, index: usize, current: &str) -> bool {
+        self.entries
+            .get(index)
+            .is_some_and(|entry| entry == current)
+    }
+
+    fn persist(&self) -> Result<()> {
+        let Some(path) = &self.history_file else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create state directory: {dir:?}"))?;
+        }
+        let mut content = self.entries.join("\n");
+        content.push('\n');
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to save search history to: {path:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_move_repeated_query_to_the_end() {
+        let mut history = SearchHistory::default();
+
+        history.record(":8080").unwrap();
+        history.record("-gradle").unwrap();
+        history.record(" :8080 ").unwrap();
+        history.record("").unwrap();
+
+        assert_eq!(history.entries(), &["-gradle", ":8080"].map(String::from));
+    }
+
+    #[test]
+    fn should_recall_entries_and_restore_typed_query() {
+        let mut history = SearchHistory::default();
+        history.record(":8080").unwrap();
+        history.record("-gradle").unwrap();
+
+        assert_eq!(history.previous("nod"), Some("-gradle"));
+        assert_eq!(history.previous("-gradle"), Some(":8080"));
+        assert_eq!(history.previous(":8080"), None);
+        assert_eq!(history.next(":8080"), Some("-gradle"));
+        assert_eq!(history.next("-gradle"), Some("nod"));
+        assert_eq!(history.next("nod"), None);
+    }
+
+    #[test]
+    fn should_start_over_when_recalled_query_was_edited() {
+        let mut history = SearchHistory::default();
+        history.record(":8080").unwrap();
+        history.record("-gradle").unwrap();
+
+        history.previous("");
+        assert_eq!(history.previous("-grad"), Some("-gradle"));
+        assert_eq!(history.next("-gradle"), Some("-grad"));
+    }
+
+    #[test]
+    fn should_persist_history() {
+        let history_file = std::env::temp_dir()
+            .join(format!("pik-search-history-{}", std::process::id()))
+            .join("search_history");
+        let mut history = SearchHistory::load(Some(history_file.clone()));
+        for i in 0..MAX_ENTRIES + 2 {
+            history.record(&format!("query{i}")).unwrap();
+        }
+
+        let loaded = SearchHistory::load(Some(history_file.clone()));
+
+        assert_eq!(loaded.entries().len(), MAX_ENTRIES);
+        assert_eq!(loaded.entries()[0], "query2");
+        assert_eq!(
+            loaded.entries().last().map(String::as_str),
+            Some(format!("query{}", MAX_ENTRIES + 1).as_str())
+        );
+        std::fs::remove_dir_all(history_file.parent().unwrap()).unwrap();
+    }
+}